@@ -0,0 +1,532 @@
+// AI commander
+//
+// Plays one team the way a player would: every few seconds it looks at the battlefield
+// (squad strength, enemy turrets, uplink towers) and hands out orders as GameCommands.
+// Every team hostile to it (see Diplomacy) is an enemy; allies are left alone.
+// Orders go through the same executor as player input, so AI battles record into replays;
+// during playback the commander is switched off with the rest of PlayerInputSet.
+//
+// Each decision:
+// 0. With the production queue empty, credits buy another squad at the tower (see economy.rs).
+// 1. Broken squads (too few survivors) fall back to a rally point behind their tower and hold.
+// 2. Enemies near the AI's own tower pull in the closest squads until the defenders outnumber them.
+// 3. Everything else is one assault force. It picks the best target (enemy tower - more
+//    attractive the more damaged it is - turrets, or weak enemy squads), weighing how well
+//    defended the target is. If the force is not strong enough yet it gathers and holds at a
+//    staging point out of range, then attack-moves in together.
+//
+// The commander only uses deterministic logic (no SimRng), and only issues a command when a
+// squad's order actually changes. Routing squads ignore orders (see obedient_squads), so
+// they get none and their last order is forgotten; once they rally they are ordered afresh.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::archetype::UnitArchetypes;
+use crate::constants::*;
+use crate::economy::{Economy, ProductionItem};
+use crate::game_command::{GameCommand, PlayerInputSet};
+use crate::scenario::{map_starts_new_battle, ScenarioLibrary, ScenarioState};
+use crate::selection::utils::{horizontal_direction, horizontal_distance};
+use crate::terrain::MapSwitchEvent;
+use crate::types::*;
+
+/// AI skill level
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AiDifficulty {
+    /// Slow to react, attacks piecemeal, ignores turrets and never retreats
+    Easy,
+    #[default]
+    Normal,
+    /// Reacts quickly, masses a clear advantage before attacking and saves broken squads early
+    Hard,
+}
+
+impl AiDifficulty {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "easy" => Some(AiDifficulty::Easy),
+            "normal" => Some(AiDifficulty::Normal),
+            "hard" => Some(AiDifficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// Seconds between decisions
+    fn decision_interval(self) -> f32 {
+        match self {
+            AiDifficulty::Easy => 6.0,
+            AiDifficulty::Normal => 3.0,
+            AiDifficulty::Hard => 1.5,
+        }
+    }
+
+    /// Squads below this fraction of their full size retreat
+    fn retreat_strength(self) -> f32 {
+        match self {
+            AiDifficulty::Easy => 0.0,
+            AiDifficulty::Normal => AI_BROKEN_SQUAD_STRENGTH,
+            AiDifficulty::Hard => AI_BROKEN_SQUAD_STRENGTH * 1.5,
+        }
+    }
+
+    /// Force advantage required before attacking (0 = attack immediately)
+    fn attack_ratio(self) -> f32 {
+        match self {
+            AiDifficulty::Easy => 0.0,
+            AiDifficulty::Normal => 1.0,
+            AiDifficulty::Hard => 1.5,
+        }
+    }
+
+    /// Whether enemy turrets count as threats and targets
+    fn weighs_turrets(self) -> bool {
+        self != AiDifficulty::Easy
+    }
+
+    fn defends_tower(self) -> bool {
+        self != AiDifficulty::Easy
+    }
+}
+
+/// Adds an AI commander for each listed team
+pub struct AiCommanderPlugin {
+    pub commanders: Vec<(Team, AiDifficulty)>,
+}
+
+impl AiCommanderPlugin {
+    /// AI for the teams given by --ai, at --ai-difficulty
+    pub fn from_args(args: &crate::cli::CliArgs) -> Self {
+        Self {
+            commanders: args.ai_teams().into_iter().map(|team| (team, args.ai_difficulty)).collect(),
+        }
+    }
+}
+
+impl Plugin for AiCommanderPlugin {
+    fn build(&self, app: &mut App) {
+        let commanders = self.commanders.iter()
+            .map(|&(team, difficulty)| AiCommander::new(team, difficulty))
+            .collect();
+
+        app.insert_resource(AiCommanders(commanders))
+            .add_systems(Update, ai_commander_system.in_set(PlayerInputSet));
+    }
+}
+
+/// All active AI commanders
+#[derive(Resource, Default)]
+pub struct AiCommanders(pub Vec<AiCommander>);
+
+/// What a squad has been told to do
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum AiOrder {
+    /// Attack-move onto a position
+    Attack(Vec3),
+    /// Move without stopping to fight (retreat, staging)
+    Regroup(Vec3),
+    /// Stand ground at a position
+    Hold(Vec3),
+}
+
+impl AiOrder {
+    /// Whether switching from `previous` to this order needs a new command
+    fn differs_from(&self, previous: Option<&AiOrder>) -> bool {
+        let close = |a: Vec3, b: Vec3| horizontal_distance(a, b) < AI_REORDER_DISTANCE;
+        match (self, previous) {
+            (AiOrder::Attack(a), Some(AiOrder::Attack(b))) => !close(*a, *b),
+            (AiOrder::Regroup(a), Some(AiOrder::Regroup(b))) => !close(*a, *b),
+            (AiOrder::Hold(a), Some(AiOrder::Hold(b))) => !close(*a, *b),
+            _ => true,
+        }
+    }
+}
+
+/// One AI-controlled team
+pub struct AiCommander {
+    pub team: Team,
+    pub difficulty: AiDifficulty,
+    decision_timer: Timer,
+    /// Last order given to each squad
+    orders: HashMap<u32, AiOrder>,
+    /// Current assault target
+    target: Option<Vec3>,
+}
+
+impl AiCommander {
+    pub fn new(team: Team, difficulty: AiDifficulty) -> Self {
+        Self {
+            team,
+            difficulty,
+            decision_timer: Timer::from_seconds(difficulty.decision_interval(), TimerMode::Repeating),
+            orders: HashMap::new(),
+            target: None,
+        }
+    }
+
+    /// Forget all orders (new battle)
+    fn reset(&mut self) {
+        self.orders.clear();
+        self.target = None;
+        self.decision_timer.reset();
+    }
+}
+
+// ===== BATTLEFIELD SNAPSHOT =====
+
+struct SquadInfo {
+    id: u32,
+    team: Team,
+    position: Vec3,
+    /// Where the squad was last ordered to
+    target: Vec3,
+    units: usize,
+    /// Units the squad's archetype fields at full strength
+    full_size: usize,
+    /// Routing squads drop every order the executor hands them
+    routing: bool,
+}
+
+struct TowerInfo {
+    team: Team,
+    position: Vec3,
+    health_fraction: f32,
+}
+
+/// Everything a commander looks at when deciding
+struct Battlefield {
+    diplomacy: Diplomacy,
+    squads: Vec<SquadInfo>,
+    towers: Vec<TowerInfo>,
+    turrets: Vec<(Team, Vec3)>,
+    credits: [f32; Team::MAX],
+    /// Orders in each team's production queue
+    queued: [usize; Team::MAX],
+    /// What a squad of DEFAULT_UNIT_ARCHETYPE costs
+    squad_cost: f32,
+}
+
+impl Battlefield {
+    /// Enemy infantry within `radius` of a position
+    fn enemy_units_near(&self, team: Team, position: Vec3, radius: f32) -> usize {
+        self.squads.iter()
+            .filter(|s| self.diplomacy.is_hostile(team, s.team) && horizontal_distance(s.position, position) < radius)
+            .map(|s| s.units)
+            .sum()
+    }
+
+    /// Enemy turrets within `radius` of a position
+    fn enemy_turrets_near(&self, team: Team, position: Vec3, radius: f32) -> usize {
+        self.turrets.iter()
+            .filter(|(t, pos)| self.diplomacy.is_hostile(team, *t) && horizontal_distance(*pos, position) < radius)
+            .count()
+    }
+}
+
+// ===== DECISION SYSTEM =====
+
+/// Run every commander whose decision timer has elapsed
+pub fn ai_commander_system(
+    time: Res<Time>,
+    mut ai: ResMut<AiCommanders>,
+    squad_manager: Res<SquadManager>,
+    game_state: Res<GameState>,
+    diplomacy: Res<Diplomacy>,
+    archetypes: Res<UnitArchetypes>,
+    economy: Res<Economy>,
+    (scenario_library, scenario_state): (Option<Res<ScenarioLibrary>>, Option<Res<ScenarioState>>),
+    tower_query: Query<(&Transform, &UplinkTower, &Health)>,
+    turret_query: Query<(&Transform, &TurretBase), With<Health>>,
+    mut map_switch_events: EventReader<MapSwitchEvent>,
+    mut game_commands: EventWriter<GameCommand>,
+) {
+    let new_battle = |map| map_starts_new_battle(map, scenario_library.as_deref(), scenario_state.as_deref());
+    if map_switch_events.read().filter(|event| new_battle(event.new_map)).count() > 0 {
+        for commander in ai.0.iter_mut() {
+            commander.reset();
+        }
+    }
+
+    // Scenario waves are scripted, and nothing is left to decide once the battle is over
+    if game_state.game_ended || scenario_state.map_or(false, |s| s.active) {
+        return;
+    }
+
+    let mut due = Vec::new();
+    for (index, commander) in ai.0.iter_mut().enumerate() {
+        if commander.decision_timer.tick(time.delta()).just_finished() {
+            due.push(index);
+        }
+    }
+    if due.is_empty() {
+        return;
+    }
+
+    // Sorted by id so decisions don't depend on HashMap order
+    let mut squads: Vec<SquadInfo> = squad_manager.squads.values()
+        .filter(|squad| !squad.members.is_empty())
+        .map(|squad| SquadInfo {
+            id: squad.id,
+            team: squad.team,
+            position: squad.center_position,
+            target: squad.destination(),
+            units: squad.members.len(),
+            full_size: archetypes.get(&squad.archetype).squad_size,
+            routing: squad.is_routing(),
+        })
+        .collect();
+    squads.sort_by_key(|s| s.id);
+
+    let battlefield = Battlefield {
+        diplomacy: diplomacy.clone(),
+        squads,
+        towers: tower_query.iter()
+            .filter(|(_, _, health)| !health.is_dead())
+            .map(|(transform, tower, health)| TowerInfo {
+                team: tower.team,
+                position: transform.translation,
+                health_fraction: health.health_percentage(),
+            })
+            .collect(),
+        turrets: turret_query.iter()
+            .map(|(transform, turret)| (turret.team, transform.translation))
+            .collect(),
+        credits: economy.credits,
+        queued: std::array::from_fn(|index| economy.queues[index].len()),
+        squad_cost: archetypes.get(DEFAULT_UNIT_ARCHETYPE).cost,
+    };
+
+    for index in due {
+        if let Some(command) = production_order(ai.0[index].team, &battlefield) {
+            game_commands.write(command);
+        }
+        for command in decide(&mut ai.0[index], &battlefield) {
+            game_commands.write(command);
+        }
+    }
+}
+
+/// Buy one more squad when the queue is empty and the credits are there
+fn production_order(team: Team, field: &Battlefield) -> Option<GameCommand> {
+    let index = team.index();
+    (field.queued[index] == 0 && field.credits[index] >= field.squad_cost).then(|| GameCommand::QueueProduction {
+        team,
+        item: ProductionItem::Squad { archetype: DEFAULT_UNIT_ARCHETYPE.to_string() },
+    })
+}
+
+/// Work out new orders for every squad of the commander's team
+fn decide(commander: &mut AiCommander, field: &Battlefield) -> Vec<GameCommand> {
+    let team = commander.team;
+    let difficulty = commander.difficulty;
+
+    let own: Vec<&SquadInfo> = field.squads.iter().filter(|s| s.team == team).collect();
+    let enemies: Vec<&SquadInfo> = field.squads.iter().filter(|s| field.diplomacy.is_hostile(team, s.team)).collect();
+    commander.orders.retain(|id, _| own.iter().any(|s| s.id == *id && !s.routing));
+    if own.is_empty() {
+        return Vec::new();
+    }
+
+    let own_tower = field.towers.iter().find(|t| t.team == team);
+    let home = own_tower.map(|t| t.position).unwrap_or_else(|| centroid(own.iter().map(|s| s.position)));
+
+    // With several enemies, the closest tower sets the front line
+    let enemy_tower = field.towers.iter()
+        .filter(|t| field.diplomacy.is_hostile(team, t.team))
+        .min_by(|a, b| horizontal_distance(a.position, home).total_cmp(&horizontal_distance(b.position, home)));
+    let enemy_home = match enemy_tower {
+        Some(tower) => tower.position,
+        None if !enemies.is_empty() => centroid(enemies.iter().map(|s| s.position)),
+        None => return Vec::new(),
+    };
+    let forward = horizontal_direction(home, enemy_home).normalize_or_zero();
+
+    let mut desired: Vec<(&SquadInfo, AiOrder)> = Vec::new();
+
+    // 1. Broken squads fall back behind their tower
+    let rally = home - forward * AI_RALLY_DISTANCE;
+    let mut healthy: Vec<&SquadInfo> = Vec::new();
+    for &squad in &own {
+        if (squad.units as f32) < difficulty.retreat_strength() * squad.full_size as f32 {
+            desired.push((squad, AiOrder::Regroup(rally)));
+        } else {
+            healthy.push(squad);
+        }
+    }
+
+    // 2. Defend the tower against anything that got close
+    if let Some(tower) = own_tower.filter(|_| difficulty.defends_tower()) {
+        let intruders: Vec<&&SquadInfo> = enemies.iter()
+            .filter(|s| horizontal_distance(s.position, tower.position) < AI_TOWER_DEFENSE_RADIUS)
+            .collect();
+
+        if !intruders.is_empty() {
+            let threat: usize = intruders.iter().map(|s| s.units).sum();
+            let threat_center = centroid(intruders.iter().map(|s| s.position));
+            let needed = threat as f32 * difficulty.attack_ratio().max(1.0);
+
+            healthy.sort_by(|a, b| {
+                let da = horizontal_distance(a.position, threat_center);
+                let db = horizontal_distance(b.position, threat_center);
+                da.total_cmp(&db).then(a.id.cmp(&b.id))
+            });
+
+            let mut defenders = 0.0;
+            while defenders < needed && !healthy.is_empty() {
+                let squad = healthy.remove(0);
+                defenders += squad.units as f32;
+                desired.push((squad, AiOrder::Attack(threat_center)));
+            }
+        }
+    }
+
+    // 3. Everyone else assaults the best target as one force
+    if !healthy.is_empty() {
+        healthy.sort_by_key(|s| s.id);
+        let force_units: usize = healthy.iter().map(|s| s.units).sum();
+        let force_center = centroid(healthy.iter().map(|s| s.position));
+
+        if let Some((target, defense)) = choose_target(commander, field, force_center, force_units as f32) {
+            let attack = force_units as f32 >= defense * difficulty.attack_ratio();
+            let order = if attack {
+                AiOrder::Attack(target)
+            } else {
+                // Gather just outside the target's reach, on our side of it
+                let mut back = horizontal_direction(target, force_center).normalize_or_zero();
+                if back == Vec3::ZERO {
+                    back = -forward;
+                }
+                AiOrder::Regroup(target + back * (TARGETING_RANGE + AI_ARRIVAL_RADIUS))
+            };
+            for &squad in &healthy {
+                desired.push((squad, order));
+            }
+        }
+    }
+
+    let desired = desired.into_iter()
+        .filter(|(squad, _)| !squad.routing)
+        .map(|(squad, order)| (squad.id, settle(order, squad, commander.orders.get(&squad.id))))
+        .collect();
+    issue_orders(commander, desired, forward)
+}
+
+/// Pick the assault target with the best value for its defenses.
+/// Returns the target position and its defense (infantry plus weighted turrets).
+fn choose_target(commander: &mut AiCommander, field: &Battlefield, force_center: Vec3, force_units: f32) -> Option<(Vec3, f32)> {
+    let team = commander.team;
+    let weighs_turrets = commander.difficulty.weighs_turrets();
+
+    let mut candidates: Vec<(Vec3, f32)> = Vec::new();
+    for tower in field.towers.iter().filter(|t| field.diplomacy.is_hostile(team, t.team)) {
+        candidates.push((tower.position, 1.0 + 2.0 * (1.0 - tower.health_fraction)));
+    }
+    if weighs_turrets {
+        for (_, position) in field.turrets.iter().filter(|(t, _)| field.diplomacy.is_hostile(team, *t)) {
+            candidates.push((*position, 0.8));
+        }
+    }
+    for squad in field.squads.iter().filter(|s| field.diplomacy.is_hostile(team, s.team)) {
+        let weakness = 1.0 - squad.units as f32 / squad.full_size as f32;
+        candidates.push((squad.position, 0.5 + 0.5 * weakness));
+    }
+
+    let defense_at = |position: Vec3| {
+        let mut defense = field.enemy_units_near(team, position, TARGETING_RANGE) as f32;
+        if weighs_turrets {
+            defense += field.enemy_turrets_near(team, position, AI_TURRET_THREAT_RADIUS) as f32 * AI_TURRET_THREAT_UNITS;
+        }
+        defense
+    };
+    let score_of = |(position, value): (Vec3, f32)| {
+        let defense = defense_at(position);
+        let distance = horizontal_distance(force_center, position) / BATTLEFIELD_SIZE;
+        value * force_units / (force_units + defense) - 0.5 * distance
+    };
+
+    let best = candidates.iter()
+        .copied()
+        .map(|candidate| (candidate.0, score_of(candidate)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    // Stick with the current target (it may have moved a little) unless something is clearly better
+    let current = commander.target.and_then(|target| {
+        candidates.iter()
+            .copied()
+            .filter(|(position, _)| horizontal_distance(*position, target) < AI_REORDER_DISTANCE)
+            .map(|candidate| (candidate.0, score_of(candidate)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    });
+
+    let chosen = match current {
+        Some(current) if best.1 < current.1 + AI_TARGET_SWITCH_MARGIN => current.0,
+        _ => best.0,
+    };
+
+    if commander.target.map_or(true, |target| horizontal_distance(target, chosen) >= AI_REORDER_DISTANCE) {
+        info!("AI (Team {:?}): new assault target at ({:.0}, {:.0})", team, chosen.x, chosen.z);
+    }
+    commander.target = Some(chosen);
+    Some((chosen, defense_at(chosen)))
+}
+
+/// A squad that finished its Regroup move holds there
+fn settle(order: AiOrder, squad: &SquadInfo, previous: Option<&AiOrder>) -> AiOrder {
+    let AiOrder::Regroup(point) = order else {
+        return order;
+    };
+    let already_sent = match previous {
+        Some(AiOrder::Regroup(p) | AiOrder::Hold(p)) => horizontal_distance(*p, point) < AI_REORDER_DISTANCE,
+        _ => false,
+    };
+    if already_sent && horizontal_distance(squad.position, squad.target) < AI_ARRIVAL_RADIUS {
+        AiOrder::Hold(point)
+    } else {
+        order
+    }
+}
+
+/// Turn changed orders into commands, batching squads that share an order.
+/// Large batches are split into lines of AI_SQUADS_PER_LINE, stacked back from the
+/// destination along `forward`, so a whole army doesn't spread into one enormous line.
+fn issue_orders(commander: &mut AiCommander, desired: Vec<(u32, AiOrder)>, forward: Vec3) -> Vec<GameCommand> {
+    let mut batches: Vec<(AiOrder, Vec<u32>)> = Vec::new();
+    for (squad_id, order) in desired {
+        if !order.differs_from(commander.orders.get(&squad_id)) {
+            continue;
+        }
+        commander.orders.insert(squad_id, order);
+
+        match batches.iter_mut().find(|(batch_order, _)| *batch_order == order) {
+            Some((_, squad_ids)) => squad_ids.push(squad_id),
+            None => batches.push((order, vec![squad_id])),
+        }
+    }
+
+    let mut commands = Vec::new();
+    for (order, squad_ids) in batches {
+        let (destination, mode) = match order {
+            AiOrder::Attack(destination) => (destination, MovementMode::AttackMove),
+            AiOrder::Regroup(destination) => (destination, MovementMode::Move),
+            AiOrder::Hold(_) => {
+                commands.push(GameCommand::SetMovementMode { squad_ids, mode: MovementMode::Hold });
+                continue;
+            }
+        };
+
+        for (line, chunk) in squad_ids.chunks(AI_SQUADS_PER_LINE).enumerate() {
+            commands.push(GameCommand::MoveSquads {
+                squad_ids: chunk.to_vec(),
+                destination: destination - forward * (line as f32 * AI_LINE_DEPTH),
+                facing: None,
+                mode,
+            });
+        }
+    }
+    commands
+}
+
+fn centroid(positions: impl Iterator<Item = Vec3>) -> Vec3 {
+    let (sum, count) = positions.fold((Vec3::ZERO, 0), |(sum, count), p| (sum + p, count + 1));
+    if count == 0 { Vec3::ZERO } else { sum / count as f32 }
+}
@@ -0,0 +1,232 @@
+// Unit archetypes - data-driven infantry definitions
+//
+// Each .ron file in assets/units describes one unit type (the file stem is its ID):
+// health, speed, turn rate, mass, weapon, accuracy, mesh, how many units a squad fields and
+// what a squad costs to produce (see economy.rs).
+// Files are read once at startup, so designers can add or tune unit types without
+// recompiling. Missing fields fall back to the battle droid defaults in constants.rs,
+// and the built-in "battle_droid" archetype is always available.
+//
+// spawn_single_squad takes an archetype ID; every spawned unit carries a UnitStats copy
+// of its archetype so hot combat/movement loops don't look anything up.
+//
+// Example (assets/units/battle_droid.ron):
+//   (
+//       name: "Battle Droid",
+//       health: 50.0,
+//       speed: 3.0,
+//       mass: 1.0,
+//       armor: Infantry,
+//       weapon: (weapon_type: PulsedLaser, damage: 25.0, fire_interval: 2.0, range: 200.0, accuracy: 0.7),
+//       mesh: BattleDroid,
+//       scale: 0.8,
+//       squad_size: 50,
+//       cost: 250.0,
+//       build_time: 20.0,
+//   )
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::constants::*;
+use crate::types::{ArmorClass, WeaponType};
+
+/// Procedural mesh a unit type is drawn with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UnitMesh {
+    /// Humanoid droid body with a separate head child
+    BattleDroid,
+    /// Two-legged armored platform with a turret gun
+    Walker,
+}
+
+impl UnitMesh {
+    fn create(self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        match self {
+            UnitMesh::BattleDroid => crate::setup::create_droid_mesh(meshes),
+            UnitMesh::Walker => crate::procedural_meshes::create_walker_mesh(meshes),
+        }
+    }
+
+    /// Whether the mesh is drawn with a head child entity
+    pub fn has_head(self) -> bool {
+        matches!(self, UnitMesh::BattleDroid)
+    }
+}
+
+/// Small arms carried by every unit of an archetype
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeaponStats {
+    pub weapon_type: WeaponType, // Row of DAMAGE_MATRIX
+    pub damage: f32,        // Base damage per hit, before the armor multiplier
+    pub fire_interval: f32, // Seconds between shots
+    pub range: f32,         // Targeting range (towers are engaged from 1.5x)
+    pub accuracy: f32,      // Base hit chance before modifiers
+}
+
+impl Default for WeaponStats {
+    fn default() -> Self {
+        Self {
+            weapon_type: WeaponType::PulsedLaser,
+            damage: HITSCAN_DAMAGE,
+            fire_interval: AUTO_FIRE_INTERVAL,
+            range: TARGETING_RANGE,
+            accuracy: INFANTRY_BASE_ACCURACY,
+        }
+    }
+}
+
+/// One unit type, as read from assets/units/<id>.ron
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitArchetype {
+    pub name: String,
+    pub health: f32,
+    pub speed: f32,         // March speed (units/second)
+    pub turn_rate: f32,     // Max turn speed (radians/second), infinite = turns instantly
+    pub mass: f32,          // Collision push weight (see UnitMass)
+    pub knockback_immune: bool, // Explosions can't throw it (heavy platforms)
+    pub armor: ArmorClass,
+    pub weapon: WeaponStats,
+    pub mesh: UnitMesh,
+    pub scale: f32,         // Mesh scale (commanders are drawn slightly larger)
+    pub squad_size: usize,  // Units per squad, at most SQUAD_SIZE
+    pub cost: f32,          // Credits to produce one squad
+    pub build_time: f32,    // Seconds to produce one squad
+}
+
+impl Default for UnitArchetype {
+    fn default() -> Self {
+        Self {
+            name: "Battle Droid".to_string(),
+            health: INFANTRY_HEALTH,
+            speed: MARCH_SPEED,
+            turn_rate: f32::INFINITY,
+            mass: DEFAULT_UNIT_MASS,
+            knockback_immune: false,
+            armor: ArmorClass::Infantry,
+            weapon: WeaponStats::default(),
+            mesh: UnitMesh::BattleDroid,
+            scale: 0.8,
+            squad_size: SQUAD_SIZE,
+            cost: SQUAD_COST,
+            build_time: SQUAD_BUILD_TIME,
+        }
+    }
+}
+
+impl UnitArchetype {
+    /// Per-unit copy of the stats used by combat and movement
+    pub fn stats(&self) -> UnitStats {
+        UnitStats {
+            speed: self.speed,
+            turn_rate: self.turn_rate,
+            knockback_immune: self.knockback_immune,
+            accuracy: self.weapon.accuracy,
+            weapon: self.weapon.weapon_type,
+            damage: self.weapon.damage,
+            fire_interval: self.weapon.fire_interval,
+            range: self.weapon.range,
+        }
+    }
+}
+
+/// Archetype stats stamped onto each spawned unit
+#[derive(Component, Clone, Copy, Debug)]
+pub struct UnitStats {
+    pub speed: f32,
+    pub turn_rate: f32,
+    pub knockback_immune: bool,
+    pub accuracy: f32,
+    pub weapon: WeaponType,
+    pub damage: f32,
+    pub fire_interval: f32,
+    pub range: f32,
+}
+
+/// Every known unit archetype, keyed by ID
+#[derive(Resource)]
+pub struct UnitArchetypes {
+    archetypes: HashMap<String, UnitArchetype>,
+    mesh_handles: HashMap<UnitMesh, Handle<Mesh>>,
+}
+
+impl Default for UnitArchetypes {
+    /// Only the built-in battle droid
+    fn default() -> Self {
+        let mut archetypes = HashMap::new();
+        archetypes.insert(DEFAULT_UNIT_ARCHETYPE.to_string(), UnitArchetype::default());
+        Self { archetypes, mesh_handles: HashMap::new() }
+    }
+}
+
+impl UnitArchetypes {
+    /// Read every .ron file in `dir` on top of the built-in battle droid.
+    /// Unreadable files are logged and skipped so one typo doesn't stop the game.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Self {
+        let mut result = Self::default();
+        let dir = dir.as_ref();
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("No unit archetypes loaded from {}: {}", dir.display(), e);
+                return result;
+            }
+        };
+
+        // Sorted so load order (and log output) is stable across platforms
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else {
+                continue;
+            };
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| ron::from_str::<UnitArchetype>(&text).map_err(|e| e.to_string()));
+            match parsed {
+                Ok(mut archetype) => {
+                    archetype.squad_size = archetype.squad_size.clamp(1, SQUAD_SIZE);
+                    info!("Unit archetype '{}': {} ({} per squad)", id, archetype.name, archetype.squad_size);
+                    result.archetypes.insert(id, archetype);
+                }
+                Err(e) => warn!("Skipping unit archetype {}: {}", path.display(), e),
+            }
+        }
+
+        result
+    }
+
+    /// Look up an archetype, falling back to the battle droid for unknown IDs
+    pub fn get(&self, id: &str) -> &UnitArchetype {
+        self.archetypes.get(id).unwrap_or_else(|| {
+            warn!("Unknown unit archetype '{}', using '{}'", id, DEFAULT_UNIT_ARCHETYPE);
+            &self.archetypes[DEFAULT_UNIT_ARCHETYPE]
+        })
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.archetypes.contains_key(id)
+    }
+
+    /// Known archetype IDs, sorted
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.archetypes.keys().map(String::as_str).collect();
+        ids.sort();
+        ids
+    }
+
+    /// Shared mesh handle for a unit mesh, created on first use so all units of a
+    /// type batch together
+    pub fn mesh(&mut self, mesh: UnitMesh, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        self.mesh_handles.entry(mesh).or_insert_with(|| mesh.create(meshes)).clone()
+    }
+}
@@ -1,9 +1,13 @@
 // Area damage system - handles explosion damage zones and knockback physics
-// Three zones: Core (instant death), Mid (RNG death), Rim (knockback only)
+// Three zones: Core (instant death), Mid (RNG death, survivors take shrapnel), Rim (knockback only)
+// The death zones only apply to infantry: armored units (walkers) take blast damage through
+// Health instead, scaled by DAMAGE_MATRIX and falling off from the core to the mid boundary.
+// Knockback-immune units (heavy walkers) are never thrown, see knockback_physics_system
 
 use bevy::prelude::*;
 use rand::Rng;
 
+use crate::archetype::UnitStats;
 use crate::constants::*;
 use crate::explosion_shader::{spawn_custom_shader_explosion, ExplosionAssets, ExplosionMaterial};
 use crate::terrain::TerrainHeightmap;
@@ -51,20 +55,28 @@ pub fn area_damage_system(
     mut meshes: ResMut<Assets<Mesh>>,
     mut explosion_materials: ResMut<Assets<ExplosionMaterial>>,
     time: Res<Time>,
+    mut sim_rng: ResMut<SimRng>,
+    diplomacy: Res<Diplomacy>,
     droid_query: Query<(Entity, &Transform, &BattleDroid), (Without<KnockbackState>, Without<RagdollDeath>)>,
+    mut health_query: Query<(&mut Health, &ArmorClass), With<SquadMember>>,
 ) {
-    let mut rng = rand::thread_rng();
+    let rng = &mut *sim_rng;
 
     for event in events.read() {
         let core_radius = AREA_DAMAGE_CORE_RADIUS * event.scale;
         let mid_radius = AREA_DAMAGE_MID_RADIUS * event.scale;
         let rim_radius = AREA_DAMAGE_RIM_RADIUS * event.scale;
 
+        // Squad credited with the kills, if the blast came from one
+        let source = event.source_squad
+            .and_then(|squad_id| squad_manager.get_squad(squad_id))
+            .map(|squad| (squad.id, squad.team));
+
         // Get nearby droids using spatial grid
         let nearby = spatial_grid.get_nearby_droids(event.position);
 
         for &entity in &nearby {
-            let Ok((_, transform, _droid)) = droid_query.get(entity) else {
+            let Ok((_, transform, droid)) = droid_query.get(entity) else {
                 continue;
             };
 
@@ -92,8 +104,70 @@ pub fn area_damage_system(
                 0.0,
             );
 
-            if distance <= core_radius {
-                // CORE ZONE: Instant death
+            // Only enemy casualties count towards the source squad's veterancy
+            let credited_squad = source
+                .filter(|&(_, team)| diplomacy.is_hostile(team, droid.team))
+                .map(|(squad_id, _)| squad_id);
+
+            // Formation exposure: spread-out formations lose fewer men to a blast
+            let exposure = squad_manager.unit_formation(entity).map_or(1.0, |f| f.area_damage_modifier());
+
+            let armor = health_query.get(entity).map_or(ArmorClass::Infantry, |(_, armor)| *armor);
+            if armor != ArmorClass::Infantry {
+                // ARMORED: no instant or RNG death, the blast wears down Health like any other hit
+                if distance > mid_radius {
+                    apply_knockback(
+                        &mut commands,
+                        entity,
+                        direction,
+                        ground_y,
+                        event.scale,
+                        transform.rotation,
+                        rng,
+                    );
+                    continue;
+                }
+                let t = ((distance - core_radius) / (mid_radius - core_radius)).max(0.0);
+                let damage = AREA_DAMAGE_WEAPON.damage_against(AREA_DAMAGE_BLAST * (1.0 - t) * exposure, armor);
+                let killed = health_query.get_mut(entity).is_ok_and(|(mut health, _)| {
+                    health.damage(damage);
+                    health.is_dead()
+                });
+
+                if killed {
+                    apply_death_effect(
+                        &mut commands,
+                        &mut squad_manager,
+                        entity,
+                        transform.translation,
+                        direction,
+                        ground_y,
+                        explosion_assets.as_deref(),
+                        &mut meshes,
+                        &mut explosion_materials,
+                        time.elapsed_secs_f64(),
+                        rng,
+                    );
+                    if let Some(squad_id) = credited_squad {
+                        squad_manager.credit_kill(squad_id);
+                    }
+                } else {
+                    // Knockback-immune survivors stay put (see knockback_physics_system)
+                    apply_knockback(
+                        &mut commands,
+                        entity,
+                        direction,
+                        ground_y,
+                        event.scale,
+                        transform.rotation,
+                        rng,
+                    );
+                }
+                continue;
+            }
+
+            if distance <= core_radius && (exposure >= 1.0 || rng.gen::<f32>() < exposure) {
+                // CORE ZONE: Instant death (unless a loose formation gave the unit room to dive)
                 apply_death_effect(
                     &mut commands,
                     &mut squad_manager,
@@ -105,15 +179,25 @@ pub fn area_damage_system(
                     &mut meshes,
                     &mut explosion_materials,
                     time.elapsed_secs_f64(),
-                    &mut rng,
+                    rng,
                 );
+                if let Some(squad_id) = credited_squad {
+                    squad_manager.credit_kill(squad_id);
+                }
             } else if distance <= mid_radius {
                 // MID ZONE: RNG death (probability decreases with distance)
-                // At core boundary: 80% death, at mid boundary: 20% death
-                let t = (distance - core_radius) / (mid_radius - core_radius);
-                let death_probability = 0.8 - (t * 0.6);
-
-                if rng.gen::<f32>() < death_probability {
+                // At core boundary: 80% death, at mid boundary: 20% death (before formation exposure)
+                let t = ((distance - core_radius) / (mid_radius - core_radius)).max(0.0);
+                let death_probability = ((0.8 - (t * 0.6)) * exposure).min(1.0);
+
+                // Survivors still catch shrapnel, which finishes off the already wounded
+                let killed = rng.gen::<f32>() < death_probability
+                    || health_query.get_mut(entity).is_ok_and(|(mut health, _)| {
+                        health.damage(AREA_DAMAGE_SHRAPNEL * exposure);
+                        health.is_dead()
+                    });
+
+                if killed {
                     apply_death_effect(
                         &mut commands,
                         &mut squad_manager,
@@ -125,8 +209,11 @@ pub fn area_damage_system(
                         &mut meshes,
                         &mut explosion_materials,
                         time.elapsed_secs_f64(),
-                        &mut rng,
+                        rng,
                     );
+                    if let Some(squad_id) = credited_squad {
+                        squad_manager.credit_kill(squad_id);
+                    }
                 } else {
                     // Survived mid zone - apply knockback instead
                     apply_knockback(
@@ -136,7 +223,7 @@ pub fn area_damage_system(
                         ground_y,
                         event.scale,
                         transform.rotation,
-                        &mut rng,
+                        rng,
                     );
                 }
             } else {
@@ -148,7 +235,7 @@ pub fn area_damage_system(
                     ground_y,
                     event.scale,
                     transform.rotation,
-                    &mut rng,
+                    rng,
                 );
             }
         }
@@ -283,11 +370,18 @@ pub fn knockback_physics_system(
     mut commands: Commands,
     time: Res<Time>,
     heightmap: Option<Res<TerrainHeightmap>>,
-    mut query: Query<(Entity, &mut Transform, &mut KnockbackState)>,
+    mut query: Query<(Entity, &mut Transform, &mut KnockbackState, Option<&UnitStats>)>,
 ) {
     let dt = time.delta_secs();
 
-    for (entity, mut transform, mut knockback) in query.iter_mut() {
+    for (entity, mut transform, mut knockback, stats) in query.iter_mut() {
+        // Heavy platforms shrug the blast off: no flight, no stun
+        if stats.is_some_and(|s| s.knockback_immune) {
+            transform.rotation = knockback.original_rotation;
+            commands.entity(entity).remove::<KnockbackState>();
+            continue;
+        }
+
         // Get current terrain height (mesh origin is at feet, so this is the ground level)
         let ground_y = sample_terrain_height(
             heightmap.as_deref(),
@@ -1,5 +1,7 @@
 // Artillery barrage system - player-controlled artillery strikes
 // Three variants: Single shot, Scatter barrage, Line barrage
+// Scatter and line barrages cost credits and are queued as production orders (see economy.rs);
+// the single shot is a free debug strike.
 
 use bevy::prelude::*;
 use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
@@ -8,6 +10,8 @@ use rand::Rng;
 
 use crate::area_damage::sample_terrain_height;
 use crate::constants::*;
+use crate::economy::ProductionItem;
+use crate::game_command::{GameCommand, PlayerInputSet};
 use crate::ground_explosion::{spawn_ground_explosion, spawn_impact_flash, FlipbookMaterial, GroundExplosionAssets};
 use crate::particles::{
     spawn_ground_explosion_gpu_sparks,
@@ -17,12 +21,29 @@ use crate::particles::{
     spawn_ground_explosion_gpu_smoke,
     spawn_ground_explosion_gpu_wisp,
 };
+use crate::scenario::ScenarioState;
 use crate::selection::utils::screen_to_ground_with_heightmap;
 use crate::selection::visuals::movement::create_arrow_mesh;
 use crate::terrain::TerrainHeightmap;
 use crate::types::*;
 use crate::wfx_materials::AdditiveMaterial;
 
+/// Player artillery: V/B/N hotkey targeting, cursor/line visuals and delayed shell impacts
+pub struct ArtilleryPlugin;
+
+impl Plugin for ArtilleryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ArtilleryState::default())
+            .add_systems(Update, (
+                artillery_input_system.in_set(PlayerInputSet),
+                artillery_visual_system,
+                artillery_cursor_system,
+            ))
+            // Shell delays count down in simulation time so impacts land on the same tick
+            .add_systems(FixedUpdate, artillery_spawn_system.before(crate::area_damage::area_damage_system));
+    }
+}
+
 // ===== RESOURCES & COMPONENTS =====
 
 /// Artillery mode selection
@@ -31,8 +52,8 @@ pub enum ArtilleryMode {
     #[default]
     None,
     SingleShot,     // F5: Single explosion at cursor (debug)
-    ScatterBarrage, // F6: 6-10 shells scattered around cursor
-    LineBarrage,    // F7: Shells along a dragged line
+    ScatterBarrage, // F6: 6-10 shells scattered around cursor (paid, queued)
+    LineBarrage,    // F7: Shells along a dragged line (paid, queued)
 }
 
 /// Artillery state resource
@@ -66,6 +87,17 @@ impl ArtilleryState {
     }
 }
 
+/// An artillery strike requested by the player (or AI/scripts) via GameCommand::FireArtillery
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ArtilleryBarrage {
+    /// One immediate shell
+    Single { position: Vec3 },
+    /// 6-10 shells scattered around a point
+    Scatter { center: Vec3 },
+    /// Shells walked along a line (clamped to ARTILLERY_LINE_MAX_LENGTH)
+    Line { start: Vec3, end: Vec3 },
+}
+
 /// A pending artillery shell waiting to land
 pub struct PendingShell {
     pub position: Vec3,
@@ -91,7 +123,12 @@ pub fn artillery_input_system(
     camera_query: Query<(&Camera, &GlobalTransform), With<RtsCamera>>,
     heightmap: Option<Res<TerrainHeightmap>>,
     mut artillery_state: ResMut<ArtilleryState>,
+    scenario_state: Res<ScenarioState>,
+    mut game_commands: EventWriter<GameCommand>,
 ) {
+    // Barrages are paid for from the player's credits
+    let team = scenario_state.player_team();
+
     // Toggle modes with V/B/N
     if keyboard.just_pressed(KeyCode::KeyV) {
         artillery_state.toggle_mode(
@@ -127,6 +164,10 @@ pub fn artillery_input_system(
     let Some(cursor_pos) = window.cursor_position() else {
         return;
     };
+    // Clicks on the minimap don't fire
+    if crate::minimap::cursor_over_minimap(window) {
+        return;
+    }
 
     let hm = heightmap.as_ref().map(|h| h.as_ref());
     let current_world_pos = screen_to_ground_with_heightmap(cursor_pos, camera, camera_transform, hm);
@@ -135,13 +176,8 @@ pub fn artillery_input_system(
         ArtilleryMode::SingleShot => {
             // Left click to fire single shell
             if mouse_button.just_pressed(MouseButton::Left) {
-                if let Some(pos) = current_world_pos {
-                    artillery_state.pending_shells.push(PendingShell {
-                        position: pos,
-                        delay: 0.0, // Immediate
-                        scale: 1.0,
-                    });
-                    info!("Artillery: Single shell at {:?}", pos);
+                if let Some(position) = current_world_pos {
+                    game_commands.write(GameCommand::FireArtillery(ArtilleryBarrage::Single { position }));
                 }
             }
         }
@@ -149,33 +185,10 @@ pub fn artillery_input_system(
             // Left click to call scatter barrage
             if mouse_button.just_pressed(MouseButton::Left) {
                 if let Some(center) = current_world_pos {
-                    let mut rng = rand::thread_rng();
-                    let shell_count =
-                        rng.gen_range(ARTILLERY_SHELL_COUNT_MIN..=ARTILLERY_SHELL_COUNT_MAX);
-
-                    for _ in 0..shell_count {
-                        let offset = Vec3::new(
-                            rng.gen_range(-ARTILLERY_SCATTER_RADIUS..ARTILLERY_SCATTER_RADIUS),
-                            0.0,
-                            rng.gen_range(-ARTILLERY_SCATTER_RADIUS..ARTILLERY_SCATTER_RADIUS),
-                        );
-                        let delay =
-                            rng.gen_range(ARTILLERY_SHELL_DELAY_MIN..ARTILLERY_SHELL_DELAY_MAX);
-
-                        // Sample terrain height at shell position
-                        let shell_pos = center + offset;
-                        let y = sample_terrain_height(hm, shell_pos.x, shell_pos.z, 0.0);
-
-                        artillery_state.pending_shells.push(PendingShell {
-                            position: Vec3::new(shell_pos.x, y, shell_pos.z),
-                            delay,
-                            scale: 1.0,
-                        });
-                    }
-                    info!(
-                        "Artillery: Scatter barrage ({} shells) around {:?}",
-                        shell_count, center
-                    );
+                    game_commands.write(GameCommand::QueueProduction {
+                        team,
+                        item: ProductionItem::Artillery(ArtilleryBarrage::Scatter { center }),
+                    });
                 }
             }
         }
@@ -201,52 +214,10 @@ pub fn artillery_input_system(
                 if let (Some(start), Some(end)) =
                     (artillery_state.line_start, artillery_state.line_current)
                 {
-                    let direction = end - start;
-                    let mut line_length = direction.length();
-
-                    // Clamp to max length
-                    if line_length > ARTILLERY_LINE_MAX_LENGTH {
-                        line_length = ARTILLERY_LINE_MAX_LENGTH;
-                    }
-
-                    if line_length > 1.0 {
-                        let dir_normalized = direction.normalize();
-                        let shell_count =
-                            (line_length / ARTILLERY_LINE_SHELL_SPACING).ceil() as usize;
-                        let shell_count = shell_count.max(2); // At least 2 shells
-
-                        let mut rng = rand::thread_rng();
-
-                        for i in 0..shell_count {
-                            let t = if shell_count > 1 {
-                                i as f32 / (shell_count - 1) as f32
-                            } else {
-                                0.5
-                            };
-                            let base_pos = start + dir_normalized * (t * line_length);
-
-                            // Add small random scatter perpendicular to line
-                            let perp = Vec3::new(-dir_normalized.z, 0.0, dir_normalized.x);
-                            let scatter = perp * rng.gen_range(-3.0..3.0);
-                            let shell_pos = base_pos + scatter;
-
-                            // Sample terrain height
-                            let y = sample_terrain_height(hm, shell_pos.x, shell_pos.z, 0.0);
-
-                            // Stagger timing along line
-                            let delay = i as f32 * 0.25 + rng.gen_range(0.0..0.1);
-
-                            artillery_state.pending_shells.push(PendingShell {
-                                position: Vec3::new(shell_pos.x, y, shell_pos.z),
-                                delay,
-                                scale: 1.0,
-                            });
-                        }
-                        info!(
-                            "Artillery: Line barrage ({} shells) from {:?} to {:?}",
-                            shell_count, start, end
-                        );
-                    }
+                    game_commands.write(GameCommand::QueueProduction {
+                        team,
+                        item: ProductionItem::Artillery(ArtilleryBarrage::Line { start, end }),
+                    });
                 }
 
                 // Reset drag state
@@ -257,6 +228,94 @@ pub fn artillery_input_system(
     }
 }
 
+/// Queue the shells for a barrage. Scatter and line jitter use the simulation RNG so
+/// barrages land identically in replays.
+pub fn queue_artillery_barrage(
+    artillery_state: &mut ArtilleryState,
+    barrage: ArtilleryBarrage,
+    hm: Option<&TerrainHeightmap>,
+    rng: &mut impl Rng,
+) {
+    match barrage {
+        ArtilleryBarrage::Single { position } => {
+            artillery_state.pending_shells.push(PendingShell {
+                position,
+                delay: 0.0, // Immediate
+                scale: 1.0,
+            });
+            info!("Artillery: Single shell at {:?}", position);
+        }
+        ArtilleryBarrage::Scatter { center } => {
+            let shell_count =
+                rng.gen_range(ARTILLERY_SHELL_COUNT_MIN..=ARTILLERY_SHELL_COUNT_MAX);
+
+            for _ in 0..shell_count {
+                let offset = Vec3::new(
+                    rng.gen_range(-ARTILLERY_SCATTER_RADIUS..ARTILLERY_SCATTER_RADIUS),
+                    0.0,
+                    rng.gen_range(-ARTILLERY_SCATTER_RADIUS..ARTILLERY_SCATTER_RADIUS),
+                );
+                let delay =
+                    rng.gen_range(ARTILLERY_SHELL_DELAY_MIN..ARTILLERY_SHELL_DELAY_MAX);
+
+                // Sample terrain height at shell position
+                let shell_pos = center + offset;
+                let y = sample_terrain_height(hm, shell_pos.x, shell_pos.z, 0.0);
+
+                artillery_state.pending_shells.push(PendingShell {
+                    position: Vec3::new(shell_pos.x, y, shell_pos.z),
+                    delay,
+                    scale: 1.0,
+                });
+            }
+            info!(
+                "Artillery: Scatter barrage ({} shells) around {:?}",
+                shell_count, center
+            );
+        }
+        ArtilleryBarrage::Line { start, end } => {
+            let direction = end - start;
+            // Clamp to max length
+            let line_length = direction.length().min(ARTILLERY_LINE_MAX_LENGTH);
+
+            if line_length <= 1.0 {
+                return;
+            }
+
+            let dir_normalized = direction.normalize();
+            let shell_count =
+                (line_length / ARTILLERY_LINE_SHELL_SPACING).ceil() as usize;
+            let shell_count = shell_count.max(2); // At least 2 shells
+
+            for i in 0..shell_count {
+                let t = i as f32 / (shell_count - 1) as f32;
+                let base_pos = start + dir_normalized * (t * line_length);
+
+                // Add small random scatter perpendicular to line
+                let perp = Vec3::new(-dir_normalized.z, 0.0, dir_normalized.x);
+                let scatter = perp * rng.gen_range(-3.0..3.0);
+                let shell_pos = base_pos + scatter;
+
+                // Sample terrain height
+                let y = sample_terrain_height(hm, shell_pos.x, shell_pos.z, 0.0);
+
+                // Stagger timing along line
+                let delay = i as f32 * 0.25 + rng.gen_range(0.0..0.1);
+
+                artillery_state.pending_shells.push(PendingShell {
+                    position: Vec3::new(shell_pos.x, y, shell_pos.z),
+                    delay,
+                    scale: 1.0,
+                });
+            }
+            info!(
+                "Artillery: Line barrage ({} shells) from {:?} to {:?}",
+                shell_count, start, end
+            );
+        }
+    }
+}
+
 /// Update artillery line visual (red arrow for line barrage)
 pub fn artillery_visual_system(
     mut commands: Commands,
@@ -416,7 +475,7 @@ pub fn artillery_spawn_system(
             }
 
             // Fire area damage event
-            area_damage_events.write(AreaDamageEvent { position, scale });
+            area_damage_events.write(AreaDamageEvent { position, scale, source_squad: None });
         }
     }
 }
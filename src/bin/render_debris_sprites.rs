@@ -10,6 +10,8 @@ use bevy::render::camera::ClearColorConfig;
 use bevy::render::view::screenshot::{save_to_disk, Screenshot};
 use std::f32::consts::PI;
 
+use bevy_mass_render::ground_explosion::create_debris_meshes;
+
 const CELL_SIZE: u32 = 64;
 const NUM_ANGLES: u32 = 8;
 const NUM_VARIANTS: u32 = 3;
@@ -69,15 +71,8 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    // Create the 3 debris mesh variants (same as ground_explosion.rs)
-    let debris_meshes = [
-        // Variant 0: Small cube (chunky rock)
-        meshes.add(Cuboid::new(1.0, 0.8, 0.6)),
-        // Variant 1: Flat slab (debris piece)
-        meshes.add(Cuboid::new(1.2, 0.4, 0.8)),
-        // Variant 2: Elongated piece (shrapnel)
-        meshes.add(Cuboid::new(0.5, 0.5, 1.4)),
-    ];
+    // Create the 3 debris mesh variants (shared with the in-game ground explosion)
+    let debris_meshes = create_debris_meshes(&mut meshes);
 
     // Debris material - dark brown/grey
     let debris_material = materials.add(StandardMaterial {
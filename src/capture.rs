@@ -0,0 +1,443 @@
+// Capture points - ground that squads take by standing on it
+//
+// A capture point belongs to nobody until one side's squads hold it uncontested for
+// CAPTURE_TIME seconds. Squads of hostile alliances inside at the same time contest it and
+// freeze the progress; a hostile side has to drain the owner's progress back to zero
+// (neutralising it) before filling it for itself. Every owned point earns its owner
+// CAPTURE_POINTS_PER_SECOND victory points, plus its bonus: more points (Income) or a fresh
+// squad every CAPTURE_REINFORCEMENT_INTERVAL seconds (Reinforcements).
+//
+// Victory points (GameState::points) also come from destroyed secondary objectives and
+// decide --points-to-win and --time-limit battles (see win_condition_system).
+//
+// Skirmish maps get a center point and two flank points; scenarios place their own.
+// The simulation is render-free; the ring, progress bar and HUD are added by GamePlugin.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::archetype::UnitArchetypes;
+use crate::constants::*;
+use crate::scenario::{map_starts_new_battle, ScenarioLibrary, ScenarioState, ScenarioUnit};
+use crate::setup::{create_team_materials, spawn_single_squad, UnitMaterials};
+use crate::terrain::{MapSwitchEvent, TerrainHeightmap};
+use crate::types::*;
+
+/// Capture progress and bonuses, ahead of the win condition
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, (
+            capture_point_system,
+            capture_bonus_system,
+        ).chain().before(crate::objective::win_condition_system));
+    }
+}
+
+/// What holding a point is worth on top of CAPTURE_POINTS_PER_SECOND
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CaptureBonus {
+    /// Extra victory points per second
+    Income(f32),
+    /// A squad of this archetype every CAPTURE_REINFORCEMENT_INTERVAL seconds
+    /// (at most CAPTURE_REINFORCEMENT_MAX_SQUADS of them alive)
+    Reinforcements {
+        #[serde(default = "default_archetype")]
+        archetype: String,
+    },
+}
+
+fn default_archetype() -> String {
+    DEFAULT_UNIT_ARCHETYPE.to_string()
+}
+
+#[derive(Component)]
+pub struct CapturePoint {
+    pub name: String,
+    pub radius: f32,
+    pub bonus: CaptureBonus,
+    /// Team that holds the point and earns its bonus
+    pub owner: Option<Team>,
+    /// Team the progress belongs to: the owner, or whoever is taking the point
+    pub holder: Option<Team>,
+    /// 0 to 1; the holder owns the point once it reaches 1
+    pub progress: f32,
+    /// Squads of hostile alliances are inside
+    pub contested: bool,
+    /// Seconds towards the next reinforcement squad
+    pub bonus_timer: f32,
+    /// Squads this point has sent (Reinforcements)
+    pub reinforcement_squads: Vec<u32>,
+}
+
+impl CapturePoint {
+    /// A point owned by `owner` from the start, or neutral
+    pub fn new(name: impl Into<String>, radius: f32, bonus: CaptureBonus, owner: Option<Team>) -> Self {
+        Self {
+            name: name.into(),
+            radius,
+            bonus,
+            owner,
+            holder: owner,
+            progress: if owner.is_some() { 1.0 } else { 0.0 },
+            contested: false,
+            bonus_timer: 0.0,
+            reinforcement_squads: Vec::new(),
+        }
+    }
+}
+
+/// Spawn a capture point on the terrain at `position` (XZ)
+pub fn spawn_capture_point(commands: &mut Commands, heightmap: &TerrainHeightmap, point: CapturePoint, position: Vec3) -> Entity {
+    let y = heightmap.sample_height(position.x, position.z);
+    let name = Name::new(point.name.clone());
+    commands.spawn((
+        Transform::from_xyz(position.x, y, position.z),
+        Visibility::default(),
+        point,
+        name,
+    )).id()
+}
+
+/// The skirmish layout: a center point sending reinforcements, two flank points paying income
+fn spawn_skirmish_points(commands: &mut Commands, heightmap: &TerrainHeightmap) {
+    let center = CapturePoint::new("Center", CAPTURE_POINT_RADIUS, CaptureBonus::Reinforcements { archetype: default_archetype() }, None);
+    spawn_capture_point(commands, heightmap, center, Vec3::ZERO);
+    for (name, z) in [("North Flank", -CAPTURE_FLANK_OFFSET), ("South Flank", CAPTURE_FLANK_OFFSET)] {
+        let flank = CapturePoint::new(name, CAPTURE_POINT_RADIUS, CaptureBonus::Income(CAPTURE_INCOME_POINTS), None);
+        spawn_capture_point(commands, heightmap, flank, Vec3::new(0.0, 0.0, z));
+    }
+}
+
+/// Startup: capture points for the opening skirmish battle
+pub fn spawn_skirmish_capture_points(mut commands: Commands, heightmap: Res<TerrainHeightmap>) {
+    spawn_skirmish_points(&mut commands, &heightmap);
+}
+
+/// Map switch: skirmish points move to the new terrain (scenario and debug maps have none;
+/// scenarios spawn and clean up their own)
+pub fn respawn_capture_points_on_map_switch(
+    mut commands: Commands,
+    mut map_switch_events: EventReader<MapSwitchEvent>,
+    heightmap: Res<TerrainHeightmap>,
+    (scenario_library, scenario_state): (Option<Res<ScenarioLibrary>>, Option<Res<ScenarioState>>),
+    point_query: Query<Entity, (With<CapturePoint>, Without<ScenarioUnit>)>,
+) {
+    for event in map_switch_events.read() {
+        for entity in point_query.iter() {
+            commands.entity(entity).try_despawn();
+        }
+        if !map_starts_new_battle(event.new_map, scenario_library.as_deref(), scenario_state.as_deref()) {
+            spawn_skirmish_points(&mut commands, &heightmap);
+        }
+    }
+}
+
+// ===== SIMULATION =====
+
+/// Advance capture progress from the squads standing on each point
+pub fn capture_point_system(
+    time: Res<Time>,
+    diplomacy: Res<Diplomacy>,
+    squad_manager: Res<SquadManager>,
+    mut point_query: Query<(&Transform, &mut CapturePoint)>,
+) {
+    let step = time.delta_secs() / CAPTURE_TIME;
+
+    for (transform, mut point) in point_query.iter_mut() {
+        let center = transform.translation.xz();
+
+        // Units inside, per team
+        let mut present = [0usize; Team::MAX];
+        for squad in squad_manager.squads.values() {
+            if !squad.members.is_empty() && squad.center_position.xz().distance(center) <= point.radius {
+                present[squad.team.index()] += squad.members.len();
+            }
+        }
+
+        // The biggest force leads (the first team on ties); any hostile force inside contests it
+        let Some(leader) = Team::ALL.iter().copied()
+            .filter(|team| present[team.index()] > 0)
+            .max_by_key(|team| (present[team.index()], std::cmp::Reverse(team.0)))
+        else {
+            point.contested = false;
+            continue;
+        };
+        point.contested = Team::ALL.iter()
+            .any(|&team| present[team.index()] > 0 && diplomacy.is_hostile(team, leader));
+        if point.contested {
+            continue;
+        }
+
+        match point.holder {
+            Some(holder) if diplomacy.is_hostile(holder, leader) => {
+                // Drain the other side's progress first
+                point.progress -= step;
+                if point.progress <= 0.0 {
+                    if let Some(owner) = point.owner.take() {
+                        info!("{} neutralised, Team {:?} lost it", point.name, owner);
+                    }
+                    point.progress = 0.0;
+                    point.holder = None;
+                    point.bonus_timer = 0.0;
+                }
+            }
+            _ => {
+                let holder = *point.holder.get_or_insert(leader);
+                point.progress = (point.progress + step).min(1.0);
+                if point.progress >= 1.0 && point.owner.is_none() {
+                    point.owner = Some(holder);
+                    info!("{} captured by Team {:?}", point.name, holder);
+                }
+            }
+        }
+    }
+}
+
+/// Pay owned points' victory points and send their reinforcements
+pub fn capture_bonus_system(
+    time: Res<Time>,
+    mut game_state: ResMut<GameState>,
+    mut commands: Commands,
+    (mut meshes, mut materials): (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>),
+    (mut squad_manager, mut archetypes, mut sim_rng, heightmap):
+        (ResMut<SquadManager>, ResMut<UnitArchetypes>, ResMut<SimRng>, Res<TerrainHeightmap>),
+    mut point_query: Query<(&Transform, &mut CapturePoint)>,
+    // Reinforcements share one material set per team
+    mut team_materials: Local<HashMap<Team, UnitMaterials>>,
+) {
+    if game_state.game_ended {
+        return;
+    }
+    let delta = time.delta_secs();
+
+    for (transform, mut point) in point_query.iter_mut() {
+        let Some(owner) = point.owner else {
+            continue;
+        };
+
+        let archetype = match &point.bonus {
+            CaptureBonus::Income(extra) => {
+                game_state.points[owner.index()] += (CAPTURE_POINTS_PER_SECOND + extra) * delta;
+                continue;
+            }
+            CaptureBonus::Reinforcements { archetype } => archetype.clone(),
+        };
+        game_state.points[owner.index()] += CAPTURE_POINTS_PER_SECOND * delta;
+
+        point.bonus_timer += delta;
+        if point.bonus_timer < CAPTURE_REINFORCEMENT_INTERVAL {
+            continue;
+        }
+        point.bonus_timer = 0.0;
+
+        // Only the owner's squads still standing count towards the cap
+        point.reinforcement_squads.retain(|&squad_id| {
+            squad_manager.get_squad(squad_id).is_some_and(|squad| squad.team == owner && !squad.members.is_empty())
+        });
+        if point.reinforcement_squads.len() >= CAPTURE_REINFORCEMENT_MAX_SQUADS {
+            continue;
+        }
+
+        // The squad forms up on the owner's side of the point, facing out
+        let offset = owner.home_direction() * point.radius;
+        let (x, z) = (transform.translation.x + offset.x, transform.translation.z + offset.z);
+        let position = Vec3::new(x, heightmap.sample_height(x, z), z);
+        let unit_materials = team_materials
+            .entry(owner)
+            .or_insert_with(|| create_team_materials(&mut materials, owner));
+        let squad_id = spawn_single_squad(
+            &mut commands,
+            &mut squad_manager,
+            &mut archetypes,
+            &archetype,
+            &mut meshes,
+            unit_materials,
+            &mut materials,
+            owner,
+            position,
+            -owner.home_direction(),
+            &heightmap,
+            &mut *sim_rng,
+        );
+        point.reinforcement_squads.push(squad_id);
+        info!("{} sent Team {:?} a squad ({})", point.name, owner, squad_id);
+    }
+}
+
+// ===== PRESENTATION =====
+
+/// Height of the progress bar above the point
+const CAPTURE_BAR_HEIGHT: f32 = 12.0;
+const CAPTURE_BAR_WIDTH: f32 = 14.0;
+const CAPTURE_RING_WIDTH: f32 = 1.5;
+const NEUTRAL_COLOR: Color = Color::srgba(0.7, 0.7, 0.7, 0.6);
+
+/// Ground ring showing a point's radius and owner (child of the point)
+#[derive(Component)]
+pub struct CapturePointRing;
+
+/// Billboard progress bar above a point (child of the point)
+#[derive(Component)]
+pub struct CapturePointBar;
+
+/// Capture points and victory points readout
+#[derive(Component)]
+pub struct CaptureHudUI;
+
+/// Give new capture points their ring and progress bar
+pub fn spawn_capture_point_visuals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut bar_materials: ResMut<Assets<crate::turrets::HealthBarMaterial>>,
+    point_query: Query<(Entity, &CapturePoint), Added<CapturePoint>>,
+) {
+    for (entity, point) in point_query.iter() {
+        let ring_mesh = meshes.add(Annulus::new(point.radius - CAPTURE_RING_WIDTH, point.radius));
+        let ring_material = materials.add(StandardMaterial {
+            base_color: NEUTRAL_COLOR,
+            unlit: true,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        });
+        let bar_mesh = meshes.add(Rectangle::new(CAPTURE_BAR_WIDTH, 0.8));
+        let bar_material = bar_materials.add(crate::turrets::HealthBarMaterial {
+            health_data: Vec4::new(point.progress, 0.0, 0.0, 0.0),
+        });
+
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                Mesh3d(ring_mesh),
+                MeshMaterial3d(ring_material),
+                Transform::from_xyz(0.0, 0.3, 0.0).with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+                bevy::pbr::NotShadowCaster,
+                CapturePointRing,
+            ));
+            parent.spawn((
+                Mesh3d(bar_mesh),
+                MeshMaterial3d(bar_material),
+                Transform::from_xyz(0.0, CAPTURE_BAR_HEIGHT, 0.0),
+                bevy::pbr::NotShadowCaster,
+                CapturePointBar,
+            ));
+        });
+    }
+}
+
+/// Color rings by owner and fill the progress bars
+pub fn update_capture_point_visuals(
+    point_query: Query<&CapturePoint>,
+    ring_query: Query<(&ChildOf, &MeshMaterial3d<StandardMaterial>), With<CapturePointRing>>,
+    mut bar_query: Query<(&ChildOf, &mut Transform, &MeshMaterial3d<crate::turrets::HealthBarMaterial>), With<CapturePointBar>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut bar_materials: ResMut<Assets<crate::turrets::HealthBarMaterial>>,
+    camera_query: Query<&Transform, (With<RtsCamera>, Without<CapturePointBar>)>,
+) {
+    for (child_of, material_handle) in ring_query.iter() {
+        let Ok(point) = point_query.get(child_of.parent()) else { continue };
+        let color = match point.owner {
+            _ if point.contested => Color::srgba(1.0, 0.8, 0.2, 0.8), // Amber while fought over
+            Some(owner) => owner.shield_color().with_alpha(0.8),
+            None => NEUTRAL_COLOR,
+        };
+        if let Some(material) = materials.get_mut(&material_handle.0) {
+            if material.base_color != color {
+                material.base_color = color;
+            }
+        }
+    }
+
+    let camera_rotation = camera_query.single().map(|camera| camera.rotation).ok();
+    for (child_of, mut transform, material_handle) in bar_query.iter_mut() {
+        let Ok(point) = point_query.get(child_of.parent()) else { continue };
+        if let Some(rotation) = camera_rotation {
+            transform.rotation = rotation;
+        }
+        if let Some(material) = bar_materials.get_mut(&material_handle.0) {
+            if material.health_data.x != point.progress {
+                material.health_data.x = point.progress;
+            }
+        }
+    }
+}
+
+/// Spawn the capture point and victory point readout (bottom center)
+pub fn spawn_capture_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Percent(35.0),
+            ..default()
+        },
+        CaptureHudUI,
+    ));
+}
+
+/// Victory points, the time limit, each point's state and the battle result
+pub fn update_capture_hud(
+    game_state: Res<GameState>,
+    diplomacy: Res<Diplomacy>,
+    win_conditions: Res<WinConditions>,
+    point_query: Query<&CapturePoint>,
+    mut hud_query: Query<&mut Text, With<CaptureHudUI>>,
+) {
+    let mut lines = Vec::new();
+
+    let scored = game_state.points.iter().any(|&points| points > 0.0);
+    if !point_query.is_empty() || scored || win_conditions.points_to_win.is_some() {
+        let mut line = diplomacy.teams.iter()
+            .map(|team| format!("{:?}: {:.0}", team, game_state.points[team.index()]))
+            .collect::<Vec<_>>()
+            .join("  ");
+        line = format!("Points  {}", line);
+        if let Some(target) = win_conditions.points_to_win {
+            line += &format!("  ({:.0} to win)", target);
+        }
+        lines.push(line);
+    }
+    if let Some(limit) = win_conditions.time_limit {
+        let left = (limit - game_state.battle_time).max(0.0) as u32;
+        lines.push(format!("Time left {}:{:02}", left / 60, left % 60));
+    }
+
+    let mut points: Vec<&CapturePoint> = point_query.iter().collect();
+    points.sort_by(|a, b| a.name.cmp(&b.name));
+    let point_states: Vec<String> = points.iter().map(|point| {
+        let state = match (point.owner, point.holder) {
+            _ if point.contested => "contested".to_string(),
+            (Some(owner), _) if point.progress >= 1.0 => format!("{:?}", owner),
+            (Some(owner), _) => format!("{:?} {:.0}%", owner, point.progress * 100.0),
+            (None, Some(holder)) => format!("{:?} taking {:.0}%", holder, point.progress * 100.0),
+            (None, None) => "neutral".to_string(),
+        };
+        format!("{}: {}", point.name, state)
+    }).collect();
+    if !point_states.is_empty() {
+        lines.push(point_states.join(" | "));
+    }
+
+    if game_state.game_ended {
+        let reason = game_state.end_reason.as_deref().unwrap_or("battle over");
+        match game_state.winner {
+            Some(winner) => lines.push(format!("Team {:?} wins ({})", winner, reason)),
+            None => lines.push(format!("Draw ({})", reason)),
+        }
+    }
+
+    for mut text in hud_query.iter_mut() {
+        let new_text = lines.join("\n");
+        if text.0 != new_text {
+            text.0 = new_text;
+        }
+    }
+}
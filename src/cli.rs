@@ -0,0 +1,175 @@
+// Command-line arguments shared by the windowed game and headless mode
+//
+// Usage: cargo run --release -- [--headless] [--seed <u64>] [--tick-rate <hz>]
+//                               [--record <file.ron> | --replay <file.ron>]
+//                               [--teams <a,b | a,b,c,d | ac,bd>]
+//                               [--ai <a|b|both|all|off|letters>] [--ai-difficulty <easy|normal|hard>]
+//                               [--scenario <id>]
+//                               [--points-to-win <n>] [--time-limit <seconds>] [--no-tower-victory]
+
+use crate::ai::AiDifficulty;
+use crate::types::{Diplomacy, Team, WinConditions};
+
+/// Parsed command-line flags
+#[derive(Clone, Debug)]
+pub struct CliArgs {
+    /// Resolve a battle without a window and print the result
+    pub headless: bool,
+    /// Seed for SimRng; a random seed is chosen (and logged) when omitted
+    pub seed: Option<u64>,
+    /// Simulation ticks per second (FixedUpdate)
+    pub tick_rate: f64,
+    /// Record player commands to this replay file
+    pub record: Option<String>,
+    /// Play back this replay file (overrides seed and tick rate)
+    pub replay: Option<String>,
+    /// Alliances taking part: each group is one side, teams within it are allied
+    pub alliances: Vec<Vec<Team>>,
+    /// Teams commanded by the AI (None: every team except the player's Team A)
+    pub ai_teams: Option<Vec<Team>>,
+    pub ai_difficulty: AiDifficulty,
+    /// Scenario to start (an assets/scenarios file stem); its map loads at startup
+    pub scenario: Option<String>,
+    /// How the battle can be won outside scenarios
+    pub win_conditions: WinConditions,
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            seed: None,
+            tick_rate: crate::constants::SIMULATION_TICK_RATE,
+            record: None,
+            replay: None,
+            alliances: vec![vec![Team::A], vec![Team::B]],
+            ai_teams: None,
+            ai_difficulty: AiDifficulty::default(),
+            scenario: None,
+            win_conditions: WinConditions::default(),
+        }
+    }
+}
+
+impl CliArgs {
+    /// Parse std::env::args(). Unknown flags are ignored, malformed values are reported and ignored.
+    pub fn parse() -> Self {
+        let mut args = CliArgs::default();
+        let mut iter = std::env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--headless" => args.headless = true,
+                "--seed" => match iter.next().map(|value| value.parse::<u64>()) {
+                    Some(Ok(seed)) => args.seed = Some(seed),
+                    _ => eprintln!("--seed expects an unsigned integer, using a random seed"),
+                },
+                "--tick-rate" => match iter.next().map(|value| value.parse::<f64>()) {
+                    Some(Ok(hz)) if hz > 0.0 => args.tick_rate = hz,
+                    _ => eprintln!("--tick-rate expects a positive number, using {} Hz", args.tick_rate),
+                },
+                "--record" => match iter.next() {
+                    Some(path) => args.record = Some(path),
+                    None => eprintln!("--record expects a file path, not recording"),
+                },
+                "--replay" => match iter.next() {
+                    Some(path) => args.replay = Some(path),
+                    None => eprintln!("--replay expects a file path, ignoring"),
+                },
+                "--teams" => match iter.next().as_deref().and_then(parse_alliances) {
+                    Some(alliances) => args.alliances = alliances,
+                    None => eprintln!("--teams expects comma-separated alliances of team letters a-d (e.g. a,b,c or ac,bd), using a,b"),
+                },
+                "--ai" => match iter.next().as_deref() {
+                    Some("both") => args.ai_teams = Some(vec![Team::A, Team::B]),
+                    Some("all") => args.ai_teams = Some(Team::ALL.to_vec()),
+                    Some("off") => args.ai_teams = Some(Vec::new()),
+                    Some(letters) => match letters.chars().map(Team::from_letter).collect::<Option<Vec<Team>>>() {
+                        Some(teams) => args.ai_teams = Some(teams),
+                        None => eprintln!("--ai expects team letters, both, all or off, using the AI for every team but A"),
+                    },
+                    None => eprintln!("--ai expects team letters, both, all or off, using the AI for every team but A"),
+                },
+                "--ai-difficulty" => match iter.next().as_deref().and_then(AiDifficulty::parse) {
+                    Some(difficulty) => args.ai_difficulty = difficulty,
+                    None => eprintln!("--ai-difficulty expects easy, normal or hard, using {:?}", args.ai_difficulty),
+                },
+                "--scenario" => match iter.next() {
+                    Some(id) => args.scenario = Some(id),
+                    None => eprintln!("--scenario expects a scenario ID, ignoring"),
+                },
+                "--points-to-win" => match iter.next().map(|value| value.parse::<f32>()) {
+                    Some(Ok(points)) if points > 0.0 => args.win_conditions.points_to_win = Some(points),
+                    _ => eprintln!("--points-to-win expects a positive number, ignoring"),
+                },
+                "--time-limit" => match iter.next().map(|value| value.parse::<f32>()) {
+                    Some(Ok(seconds)) if seconds > 0.0 => args.win_conditions.time_limit = Some(seconds),
+                    _ => eprintln!("--time-limit expects a positive number of seconds, ignoring"),
+                },
+                "--no-tower-victory" => args.win_conditions.towers = false,
+                _ => {}
+            }
+        }
+
+        args
+    }
+
+    /// Team setup for this run (see --teams)
+    pub fn diplomacy(&self) -> Diplomacy {
+        Diplomacy::from_alliances(&self.alliances)
+    }
+
+    /// AI-controlled teams that are actually in the battle
+    pub fn ai_teams(&self) -> Vec<Team> {
+        let teams = self.diplomacy().teams;
+        match &self.ai_teams {
+            None => teams.into_iter().filter(|&team| team != Team::A).collect(),
+            Some(selected) => teams.into_iter().filter(|team| selected.contains(team)).collect(),
+        }
+    }
+
+    /// SimRng for this run: the requested seed, or a random (logged) one
+    pub fn sim_rng(&self) -> crate::types::SimRng {
+        match self.seed {
+            Some(seed) => crate::types::SimRng::new(seed),
+            None => crate::types::SimRng::default(),
+        }
+    }
+
+    /// Scenario state asking for the --scenario mission (started once its map loads)
+    pub fn scenario_state(&self) -> crate::scenario::ScenarioState {
+        crate::scenario::ScenarioState::requesting(self.scenario.clone())
+    }
+
+    /// Victory rules for this run (--points-to-win, --time-limit, --no-tower-victory)
+    pub fn win_conditions(&self) -> WinConditions {
+        self.win_conditions.clone()
+    }
+
+    /// Fixed clock driving the simulation schedule at the requested tick rate
+    pub fn fixed_time(&self) -> bevy::prelude::Time<bevy::prelude::Fixed> {
+        bevy::prelude::Time::<bevy::prelude::Fixed>::from_hz(self.tick_rate)
+    }
+}
+
+/// Parse "a,b,c" (free-for-all) or "ac,bd" (2v2). Needs at least two sides and no repeated team.
+fn parse_alliances(spec: &str) -> Option<Vec<Vec<Team>>> {
+    let mut seen = Vec::new();
+    let mut alliances = Vec::new();
+    for group in spec.split(',') {
+        let mut alliance = Vec::new();
+        for letter in group.trim().chars() {
+            let team = Team::from_letter(letter)?;
+            if seen.contains(&team) {
+                return None;
+            }
+            seen.push(team);
+            alliance.push(team);
+        }
+        if alliance.is_empty() {
+            return None;
+        }
+        alliances.push(alliance);
+    }
+    (alliances.len() >= 2).then_some(alliances)
+}
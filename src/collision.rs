@@ -5,20 +5,20 @@
 
 use bevy::prelude::*;
 use rayon::prelude::*;
-use crate::types::{BattleDroid, UnitMass, SpatialGrid, KnockbackState, RagdollDeath};
+use crate::types::{BattleDroid, UnitMass, SpatialGrid, KnockbackState, RagdollDeath, SimTick};
 use crate::constants::{
     COLLISION_ENABLED, UNIT_COLLISION_RADIUS, COLLISION_PUSH_STRENGTH,
-    DEFAULT_UNIT_MASS, COLLISION_FRAME_SKIP, STATIONARY_THRESHOLD
+    DEFAULT_UNIT_MASS, COLLISION_TICK_SKIP, STATIONARY_THRESHOLD
 };
 
 /// Hard collision resolution system - pushes overlapping units apart
 /// Runs after animate_march to resolve any remaining overlaps
 /// Uses parallel iteration for performance with 10k+ units
-/// Frame-skipped: runs every COLLISION_FRAME_SKIP frames
+/// Tick-skipped: runs every COLLISION_TICK_SKIP fixed ticks
 pub fn unit_collision_system(
     time: Res<Time>,
     spatial_grid: Res<SpatialGrid>,
-    mut frame_counter: Local<u32>,
+    sim_tick: Res<SimTick>,
     mut droids: Query<
         (Entity, &mut Transform, &BattleDroid, Option<&UnitMass>),
         (Without<KnockbackState>, Without<RagdollDeath>)
@@ -29,13 +29,12 @@ pub fn unit_collision_system(
         return;
     }
 
-    // Frame skipping - only run every Nth frame
-    *frame_counter = (*frame_counter + 1) % COLLISION_FRAME_SKIP;
-    if *frame_counter != 0 {
+    // Tick skipping - only run every Nth fixed tick
+    if sim_tick.0 % COLLISION_TICK_SKIP as u64 != 0 {
         return;
     }
 
-    let delta = time.delta_secs() * COLLISION_FRAME_SKIP as f32; // Compensate for skipped frames
+    let delta = time.delta_secs() * COLLISION_TICK_SKIP as f32; // Compensate for skipped ticks
     if delta <= 0.0 {
         return;
     }
@@ -1,14 +1,47 @@
 use bevy::prelude::*;
+use rand::Rng;
 use crate::types::*;
+use crate::archetype::UnitStats;
 use crate::constants::*;
 use crate::terrain::TerrainHeightmap;
 use crate::math_utils::ray_sphere_intersection;
+use crate::cover::CoverMap;
+use crate::fog_of_war::TeamVision;
 use bevy::render::mesh::{Indices, PrimitiveTopology};
 use bevy::asset::RenderAssetUsages;
 
+/// Combat simulation: team vision, target acquisition, hitscan fire, spatial grid and cover map rebuilds, area damage
+/// Render-free - tracers and audio are only spawned when their assets are loaded
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpatialGrid::new())
+            .add_event::<AreaDamageEvent>()
+            .init_resource::<CoverMap>()
+            .init_resource::<TeamVision>()
+            .add_systems(Update, crate::cover::rebuild_cover_map)
+            .add_systems(FixedUpdate, (
+                crate::fog_of_war::update_team_vision_system.before(target_acquisition_system),
+                target_acquisition_system,
+                clear_blocked_targets_system, // Stuck prevention for AttackMove
+                hitscan_fire_system,          // Infantry use hitscan (instant damage + visual tracer)
+                turret_hitscan_fire_system,   // Turrets use hitscan too
+                collision_detection_system,   // Rebuilds the spatial grid each tick
+                squad_health_system.after(collision_detection_system),
+            ))
+            .add_systems(FixedUpdate, (
+                // Area damage system (processes AreaDamageEvent from explosions)
+                crate::area_damage::area_damage_system,
+                crate::area_damage::knockback_physics_system,
+                crate::area_damage::ragdoll_death_system,
+            ));
+    }
+}
+
 /// Check if there's a clear line of sight between shooter and target
 /// Returns true if the path is clear (no terrain blocking)
-fn has_line_of_sight(
+pub(crate) fn has_line_of_sight(
     shooter_pos: Vec3,
     target_pos: Vec3,
     heightmap: Option<&TerrainHeightmap>,
@@ -40,6 +73,41 @@ fn has_line_of_sight(
     true
 }
 
+/// Multipliers on hit chance from the shooter and target (formation, cover, ...)
+#[derive(Clone, Copy, Debug)]
+pub struct HitModifiers {
+    pub shooter: f32,          // Shooter's accuracy multiplier
+    pub target_exposure: f32,  // How easy the target is to hit
+}
+
+impl Default for HitModifiers {
+    fn default() -> Self {
+        Self { shooter: 1.0, target_exposure: 1.0 }
+    }
+}
+
+impl HitModifiers {
+    /// Modifiers from the shooter's and target's formations (None for turrets, towers, ...)
+    pub fn from_formations(shooter: Option<FormationType>, target: Option<FormationType>) -> Self {
+        Self {
+            shooter: shooter.map_or(1.0, |f| f.accuracy_modifier()),
+            target_exposure: target.map_or(1.0, |f| f.exposure_modifier()),
+        }
+    }
+
+    /// Scale the shooter's accuracy by a further factor (wounds, suppression, veterancy, ...)
+    pub fn with_shooter(mut self, factor: f32) -> Self {
+        self.shooter *= factor;
+        self
+    }
+
+    /// Scale how easy the target is to hit by a further factor (cover, ...)
+    pub fn with_target(mut self, factor: f32) -> Self {
+        self.target_exposure *= factor;
+        self
+    }
+}
+
 /// Calculate hit probability based on shooter/target states and positions
 /// Returns a value between ACCURACY_MIN and ACCURACY_MAX
 pub fn calculate_hit_chance(
@@ -48,6 +116,7 @@ pub fn calculate_hit_chance(
     target_pos: Vec3,
     shooter_stationary: bool,  // Bonus if true (not for turrets, they're always stationary)
     target_stationary: bool,   // Penalty if false (target is moving)
+    modifiers: HitModifiers,
 ) -> f32 {
     let mut accuracy = base_accuracy;
 
@@ -71,9 +140,17 @@ pub fn calculate_hit_chance(
     let range_penalty = calculate_range_penalty(distance);
     accuracy -= range_penalty;
 
+    accuracy *= modifiers.shooter * modifiers.target_exposure;
+
     accuracy.clamp(ACCURACY_MIN, ACCURACY_MAX)
 }
 
+/// Turret weapons: MG turrets fire kinetic rounds that mow infantry, heavy turrets
+/// fire heavy laser bolts that shred vehicles
+pub fn turret_weapon(is_mg: bool) -> WeaponType {
+    if is_mg { WeaponType::Kinetic } else { WeaponType::HeavyLaser }
+}
+
 /// Pitch angle (radians) from the MG barrel pivot to a target, clamped to the
 /// barrel's articulation range. Used by BOTH the rotation system (visual barrel
 /// angle) and the firing system (muzzle position) so they can never diverge.
@@ -148,29 +225,30 @@ pub fn calculate_laser_orientation(
     }
 }
 
+/// Additive, unlit bolt material in the team's laser color
+fn laser_material(team: Team, texture: Option<Handle<Image>>) -> StandardMaterial {
+    let palette = team.palette();
+    StandardMaterial {
+        base_color: palette.laser,
+        base_color_texture: texture,
+        emissive: palette.laser_emissive.into(),
+        unlit: true, // No lighting calculations
+        alpha_mode: AlphaMode::Add, // Additive blending for glow
+        cull_mode: None, // Visible from both sides
+        ..default()
+    }
+}
+
 /// Initialize cached laser assets (materials and meshes) to avoid per-shot allocation
 pub fn setup_laser_assets(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    let team_a_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.0, 2.0, 0.0), // Green for Team A
-        emissive: Color::srgb(0.0, 1.0, 0.0).into(),
-        unlit: true,
-        alpha_mode: AlphaMode::Add,
-        cull_mode: None,
-        ..default()
-    });
-
-    let team_b_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(2.0, 0.0, 0.0), // Red for Team B
-        emissive: Color::srgb(1.0, 0.0, 0.0).into(),
-        unlit: true,
-        alpha_mode: AlphaMode::Add,
-        cull_mode: None,
-        ..default()
-    });
+    // One bolt material per team, in the team's laser color
+    let team_materials = Team::ALL.iter()
+        .map(|team| materials.add(laser_material(*team, None)))
+        .collect();
 
     // MG turret uses shorter bolts (60% length)
     let mg_laser_mesh = meshes.add(Rectangle::new(LASER_WIDTH, LASER_LENGTH * 0.6));
@@ -179,8 +257,7 @@ pub fn setup_laser_assets(
     let hitscan_tracer_mesh = meshes.add(Rectangle::new(HITSCAN_TRACER_WIDTH, HITSCAN_TRACER_LENGTH));
 
     commands.insert_resource(LaserAssets {
-        team_a_material,
-        team_b_material,
+        team_materials,
         mg_laser_mesh,
         hitscan_tracer_mesh,
     });
@@ -192,7 +269,7 @@ pub fn volley_fire_system(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut images: ResMut<Assets<Image>>,
-    droid_query: Query<(&Transform, &BattleDroid), Without<LaserProjectile>>,
+    droid_query: Query<(&Transform, &BattleDroid, Option<&UnitStats>), Without<LaserProjectile>>,
     camera_query: Query<&Transform, (With<RtsCamera>, Without<LaserProjectile>)>,
     audio_assets: Res<AudioAssets>,
 ) {
@@ -230,27 +307,11 @@ pub fn volley_fire_system(
             bevy::asset::RenderAssetUsages::RENDER_WORLD,
         ));
         
-        // Create laser materials for both teams
-        let team_a_laser_material = materials.add(StandardMaterial {
-            base_color: Color::srgb(0.0, 2.0, 0.0), // Bright green for Team A
-            base_color_texture: Some(laser_texture.clone()),
-            emissive: Color::srgb(0.0, 1.0, 0.0).into(),
-            unlit: true, // No lighting calculations
-            alpha_mode: AlphaMode::Add, // Additive blending for glow
-            cull_mode: None, // Visible from both sides
-            ..default()
-        });
-        
-        let team_b_laser_material = materials.add(StandardMaterial {
-            base_color: Color::srgb(2.0, 0.0, 0.0), // Bright red for Team B
-            base_color_texture: Some(laser_texture),
-            emissive: Color::srgb(1.0, 0.0, 0.0).into(),
-            unlit: true, // No lighting calculations
-            alpha_mode: AlphaMode::Add, // Additive blending for glow
-            cull_mode: None, // Visible from both sides
-            ..default()
-        });
-        
+        // Create laser materials for every team
+        let team_laser_materials: Vec<Handle<StandardMaterial>> = Team::ALL.iter()
+            .map(|team| materials.add(laser_material(*team, Some(laser_texture.clone()))))
+            .collect();
+
         // Create laser mesh (simple quad)
         let laser_mesh = meshes.add(Rectangle::new(LASER_WIDTH, LASER_LENGTH));
         
@@ -260,7 +321,7 @@ pub fn volley_fire_system(
             .unwrap_or(Vec3::new(0.0, 100.0, 100.0)); // Fallback position
         
         // Spawn laser from each droid
-        for (droid_transform, droid) in droid_query.iter() {
+        for (droid_transform, droid, stats) in droid_query.iter() {
             // Calculate firing position (slightly in front of droid)
             let firing_pos = droid_transform.translation + Vec3::new(0.0, 0.8, 0.0);
             
@@ -274,10 +335,12 @@ pub fn volley_fire_system(
                 .with_rotation(laser_rotation);
             
             // Choose material based on team
-            let laser_material = match droid.team {
-                Team::A => team_a_laser_material.clone(),
-                Team::B => team_b_laser_material.clone(),
-            };
+            let laser_material = team_laser_materials[droid.team.index()].clone();
+
+            // Infantry fire their archetype's weapon; turrets volley plain laser bolts
+            let (weapon, damage) = stats
+                .map(|stats| (stats.weapon, stats.damage))
+                .unwrap_or((WeaponType::PulsedLaser, HITSCAN_DAMAGE));
             
             // Spawn laser projectile
             commands.spawn((
@@ -289,6 +352,8 @@ pub fn volley_fire_system(
                     lifetime: LASER_LIFETIME,
                     team: droid.team,
                     origin: firing_pos,
+                    weapon,
+                    damage,
                 },
             ));
         }
@@ -353,10 +418,12 @@ pub fn update_projectiles(
 
 pub fn target_acquisition_system(
     time: Res<Time>,
-    mut combat_query: Query<(Entity, &GlobalTransform, &BattleDroid, &mut CombatUnit), Without<crate::types::TurretRotatingAssembly>>,
+    mut combat_query: Query<(Entity, &GlobalTransform, &BattleDroid, &mut CombatUnit, &UnitStats), Without<crate::types::TurretRotatingAssembly>>,
     tower_query: Query<(Entity, &GlobalTransform, &UplinkTower), With<UplinkTower>>,
     turret_query: Query<(Entity, &GlobalTransform, &TurretBase), With<TurretBase>>,
     heightmap: Option<Res<TerrainHeightmap>>,
+    diplomacy: Res<Diplomacy>,
+    vision: Res<TeamVision>,
 ) {
     let delta_time = time.delta_secs();
     let hm = heightmap.as_ref().map(|h| h.as_ref());
@@ -365,7 +432,7 @@ pub fn target_acquisition_system(
     // Use GlobalTransform to get world position (handles parent-child hierarchies like turrets)
     let all_units: Vec<(Entity, Vec3, Team)> = combat_query
         .iter()
-        .map(|(entity, transform, droid, _)| (entity, transform.translation(), droid.team))
+        .map(|(entity, transform, droid, ..)| (entity, transform.translation(), droid.team))
         .collect();
 
     // Collect all tower data
@@ -380,7 +447,7 @@ pub fn target_acquisition_system(
         .map(|(entity, transform, turret)| (entity, transform.translation(), turret.team))
         .collect();
 
-    for (entity, transform, droid, mut combat_unit) in combat_query.iter_mut() {
+    for (entity, transform, droid, mut combat_unit, stats) in combat_query.iter_mut() {
         // Update target scan timer
         combat_unit.target_scan_timer -= delta_time;
 
@@ -391,14 +458,15 @@ pub fn target_acquisition_system(
             let shooter_pos = transform.translation();
 
             // Check enemy units first (they're the threat)
-            // Collect all enemies in range with their distances
+            // Collect all spotted enemies in range with their distances
             let mut enemies_in_range: Vec<(Entity, Vec3, f32)> = all_units.iter()
-                .filter(|(target_entity, _, target_team)| {
-                    *target_team != droid.team && *target_entity != entity
+                .filter(|(target_entity, target_position, target_team)| {
+                    diplomacy.is_hostile(droid.team, *target_team) && *target_entity != entity
+                        && vision.is_visible(droid.team, *target_position)
                 })
                 .filter_map(|&(target_entity, target_position, _)| {
                     let distance = shooter_pos.distance(target_position);
-                    if distance <= TARGETING_RANGE {
+                    if distance <= stats.range {
                         Some((target_entity, target_position, distance))
                     } else {
                         None
@@ -420,10 +488,12 @@ pub fn target_acquisition_system(
             // If no enemy units in range, check turrets (high threat buildings)
             if closest_enemy.is_none() {
                 let mut turrets_in_range: Vec<(Entity, Vec3, f32)> = all_turrets.iter()
-                    .filter(|(_, _, turret_team)| *turret_team != droid.team)
+                    .filter(|(_, turret_position, turret_team)| {
+                        diplomacy.is_hostile(droid.team, *turret_team) && vision.is_visible(droid.team, *turret_position)
+                    })
                     .filter_map(|&(turret_entity, turret_position, _)| {
                         let distance = shooter_pos.distance(turret_position);
-                        if distance <= TARGETING_RANGE {
+                        if distance <= stats.range {
                             Some((turret_entity, turret_position, distance))
                         } else {
                             None
@@ -445,10 +515,10 @@ pub fn target_acquisition_system(
             // If no turrets in range, check towers as last fallback
             if closest_enemy.is_none() {
                 let mut towers_in_range: Vec<(Entity, Vec3, f32)> = all_towers.iter()
-                    .filter(|(_, _, tower_team)| *tower_team != droid.team)
+                    .filter(|(_, _, tower_team)| diplomacy.is_hostile(droid.team, *tower_team))
                     .filter_map(|&(tower_entity, tower_position, _)| {
                         let distance = shooter_pos.distance(tower_position);
-                        if distance <= TARGETING_RANGE * 1.5 {
+                        if distance <= stats.range * 1.5 {
                             Some((tower_entity, tower_position, distance))
                         } else {
                             None
@@ -477,24 +547,28 @@ pub fn target_acquisition_system(
 pub fn hitscan_fire_system(
     time: Res<Time>,
     mut commands: Commands,
-    laser_assets: Res<LaserAssets>,
+    mut sim_rng: ResMut<SimRng>,
     spatial_grid: Res<SpatialGrid>,
-    shield_config: Res<crate::shield::ShieldConfig>,
+    (shield_config, diplomacy): (Res<crate::shield::ShieldConfig>, Res<Diplomacy>),
     mut squad_manager: ResMut<SquadManager>,
     mut combat_query: Query<
-        (&GlobalTransform, &BattleDroid, &mut CombatUnit, &MovementTracker),
+        (Entity, &GlobalTransform, &BattleDroid, &mut CombatUnit, &MovementTracker, &UnitStats, Option<&Health>),
         (Without<crate::types::TurretRotatingAssembly>, Without<HitscanTracer>, Without<KnockbackState>, Without<RagdollDeath>)
     >,
     // all_droids_query combines target lookup + hitscan collision + movement tracking
     all_droids_query: Query<(Entity, &GlobalTransform, &BattleDroid, &MovementTracker), Without<HitscanTracer>>,
     tower_target_query: Query<&GlobalTransform, With<UplinkTower>>,
-    mut turret_query: Query<(&GlobalTransform, &mut Health), With<crate::types::TurretBase>>,
-    mut tower_health_query: Query<&mut Health, (With<UplinkTower>, Without<crate::types::TurretBase>)>,
+    mut turret_query: Query<(&GlobalTransform, &mut Health, &ArmorClass), With<crate::types::TurretBase>>,
+    (mut tower_health_query, mut unit_health_query): (
+        Query<(&mut Health, &ArmorClass), (With<UplinkTower>, Without<crate::types::TurretBase>)>,
+        Query<(&mut Health, &ArmorClass), (With<SquadMember>, Without<UplinkTower>, Without<crate::types::TurretBase>)>,
+    ),
     turret_assembly_query: Query<&ChildOf, With<crate::types::TurretRotatingAssembly>>,
     mut shield_query: Query<&mut crate::shield::Shield>,
     camera_query: Query<&Transform, (With<RtsCamera>, Without<HitscanTracer>)>,
-    audio_assets: Res<AudioAssets>,
-    heightmap: Option<Res<TerrainHeightmap>>,
+    // Tracer and audio assets (absent in headless mode)
+    (laser_assets, audio_assets): (Option<Res<LaserAssets>>, Option<Res<AudioAssets>>),
+    (heightmap, cover_map): (Option<Res<TerrainHeightmap>>, Res<CoverMap>),
 ) {
     let delta_time = time.delta_secs();
 
@@ -507,7 +581,7 @@ pub fn hitscan_fire_system(
     let mut shots_fired = 0;
     const MAX_AUDIO_PER_FRAME: usize = 5;
 
-    for (droid_transform, droid, mut combat_unit, shooter_tracker) in combat_query.iter_mut() {
+    for (shooter_entity, droid_transform, droid, mut combat_unit, shooter_tracker, stats, shooter_health) in combat_query.iter_mut() {
         // Update auto fire timer
         combat_unit.auto_fire_timer -= delta_time;
 
@@ -518,7 +592,7 @@ pub fn hitscan_fire_system(
                 let target_info_opt: Option<(Vec3, bool)> = all_droids_query.get(target_entity)
                     .map(|(_, t, _, tracker)| (t.translation(), tracker.is_stationary))
                     .or_else(|_| tower_target_query.get(target_entity).map(|t| (t.translation(), true))) // Towers are stationary
-                    .or_else(|_| turret_query.get(target_entity).map(|(t, _)| (t.translation(), true))) // Turrets are stationary
+                    .or_else(|_| turret_query.get(target_entity).map(|(t, ..)| (t.translation(), true))) // Turrets are stationary
                     .ok();
 
                 let target_pos_opt = target_info_opt.map(|(pos, _)| pos);
@@ -526,7 +600,7 @@ pub fn hitscan_fire_system(
 
                 if let Some(target_pos) = target_pos_opt {
                     // Reset timer
-                    combat_unit.auto_fire_timer = AUTO_FIRE_INTERVAL;
+                    combat_unit.auto_fire_timer = stats.fire_interval * squad_manager.unit_veterancy_fire_interval(shooter_entity);
 
                     let firing_pos = droid_transform.translation() + Vec3::new(0.0, 0.8, 0.0);
                     let direction = (target_pos - firing_pos).normalize();
@@ -541,14 +615,23 @@ pub fn hitscan_fire_system(
                     combat_unit.blocked_timer = 0.0;
 
                     // === ACCURACY CHECK ===
+                    let modifiers = HitModifiers::from_formations(
+                        squad_manager.unit_formation(shooter_entity),
+                        squad_manager.unit_formation(target_entity),
+                    )
+                    .with_shooter(shooter_health.map_or(1.0, |h| h.wound_state().accuracy_modifier()))
+                    .with_shooter(squad_manager.unit_suppression_accuracy(shooter_entity))
+                    .with_shooter(squad_manager.unit_veterancy_accuracy(shooter_entity))
+                    .with_target(cover_map.exposure_modifier(target_pos, firing_pos));
                     let hit_chance = calculate_hit_chance(
-                        INFANTRY_BASE_ACCURACY,
+                        stats.accuracy,
                         firing_pos,
                         target_pos,
                         shooter_tracker.is_stationary,
                         target_stationary,
+                        modifiers,
                     );
-                    let hit_success = rand::random::<f32>() < hit_chance;
+                    let hit_success = sim_rng.gen::<f32>() < hit_chance;
 
                     let current_time = time.elapsed_secs();
                     let ray_length = firing_pos.distance(target_pos);
@@ -558,9 +641,9 @@ pub fn hitscan_fire_system(
                         // MISS - tracer goes past target (no damage)
                         // Add slight random offset for visual variety
                         let miss_offset = Vec3::new(
-                            (rand::random::<f32>() - 0.5) * 4.0,
-                            (rand::random::<f32>() - 0.5) * 4.0,
-                            (rand::random::<f32>() - 0.5) * 4.0,
+                            (sim_rng.gen::<f32>() - 0.5) * 4.0,
+                            (sim_rng.gen::<f32>() - 0.5) * 4.0,
+                            (sim_rng.gen::<f32>() - 0.5) * 4.0,
                         );
                         target_pos + miss_offset
                     } else {
@@ -569,8 +652,8 @@ pub fn hitscan_fire_system(
                         let mut shield_hit_pos: Option<Vec3> = None;
 
                         for mut shield in shield_query.iter_mut() {
-                            // Skip friendly shields
-                            if shield.team == droid.team {
+                            // Skip friendly and allied shields
+                            if diplomacy.is_allied(shield.team, droid.team) {
                                 continue;
                             }
 
@@ -589,7 +672,7 @@ pub fn hitscan_fire_system(
                                     );
                                     trace!("Hitscan shield hit: hp {} -> {}", old_hp, shield.current_hp);
                                     // Add ripple effect occasionally
-                                    if rand::random::<f32>() < 0.25 {
+                                    if sim_rng.gen::<f32>() < 0.25 {
                                         shield.add_ripple(hit_pos, current_time);
                                     }
                                     shield_hit_pos = Some(hit_pos);
@@ -608,6 +691,7 @@ pub fn hitscan_fire_system(
                                 firing_pos,
                                 direction,
                                 droid.team,
+                                &diplomacy,
                                 &spatial_grid,
                                 &all_droids_query,
                                 target_pos,
@@ -615,26 +699,38 @@ pub fn hitscan_fire_system(
 
                             match hit_result {
                                 HitscanResult::HitUnit(hit_entity, hit_pos) => {
-                                    // Despawn hit unit (try_despawn to avoid double-despawn warnings)
-                                    commands.entity(hit_entity).try_despawn();
-                                    squad_manager.remove_unit_from_squad(hit_entity);
+                                    // Wear down the unit's archetype health (anything without Health dies in one hit)
+                                    let killed = unit_health_query.get_mut(hit_entity)
+                                        .map(|(mut health, armor)| {
+                                            health.damage(stats.weapon.damage_against(stats.damage, *armor));
+                                            health.is_dead()
+                                        })
+                                        .unwrap_or(true);
+                                    if killed {
+                                        // Despawn hit unit (try_despawn to avoid double-despawn warnings)
+                                        commands.entity(hit_entity).try_despawn();
+                                        squad_manager.remove_unit_from_squad(hit_entity);
+                                        if let Some(squad_id) = squad_manager.get_unit_squad_id(shooter_entity) {
+                                            squad_manager.credit_kill(squad_id);
+                                        }
+                                    }
                                     hit_pos
                                 }
                                 HitscanResult::HitTower(hit_pos) => {
                                     // Apply damage to buildings (turrets or towers)
                                     // Try turret base directly
-                                    if let Ok((_, mut turret_health)) = turret_query.get_mut(target_entity) {
-                                        turret_health.damage(HITSCAN_DAMAGE);
+                                    if let Ok((_, mut turret_health, armor)) = turret_query.get_mut(target_entity) {
+                                        turret_health.damage(stats.weapon.damage_against(stats.damage, *armor));
                                     }
                                     // Try tower if turret query failed
-                                    else if let Ok(mut tower_health) = tower_health_query.get_mut(target_entity) {
-                                        tower_health.damage(HITSCAN_DAMAGE);
+                                    else if let Ok((mut tower_health, armor)) = tower_health_query.get_mut(target_entity) {
+                                        tower_health.damage(stats.weapon.damage_against(stats.damage, *armor));
                                     }
                                     // Target may be turret assembly (child entity) - damage parent
                                     else if let Ok(child_of) = turret_assembly_query.get(target_entity) {
                                         let parent_entity = child_of.parent();
-                                        if let Ok((_, mut turret_health)) = turret_query.get_mut(parent_entity) {
-                                            turret_health.damage(HITSCAN_DAMAGE);
+                                        if let Ok((_, mut turret_health, armor)) = turret_query.get_mut(parent_entity) {
+                                            turret_health.damage(stats.weapon.damage_against(stats.damage, *armor));
                                         }
                                     }
                                     hit_pos
@@ -644,32 +740,47 @@ pub fn hitscan_fire_system(
                         }
                     };
 
+                    // === SUPPRESSION ===
+                    // Misses whipping past hostile units pin their squads down
+                    if !hit_success {
+                        suppress_squads_near_shot(
+                            &mut squad_manager,
+                            &spatial_grid,
+                            &all_droids_query,
+                            &diplomacy,
+                            droid.team,
+                            (firing_pos, impact_pos),
+                            SUPPRESSION_NEAR_MISS_RADIUS,
+                            SUPPRESSION_PER_NEAR_MISS,
+                        );
+                    }
+
                     // === SPAWN VISUAL TRACER ===
-                    let laser_material = match droid.team {
-                        Team::A => laser_assets.team_a_material.clone(),
-                        Team::B => laser_assets.team_b_material.clone(),
-                    };
+                    // Skipped in headless mode (no render assets loaded)
+                    if let Some(laser_assets) = laser_assets.as_ref() {
+                        let laser_material = laser_assets.material(droid.team);
+
+                        // Calculate initial orientation for the tracer
+                        let velocity = direction * HITSCAN_TRACER_SPEED;
+                        let tracer_rotation = calculate_laser_orientation(velocity, firing_pos, camera_position);
 
-                    // Calculate initial orientation for the tracer
-                    let velocity = direction * HITSCAN_TRACER_SPEED;
-                    let tracer_rotation = calculate_laser_orientation(velocity, firing_pos, camera_position);
-
-                    commands.spawn((
-                        Mesh3d(laser_assets.hitscan_tracer_mesh.clone()),
-                        MeshMaterial3d(laser_material),
-                        Transform::from_translation(firing_pos).with_rotation(tracer_rotation),
-                        HitscanTracer {
-                            start_pos: firing_pos,
-                            end_pos: impact_pos,
-                            progress: 0.0,
-                            speed: HITSCAN_TRACER_SPEED,
-                            team: droid.team,
-                        },
-                    ));
+                        commands.spawn((
+                            Mesh3d(laser_assets.hitscan_tracer_mesh.clone()),
+                            MeshMaterial3d(laser_material),
+                            Transform::from_translation(firing_pos).with_rotation(tracer_rotation),
+                            HitscanTracer {
+                                start_pos: firing_pos,
+                                end_pos: impact_pos,
+                                progress: 0.0,
+                                speed: HITSCAN_TRACER_SPEED,
+                                team: droid.team,
+                            },
+                        ));
+                    }
 
                     // Play sound with proximity-based volume
                     shots_fired += 1;
-                    if shots_fired <= MAX_AUDIO_PER_FRAME {
+                    if let Some(audio_assets) = audio_assets.as_ref().filter(|_| shots_fired <= MAX_AUDIO_PER_FRAME) {
                         let mut rng = rand::thread_rng();
                         let sound = audio_assets.get_random_laser_sound(&mut rng);
                         let distance = droid_transform.translation().distance(camera_position);
@@ -692,9 +803,9 @@ pub fn hitscan_fire_system(
 pub fn turret_hitscan_fire_system(
     time: Res<Time>,
     mut commands: Commands,
-    laser_assets: Res<LaserAssets>,
+    mut sim_rng: ResMut<SimRng>,
     spatial_grid: Res<SpatialGrid>,
-    shield_config: Res<crate::shield::ShieldConfig>,
+    (shield_config, diplomacy): (Res<crate::shield::ShieldConfig>, Res<Diplomacy>),
     mut squad_manager: ResMut<SquadManager>,
     mut turret_query: Query<(
         Entity,
@@ -708,16 +819,20 @@ pub fn turret_hitscan_fire_system(
     // For target lookup, validation, and hitscan collision
     all_droids_query: Query<(Entity, &GlobalTransform, &BattleDroid, &MovementTracker), Without<HitscanTracer>>,
     all_towers_query: Query<(Entity, &GlobalTransform, &UplinkTower)>,
-    // For applying damage to buildings
-    mut turret_health_query: Query<(&GlobalTransform, &mut Health), With<crate::types::TurretBase>>,
-    mut tower_health_query: Query<&mut Health, (With<UplinkTower>, Without<crate::types::TurretBase>)>,
+    // For applying damage to buildings and units
+    mut turret_health_query: Query<(&GlobalTransform, &mut Health, &ArmorClass), With<crate::types::TurretBase>>,
+    (mut tower_health_query, mut unit_health_query): (
+        Query<(&mut Health, &ArmorClass), (With<UplinkTower>, Without<crate::types::TurretBase>)>,
+        Query<(&mut Health, &ArmorClass), (With<SquadMember>, Without<UplinkTower>, Without<crate::types::TurretBase>)>,
+    ),
     turret_assembly_query: Query<&ChildOf, With<crate::types::TurretRotatingAssembly>>,
     // For shield intersection
     mut shield_query: Query<&mut crate::shield::Shield>,
     // For tracer orientation
     camera_query: Query<&Transform, (With<RtsCamera>, Without<HitscanTracer>)>,
-    audio_assets: Res<AudioAssets>,
-    heightmap: Option<Res<TerrainHeightmap>>,
+    // Tracer and audio assets (absent in headless mode)
+    (laser_assets, audio_assets): (Option<Res<LaserAssets>>, Option<Res<AudioAssets>>),
+    (heightmap, cover_map, vision): (Option<Res<TerrainHeightmap>>, Res<CoverMap>, Res<TeamVision>),
 ) {
     let delta_time = time.delta_secs();
 
@@ -754,6 +869,14 @@ pub fn turret_hitscan_fire_system(
     let mg_burst_volume_scale = 1.0 / (active_mg_bursts as f32).sqrt();
 
     for (turret_entity, global_transform, local_transform, droid, mut combat_unit, mut turret, mut mg_turret_opt) in turret_query.iter_mut() {
+        // A destroyed turret stops firing now, not once turret_destruction_system despawns it
+        let base_dead = turret_assembly_query.get(turret_entity).is_ok_and(|child_of| {
+            turret_health_query.get(child_of.parent()).is_ok_and(|(_, health, _)| health.is_dead())
+        });
+        if base_dead {
+            continue;
+        }
+
         // === MG FIRING MODE CONTROL ===
         let mut can_fire = true;
         if let Some(ref mut mg_turret) = mg_turret_opt {
@@ -794,7 +917,9 @@ pub fn turret_hitscan_fire_system(
 
                 // Check enemy units first
                 for (target_entity, target_transform, target_droid, _) in all_droids_query.iter() {
-                    if target_droid.team != droid.team {
+                    if diplomacy.is_hostile(droid.team, target_droid.team)
+                        && vision.is_visible(droid.team, target_transform.translation())
+                    {
                         let distance = shooter_pos.distance(target_transform.translation());
                         if distance <= TARGETING_RANGE {
                             if closest_enemy.map(|(_, d)| distance < d).unwrap_or(true) {
@@ -807,7 +932,7 @@ pub fn turret_hitscan_fire_system(
                 // Fallback to towers
                 if closest_enemy.is_none() {
                     for (target_entity, target_transform, target_tower) in all_towers_query.iter() {
-                        if target_tower.team != droid.team {
+                        if diplomacy.is_hostile(droid.team, target_tower.team) {
                             let distance = shooter_pos.distance(target_transform.translation());
                             if distance <= TARGETING_RANGE {
                                 if closest_enemy.map(|(_, d)| distance < d).unwrap_or(true) {
@@ -832,14 +957,15 @@ pub fn turret_hitscan_fire_system(
                 let target_info_opt: Option<(Vec3, bool)> = all_droids_query.get(target_entity)
                     .map(|(_, t, _, tracker)| (t.translation(), tracker.is_stationary))
                     .or_else(|_| all_towers_query.get(target_entity).map(|(_, t, _)| (t.translation(), true)))
-                    .or_else(|_| turret_health_query.get(target_entity).map(|(t, _)| (t.translation(), true)))
+                    .or_else(|_| turret_health_query.get(target_entity).map(|(t, ..)| (t.translation(), true)))
                     .ok();
 
                 if let Some((target_pos, target_stationary)) = target_info_opt {
                     let is_mg = mg_turret_opt.is_some();
 
-                    // Determine fire interval based on turret type
+                    // Determine fire interval and weapon based on turret type
                     let fire_interval = if is_mg { 0.05 } else { AUTO_FIRE_INTERVAL };
+                    let weapon = turret_weapon(is_mg);
                     combat_unit.auto_fire_timer = fire_interval;
 
                     // Calculate firing position from barrel
@@ -881,8 +1007,10 @@ pub fn turret_hitscan_fire_system(
                         target_pos,
                         true, // Turrets always stationary
                         target_stationary,
+                        HitModifiers::from_formations(None, squad_manager.unit_formation(target_entity))
+                            .with_target(cover_map.exposure_modifier(target_pos, firing_pos)),
                     );
-                    let hit_success = rand::random::<f32>() < hit_chance;
+                    let hit_success = sim_rng.gen::<f32>() < hit_chance;
 
                     let current_time = time.elapsed_secs();
                     let ray_length = firing_pos.distance(target_pos);
@@ -891,9 +1019,9 @@ pub fn turret_hitscan_fire_system(
                     let impact_pos = if !hit_success {
                         // MISS - tracer goes past target
                         let miss_offset = Vec3::new(
-                            (rand::random::<f32>() - 0.5) * 4.0,
-                            (rand::random::<f32>() - 0.5) * 4.0,
-                            (rand::random::<f32>() - 0.5) * 4.0,
+                            (sim_rng.gen::<f32>() - 0.5) * 4.0,
+                            (sim_rng.gen::<f32>() - 0.5) * 4.0,
+                            (sim_rng.gen::<f32>() - 0.5) * 4.0,
                         );
                         target_pos + miss_offset
                     } else {
@@ -901,7 +1029,7 @@ pub fn turret_hitscan_fire_system(
                         let mut shield_hit_pos: Option<Vec3> = None;
 
                         for mut shield in shield_query.iter_mut() {
-                            if shield.team == droid.team {
+                            if diplomacy.is_allied(shield.team, droid.team) {
                                 continue;
                             }
 
@@ -916,7 +1044,7 @@ pub fn turret_hitscan_fire_system(
                                         shield_config.impact_flash_duration
                                     );
                                     trace!("Turret hitscan shield hit: hp {} -> {}", old_hp, shield.current_hp);
-                                    if rand::random::<f32>() < 0.25 {
+                                    if sim_rng.gen::<f32>() < 0.25 {
                                         shield.add_ripple(hit_pos, current_time);
                                     }
                                     shield_hit_pos = Some(hit_pos);
@@ -933,6 +1061,7 @@ pub fn turret_hitscan_fire_system(
                                 firing_pos,
                                 direction,
                                 droid.team,
+                                &diplomacy,
                                 &spatial_grid,
                                 &all_droids_query,
                                 target_pos,
@@ -940,20 +1069,28 @@ pub fn turret_hitscan_fire_system(
 
                             match hit_result {
                                 HitscanResult::HitUnit(hit_entity, hit_pos) => {
-                                    commands.entity(hit_entity).try_despawn();
-                                    squad_manager.remove_unit_from_squad(hit_entity);
+                                    let killed = unit_health_query.get_mut(hit_entity)
+                                        .map(|(mut health, armor)| {
+                                            health.damage(weapon.damage_against(HITSCAN_DAMAGE, *armor));
+                                            health.is_dead()
+                                        })
+                                        .unwrap_or(true);
+                                    if killed {
+                                        commands.entity(hit_entity).try_despawn();
+                                        squad_manager.remove_unit_from_squad(hit_entity);
+                                    }
                                     hit_pos
                                 }
                                 HitscanResult::HitTower(hit_pos) => {
                                     // Apply damage to buildings
-                                    if let Ok((_, mut health)) = turret_health_query.get_mut(target_entity) {
-                                        health.damage(HITSCAN_DAMAGE);
-                                    } else if let Ok(mut health) = tower_health_query.get_mut(target_entity) {
-                                        health.damage(HITSCAN_DAMAGE);
+                                    if let Ok((_, mut health, armor)) = turret_health_query.get_mut(target_entity) {
+                                        health.damage(weapon.damage_against(HITSCAN_DAMAGE, *armor));
+                                    } else if let Ok((mut health, armor)) = tower_health_query.get_mut(target_entity) {
+                                        health.damage(weapon.damage_against(HITSCAN_DAMAGE, *armor));
                                     } else if let Ok(child_of) = turret_assembly_query.get(target_entity) {
                                         let parent_entity = child_of.parent();
-                                        if let Ok((_, mut health)) = turret_health_query.get_mut(parent_entity) {
-                                            health.damage(HITSCAN_DAMAGE);
+                                        if let Ok((_, mut health, armor)) = turret_health_query.get_mut(parent_entity) {
+                                            health.damage(weapon.damage_against(HITSCAN_DAMAGE, *armor));
                                         }
                                     }
                                     hit_pos
@@ -963,35 +1100,63 @@ pub fn turret_hitscan_fire_system(
                         }
                     };
 
+                    // === SUPPRESSION ===
+                    // Continuous MG fire is area denial: every round, hit or miss, pins down
+                    // squads around its impact. Other turret fire only suppresses on near misses.
+                    if is_continuous_mode {
+                        suppress_squads_near_shot(
+                            &mut squad_manager,
+                            &spatial_grid,
+                            &all_droids_query,
+                            &diplomacy,
+                            droid.team,
+                            (impact_pos, impact_pos),
+                            SUPPRESSION_MG_AREA_RADIUS,
+                            SUPPRESSION_PER_MG_ROUND,
+                        );
+                    } else if !hit_success {
+                        suppress_squads_near_shot(
+                            &mut squad_manager,
+                            &spatial_grid,
+                            &all_droids_query,
+                            &diplomacy,
+                            droid.team,
+                            (firing_pos, impact_pos),
+                            SUPPRESSION_NEAR_MISS_RADIUS,
+                            SUPPRESSION_PER_NEAR_MISS,
+                        );
+                    }
+
                     // === SPAWN VISUAL TRACER ===
-                    // Turrets are Team A = green
-                    let laser_material = laser_assets.team_a_material.clone();
+                    if let Some(laser_assets) = laser_assets.as_ref() {
+                        let laser_material = laser_assets.material(droid.team);
 
-                    // Use MG mesh for MG turrets, standard for heavy
-                    let tracer_mesh = if is_mg {
-                        laser_assets.mg_laser_mesh.clone()
-                    } else {
-                        laser_assets.hitscan_tracer_mesh.clone()
-                    };
+                        // Use MG mesh for MG turrets, standard for heavy
+                        let tracer_mesh = if is_mg {
+                            laser_assets.mg_laser_mesh.clone()
+                        } else {
+                            laser_assets.hitscan_tracer_mesh.clone()
+                        };
 
-                    // Faster tracer speed for turrets (instant feel)
-                    let tracer_speed = if is_mg { 600.0 } else { 500.0 };
-
-                    let velocity = direction * tracer_speed;
-                    let tracer_rotation = calculate_laser_orientation(velocity, firing_pos, camera_position);
-
-                    commands.spawn((
-                        Mesh3d(tracer_mesh),
-                        MeshMaterial3d(laser_material),
-                        Transform::from_translation(firing_pos).with_rotation(tracer_rotation),
-                        HitscanTracer {
-                            start_pos: firing_pos,
-                            end_pos: impact_pos,
-                            progress: 0.0,
-                            speed: tracer_speed,
-                            team: droid.team,
-                        },
-                    ));
+                        // Faster tracer speed for turrets (instant feel)
+                        let tracer_speed = if is_mg { 600.0 } else { 500.0 };
+
+                        let velocity = direction * tracer_speed;
+                        let tracer_rotation = calculate_laser_orientation(velocity, firing_pos, camera_position);
+
+                        commands.spawn((
+                            Mesh3d(tracer_mesh),
+                            MeshMaterial3d(laser_material),
+                            Transform::from_translation(firing_pos).with_rotation(tracer_rotation),
+                            HitscanTracer {
+                                start_pos: firing_pos,
+                                end_pos: impact_pos,
+                                progress: 0.0,
+                                speed: tracer_speed,
+                                team: droid.team,
+                            },
+                        ));
+                    }
 
                     // Advance barrel index (MG has 1 barrel, Heavy has 2)
                     let barrel_count = if is_mg { 1 } else { standard_barrel_positions.len() };
@@ -1003,6 +1168,9 @@ pub fn turret_hitscan_fire_system(
                     }
 
                     // === AUDIO ===
+                    let Some(audio_assets) = audio_assets.as_ref() else {
+                        continue;
+                    };
                     if is_mg {
                         // Play burst sound once at start of each burst (not per-shot)
                         let burst_just_started = mg_turret_opt.as_ref()
@@ -1139,6 +1307,7 @@ fn perform_hitscan(
     start: Vec3,
     direction: Vec3,
     shooter_team: Team,
+    diplomacy: &Diplomacy,
     spatial_grid: &SpatialGrid,
     droid_query: &Query<(Entity, &GlobalTransform, &BattleDroid, &MovementTracker), Without<HitscanTracer>>,
     target_pos: Vec3,
@@ -1160,8 +1329,8 @@ fn perform_hitscan(
 
         for &entity in &nearby {
             if let Ok((_, droid_transform, droid, _)) = droid_query.get(entity) {
-                // Skip friendly fire
-                if droid.team == shooter_team {
+                // Skip friendly and allied fire
+                if diplomacy.is_allied(droid.team, shooter_team) {
                     continue;
                 }
 
@@ -1207,6 +1376,50 @@ fn perform_hitscan(
     }
 }
 
+/// Suppress the squads of hostile units within `radius` of a shot's path (start, end).
+/// Only units around the end of the path are checked, and each squad counts once per shot.
+fn suppress_squads_near_shot(
+    squad_manager: &mut SquadManager,
+    spatial_grid: &SpatialGrid,
+    droid_query: &Query<(Entity, &GlobalTransform, &BattleDroid, &MovementTracker), Without<HitscanTracer>>,
+    diplomacy: &Diplomacy,
+    shooter_team: Team,
+    (shot_start, shot_end): (Vec3, Vec3),
+    radius: f32,
+    amount: f32,
+) {
+    let segment = shot_end - shot_start;
+    let mut suppressed: Vec<u32> = Vec::new();
+
+    for entity in spatial_grid.get_nearby_droids(shot_end) {
+        let Ok((_, transform, droid, _)) = droid_query.get(entity) else {
+            continue;
+        };
+        if !diplomacy.is_hostile(shooter_team, droid.team) {
+            continue;
+        }
+
+        // Closest point of the shot to the unit
+        let position = transform.translation();
+        let t = ((position - shot_start).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+        if position.distance(shot_start + segment * t) > radius {
+            continue;
+        }
+
+        if let Some(squad_id) = squad_manager.get_unit_squad_id(entity) {
+            if !suppressed.contains(&squad_id) {
+                suppressed.push(squad_id);
+            }
+        }
+    }
+
+    for squad_id in suppressed {
+        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
+            squad.suppress(amount);
+        }
+    }
+}
+
 /// Update hitscan tracers - move them along their path and despawn when done
 pub fn update_hitscan_tracers(
     time: Res<Time>,
@@ -1250,7 +1463,10 @@ pub fn collision_detection_system(
     laser_query: Query<(Entity, &Transform, &LaserProjectile)>,
     droid_query: Query<(Entity, &Transform, &BattleDroid, &SquadMember, &MovementTracker), Without<LaserProjectile>>,
     building_query: Query<(Entity, &GlobalTransform, &crate::types::BuildingCollider)>,
-    mut turret_health_query: Query<&mut crate::types::Health, With<crate::types::TurretBase>>,
+    mut turret_health_query: Query<(&mut crate::types::Health, &crate::types::TurretBase, &ArmorClass)>,
+    mut unit_health_query: Query<(&mut Health, &ArmorClass), (With<SquadMember>, Without<crate::types::TurretBase>)>,
+    mut sim_rng: ResMut<SimRng>,
+    diplomacy: Res<Diplomacy>,
 ) {
     // Clear and rebuild the spatial grid each frame
     spatial_grid.clear();
@@ -1281,10 +1497,10 @@ pub fn collision_detection_system(
                 hit_building = true;
 
                 // Apply damage to turrets if hit by enemy laser
-                if let Ok(mut turret_health) = turret_health_query.get_mut(building_entity) {
-                    // Only enemy lasers damage turrets (turrets are Team::A, enemies are Team::B)
-                    if laser.team == crate::types::Team::B {
-                        turret_health.damage(25.0); // Same damage as tower hits
+                if let Ok((mut turret_health, turret, armor)) = turret_health_query.get_mut(building_entity) {
+                    // Only enemy lasers damage turrets
+                    if diplomacy.is_hostile(laser.team, turret.team) {
+                        turret_health.damage(laser.weapon.damage_against(laser.damage, *armor));
                     }
                 }
                 break;
@@ -1307,8 +1523,8 @@ pub fn collision_detection_system(
 
             // Get droid data - we need to check if it still exists and get its data
             if let Ok((_, droid_transform, droid, _squad_member, movement_tracker)) = droid_query.get(droid_entity) {
-                // Skip friendly fire
-                if laser.team == droid.team {
+                // Skip friendly and allied fire
+                if diplomacy.is_allied(laser.team, droid.team) {
                     continue;
                 }
 
@@ -1323,16 +1539,25 @@ pub fn collision_detection_system(
                         droid_transform.translation,
                         true, // Turrets are always stationary
                         movement_tracker.is_stationary,
+                        HitModifiers::from_formations(None, squad_manager.unit_formation(droid_entity)),
                     );
 
                     // Always despawn the laser on collision
                     entities_to_despawn.insert(laser_entity);
 
-                    // Only despawn/kill the droid if hit succeeds
-                    if rand::random::<f32>() < hit_chance {
-                        entities_to_despawn.insert(droid_entity);
-                        // Handle squad casualty immediately (commander promotion, etc.)
-                        squad_manager.remove_unit_from_squad(droid_entity);
+                    // Only damage the droid if hit succeeds; kill it once its health runs out
+                    if sim_rng.gen::<f32>() < hit_chance {
+                        let killed = unit_health_query.get_mut(droid_entity)
+                            .map(|(mut health, armor)| {
+                                health.damage(laser.weapon.damage_against(laser.damage, *armor));
+                                health.is_dead()
+                            })
+                            .unwrap_or(true);
+                        if killed {
+                            entities_to_despawn.insert(droid_entity);
+                            // Handle squad casualty immediately (commander promotion, etc.)
+                            squad_manager.remove_unit_from_squad(droid_entity);
+                        }
                     }
                     // If miss, laser still despawns but droid survives
 
@@ -1540,4 +1765,17 @@ pub fn clear_blocked_targets_system(
             combat.blocked_timer = 0.0;
         }
     }
-} 
\ No newline at end of file
+} 
+
+/// Refresh each squad's average health fraction from its living members
+pub fn squad_health_system(
+    mut squad_manager: ResMut<SquadManager>,
+    health_query: Query<&Health, With<SquadMember>>,
+) {
+    for squad in squad_manager.squads.values_mut() {
+        let (total, count) = squad.members.iter()
+            .filter_map(|&member| health_query.get(member).ok())
+            .fold((0.0, 0), |(total, count), health| (total + health.health_percentage(), count + 1));
+        squad.average_health = if count > 0 { total / count as f32 } else { 0.0 };
+    }
+}
@@ -26,6 +26,11 @@ pub fn commander_promotion_system(
     
     // Update squads that need new commanders
     for (squad_id, _old_commander, _team) in squads_needing_update {
+        // Losing the commander shakes the squad (see squad_morale_system)
+        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
+            squad.commander_lost = true;
+        }
+
         // Find a new commander from this squad
         let mut potential_commander = None;
         for (entity, squad_member) in unit_query.iter() {
@@ -105,10 +110,7 @@ pub fn commander_visual_update_system(
         if make_commander {
             // Create unique commander materials for this promoted unit
             let new_commander_body = materials.add(StandardMaterial {
-                base_color: match team {
-                    Team::A => Color::srgb(0.9, 0.8, 0.4), // Golden yellow
-                    Team::B => Color::srgb(0.9, 0.5, 0.3), // Orange-red
-                },
+                base_color: team.palette().commander_body,
                 metallic: 0.5,
                 perceptual_roughness: 0.3,
                 alpha_mode: AlphaMode::Opaque,
@@ -128,10 +130,7 @@ pub fn commander_visual_update_system(
             for &child_entity in children.iter() {
                 if head_query.get(child_entity).is_ok() {
                     let new_commander_head = materials.add(StandardMaterial {
-                        base_color: match team {
-                            Team::A => Color::srgb(1.0, 0.9, 0.5), // Bright gold
-                            Team::B => Color::srgb(1.0, 0.6, 0.4), // Bright orange
-                        },
+                        base_color: team.palette().commander_head,
                         metallic: 0.4,
                         perceptual_roughness: 0.4,
                         ..default()
@@ -148,14 +147,8 @@ pub fn commander_visual_update_system(
         } else {
             // Revert to regular materials with safety checks
             let new_regular_body = materials.add(StandardMaterial {
-                base_color: match team {
-                    Team::A => Color::srgb(0.7, 0.7, 0.8), // Regular blue-gray
-                    Team::B => Color::srgb(0.9, 0.9, 0.95), // Regular white
-                },
-                metallic: match team {
-                    Team::A => 0.3,
-                    Team::B => 0.4,
-                },
+                base_color: team.palette().body,
+                metallic: team.palette().body_metallic,
                 perceptual_roughness: 0.5,
                 ..default()
             });
@@ -171,14 +164,8 @@ pub fn commander_visual_update_system(
             for &child_entity in children.iter() {
                 if head_query.get(child_entity).is_ok() {
                     let new_regular_head = materials.add(StandardMaterial {
-                        base_color: match team {
-                            Team::A => Color::srgb(0.8, 0.6, 0.4), // Regular head
-                            Team::B => Color::srgb(0.95, 0.95, 1.0), // Regular head
-                        },
-                        metallic: match team {
-                            Team::A => 0.2,
-                            Team::B => 0.3,
-                        },
+                        base_color: team.palette().head,
+                        metallic: team.palette().body_metallic - 0.1,
                         perceptual_roughness: 0.6,
                         ..default()
                     });
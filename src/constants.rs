@@ -1,11 +1,15 @@
-pub const ARMY_SIZE_PER_TEAM: usize = 5_000;
+pub const ARMY_SIZE_PER_TEAM: usize = 5_000; // In a two-team battle; more teams split 2x this between them
 #[allow(dead_code)]
 pub const FORMATION_WIDTH: f32 = 200.0;
 #[allow(dead_code)]
 pub const UNIT_SPACING: f32 = 2.0;
 pub const MARCH_DISTANCE: f32 = 150.0;
-pub const MARCH_SPEED: f32 = 3.0;
+pub const MARCH_SPEED: f32 = 3.0; // Battle droid default; per-unit speed comes from the unit archetype
 pub const BATTLEFIELD_SIZE: f32 = 400.0;
+pub const MULTI_TEAM_SPAWN_SCALE: f32 = 1.25; // Armies start further out with 3+ teams so sides don't overlap
+
+// Simulation timestep
+pub const SIMULATION_TICK_RATE: f64 = 60.0; // Gameplay ticks per second (FixedUpdate), override with --tick-rate
 
 // Squad and formation constants
 pub const SQUAD_SIZE: usize = 50;
@@ -30,6 +34,26 @@ pub const LASER_LIFETIME: f32 = 3.0;
 pub const LASER_LENGTH: f32 = 3.0;
 pub const LASER_WIDTH: f32 = 0.2;
 
+// Unit archetypes (see archetype.rs). The infantry stats below are the battle droid
+// defaults used when an archetype file leaves a field out.
+pub const UNIT_ARCHETYPE_DIR: &str = "assets/units";
+pub const DEFAULT_UNIT_ARCHETYPE: &str = "battle_droid";
+pub const WALKER_ARCHETYPE: &str = "walker";
+pub const INFANTRY_HEALTH: f32 = 50.0; // Two HITSCAN_DAMAGE hits
+
+// Scenarios (see scenario/definition.rs)
+pub const SCENARIO_DIR: &str = "assets/scenarios";
+pub const DEFAULT_SCENARIO: &str = "firebase_delta"; // Built in, always available
+pub const SCENARIO_MESSAGE_SECONDS: f32 = 6.0; // How long trigger messages stay on screen
+
+// Wounds: infantry below these health fractions fight and march worse (see WoundState)
+pub const WOUNDED_HEALTH_FRACTION: f32 = 0.75;
+pub const CRITICAL_HEALTH_FRACTION: f32 = 0.35;
+pub const WOUNDED_SPEED_MULTIPLIER: f32 = 0.85;
+pub const CRITICAL_SPEED_MULTIPLIER: f32 = 0.6;
+pub const WOUNDED_ACCURACY_MULTIPLIER: f32 = 0.9;
+pub const CRITICAL_ACCURACY_MULTIPLIER: f32 = 0.75;
+
 // Combat settings
 pub const TARGETING_RANGE: f32 = 200.0;
 pub const TARGET_SCAN_INTERVAL: f32 = 2.0;
@@ -40,7 +64,17 @@ pub const AUTO_FIRE_INTERVAL: f32 = 2.0;
 pub const HITSCAN_TRACER_SPEED: f32 = 400.0;  // Visual tracer speed (faster than projectiles for snappy feel)
 pub const HITSCAN_TRACER_LENGTH: f32 = 4.0;   // Length of the tracer bolt visual
 pub const HITSCAN_TRACER_WIDTH: f32 = 0.25;   // Slightly wider than projectiles for visibility
-pub const HITSCAN_DAMAGE: f32 = 25.0;         // Damage per hitscan hit (buildings)
+pub const HITSCAN_DAMAGE: f32 = 25.0;         // Damage per hitscan hit (base value, scaled by DAMAGE_MATRIX)
+
+// Weapon vs armor damage multipliers (see WeaponType::damage_against)
+// Rows: PulsedLaser, Kinetic, AntiArmor, HeavyLaser
+// Columns: Infantry, Vehicle, Structure
+pub const DAMAGE_MATRIX: [[f32; 3]; 4] = [
+    [1.0, 0.25, 1.0], // PulsedLaser: infantry rifles, all-rounder that barely scratches armor
+    [1.5, 0.2, 0.5],  // Kinetic: MG turrets mow infantry, bullets glance off armor
+    [0.5, 3.0, 1.5],  // AntiArmor: rockets built to crack vehicles and bunkers
+    [1.0, 2.5, 1.5],  // HeavyLaser: heavy turrets shred vehicles
+];
 
 // MG turret barrel geometry — shared by the spawn code (turrets.rs), the rotation
 // system, and the firing system. These MUST stay in sync or the visual barrel
@@ -71,6 +105,32 @@ pub const MAX_EXPLOSIONS_PER_FRAME: usize = 50; // Limit explosions per frame to
 #[allow(dead_code)]
 pub const PARTICLE_SPAWN_PROBABILITY: f32 = 0.3; // Probability (0.0-1.0) that an explosion spawns particles
 
+// Capture points and victory points (see capture.rs)
+pub const CAPTURE_POINT_RADIUS: f32 = 25.0;
+pub const CAPTURE_TIME: f32 = 20.0; // Seconds for one side to take (or neutralise) an uncontested point
+pub const CAPTURE_POINTS_PER_SECOND: f32 = 1.0; // Victory points every owned point earns
+pub const CAPTURE_INCOME_POINTS: f32 = 2.0; // Extra points per second from an Income point (skirmish maps)
+pub const CAPTURE_REINFORCEMENT_INTERVAL: f32 = 60.0; // Seconds between squads from a Reinforcements point
+pub const CAPTURE_REINFORCEMENT_MAX_SQUADS: usize = 2; // Squads from one point alive at a time
+pub const CAPTURE_FLANK_OFFSET: f32 = 140.0; // Skirmish flank points, either side of the center point
+pub const SECONDARY_OBJECTIVE_POINTS: f32 = 100.0; // Awarded to every hostile team when a secondary objective falls
+
+// Credits and production (see economy.rs)
+pub const STARTING_CREDITS: f32 = 300.0;
+pub const CREDITS_PER_SECOND: f32 = 3.0; // Passive income for every team
+pub const CREDITS_PER_VICTORY_POINT: f32 = 1.0; // Capture points and secondary objectives pay out as they score
+pub const SQUAD_COST: f32 = 250.0; // Archetypes without their own cost
+pub const SQUAD_BUILD_TIME: f32 = 20.0; // Seconds
+pub const MG_TURRET_COST: f32 = 150.0;
+pub const HEAVY_TURRET_COST: f32 = 250.0;
+pub const TURRET_BUILD_TIME: f32 = 12.0;
+pub const ARTILLERY_STRIKE_COST: f32 = 120.0; // Scatter and line barrages (the V single shot stays free)
+pub const ARTILLERY_CALL_IN_TIME: f32 = 4.0; // Seconds from the front of the queue to the shells being fired
+pub const PRODUCTION_QUEUE_MAX: usize = 5; // Orders per team
+pub const PRODUCTION_SPAWN_DISTANCE: f32 = 45.0; // New squads form up this far in front of the tower
+pub const PRODUCTION_TURRET_DISTANCE: f32 = 30.0; // New turrets ring the tower at this distance
+pub const PRODUCTION_TURRET_SPACING: f32 = 0.5; // Radians between neighbouring turrets on the ring
+
 // Selection system settings
 pub const SELECTION_CLICK_RADIUS: f32 = 15.0;       // How close to squad center to select (generous for usability)
 pub const SELECTION_RING_INNER_RADIUS: f32 = 8.0;   // Inner radius of selection ring
@@ -138,6 +198,9 @@ pub fn proximity_volume(distance: f32, max_volume: f32) -> f32 {
 pub const AREA_DAMAGE_CORE_RADIUS: f32 = 5.0;   // Instant death zone
 pub const AREA_DAMAGE_MID_RADIUS: f32 = 12.0;   // RNG death zone (probability decreases with distance)
 pub const AREA_DAMAGE_RIM_RADIUS: f32 = 20.0;   // Knockback only zone
+pub const AREA_DAMAGE_SHRAPNEL: f32 = 25.0;    // Damage to mid-zone survivors (can still kill the wounded)
+pub const AREA_DAMAGE_BLAST: f32 = 100.0;      // Base blast damage to armored units at the core, falling off to the mid boundary
+pub const AREA_DAMAGE_WEAPON: crate::types::WeaponType = crate::types::WeaponType::AntiArmor; // Blast row of DAMAGE_MATRIX
 
 /// Knockback physics (for units in rim zone)
 pub const KNOCKBACK_BASE_SPEED: f32 = 15.0;     // Base launch velocity
@@ -174,10 +237,10 @@ pub const SOFT_AVOIDANCE_RADIUS: f32 = 2.0;
 pub const SOFT_AVOIDANCE_STRENGTH: f32 = 0.0;
 /// Push force multiplier for hard collision resolution
 pub const COLLISION_PUSH_STRENGTH: f32 = 8.0;
-/// Default mass for battle droids (future: heavy platforms = 5.0, drones = 0.3)
+/// Default mass for battle droids (other unit types set theirs in the archetype file)
 pub const DEFAULT_UNIT_MASS: f32 = 1.0;
-/// How often to run collision (1 = every frame, 2 = every other frame, etc.)
-pub const COLLISION_FRAME_SKIP: u32 = 2;
+/// How often to run collision (1 = every tick, 2 = every other tick, etc.)
+pub const COLLISION_TICK_SKIP: u32 = 2;
 /// Threshold for considering a unit "stationary" (target ~= spawn)
 pub const STATIONARY_THRESHOLD: f32 = 0.5;
 
@@ -220,4 +283,102 @@ pub const ACCURACY_STATIONARY_TIME_THRESHOLD: f32 = 0.5;
 pub const ACCURACY_MOVEMENT_THRESHOLD: f32 = 0.5;
 /// Time before clearing target if unit can't fire (stuck prevention for AttackMove)
 /// Should be longer than AUTO_FIRE_INTERVAL to allow normal fire cycles
-pub const BLOCKED_TARGET_TIMEOUT: f32 = 3.0;
\ No newline at end of file
+pub const BLOCKED_TARGET_TIMEOUT: f32 = 3.0;
+// ===== FORMATIONS =====
+pub const FORMATION_LINE_WIDTH: usize = 25;      // Men per rank in Line (2 ranks)
+pub const FORMATION_COLUMN_WIDTH: usize = 5;     // Men per rank in Column (10 ranks)
+pub const FORMATION_LOOSE_SPACING: f32 = 2.0;    // Spacing multiplier for Loose/Skirmish
+pub const FORMATION_RESHAPE_SPEED: f32 = 0.6;    // Formation changes complete in ~1.7s
+
+// ===== MORALE =====
+
+pub const MORALE_CASUALTY_WEIGHT: f32 = 1.5;     // Morale lost per full squad's worth of casualties
+pub const MORALE_COMMANDER_LOSS: f32 = 0.2;      // Losing the squad commander
+pub const MORALE_EXPLOSION_RADIUS: f32 = 30.0;   // Squads centered this close to a blast are shaken (x blast scale)
+pub const MORALE_EXPLOSION_LOSS: f32 = 0.1;      // Per nearby blast (x blast scale)
+pub const MORALE_TOWER_LOSS: f32 = 0.35;         // Every squad of a team (and its allies) whose tower falls
+pub const MORALE_RECOVERY_RATE: f32 = 0.04;      // Per second while near friendly squads or a friendly tower
+pub const MORALE_FRIENDLY_RADIUS: f32 = 40.0;    // Steady allied squads within this distance reassure
+pub const MORALE_TOWER_RADIUS: f32 = 100.0;      // A standing allied tower within this distance reassures
+pub const MORALE_BREAK_THRESHOLD: f32 = 0.25;    // Squads at or below this rout
+pub const MORALE_RALLY_THRESHOLD: f32 = 0.6;     // Routing squads rally once recovered to this
+pub const MORALE_MIN_ROUT_TIME: f32 = 10.0;      // Seconds a squad ignores orders after breaking
+
+// ===== SUPPRESSION =====
+
+pub const SUPPRESSION_NEAR_MISS_RADIUS: f32 = 3.0;     // A missed shot passing this close to a unit suppresses its squad
+pub const SUPPRESSION_PER_NEAR_MISS: f32 = 0.02;       // Squad suppression (0-1) added per near miss
+pub const SUPPRESSION_MG_AREA_RADIUS: f32 = 5.0;       // Continuous MG fire suppresses every squad this close to its impacts (<= GRID_CELL_SIZE)
+pub const SUPPRESSION_PER_MG_ROUND: f32 = 0.01;        // Per Continuous MG round, hit or miss (~20 rounds/s)
+pub const SUPPRESSION_EXPLOSION_RADIUS: f32 = 40.0;    // Blast suppression radius (x blast scale)
+pub const SUPPRESSION_PER_EXPLOSION: f32 = 0.3;        // Per nearby blast (x blast scale)
+pub const SUPPRESSION_DECAY_RATE: f32 = 0.15;          // Suppression shed per second
+pub const SUPPRESSION_ACCURACY_PENALTY: f32 = 0.5;     // Fully suppressed squads hit half as often
+pub const SUPPRESSION_SPEED_PENALTY: f32 = 0.5;        // ... and crawl at half speed
+
+// ===== VETERANCY =====
+
+pub const VETERANCY_RANK_KILLS: [u32; 3] = [10, 30, 75];  // Squad kills needed for Veteran, Elite and Heroic
+pub const VETERANCY_ACCURACY_PER_RANK: f32 = 0.08;         // Shooter accuracy multiplier gained per rank
+pub const VETERANCY_FIRE_RATE_PER_RANK: f32 = 0.1;         // Fire interval shortened by this fraction per rank
+pub const RANK_CHEVRON_HEIGHT: f32 = 4.2;                  // Above the commander, just over the commander marker (3.0)
+
+// ===== COVER =====
+
+pub const COVER_MIN_ROCK_SCALE: f32 = 1.2;   // Smaller rocks are too small to hide behind
+pub const COVER_RANGE: f32 = 3.0;            // Units this far past a cover object's edge can use it
+pub const COVER_MIN_ALIGNMENT: f32 = 0.5;    // Cover must lie within ~60 degrees of the line to the shooter
+pub const COVER_EXPOSURE: f32 = 0.5;         // Hit chance multiplier against units in cover
+pub const COVER_SNAP_RADIUS: f32 = 20.0;     // Move orders this close to cover snap behind it
+pub const COVER_STANDOFF: f32 = 1.5;         // Gap left between a snapped squad's center and the cover (< COVER_RANGE)
+pub const COVER_CELL_SIZE: f32 = 10.0;       // CoverMap bucket size (>= largest rock radius + COVER_RANGE)
+
+// ===== FOG OF WAR =====
+
+pub const VISION_CELL_SIZE: f32 = 10.0;           // Visibility grid resolution (grid spans TERRAIN_SIZE)
+pub const VISION_SQUAD_SIGHT_RANGE: f32 = 180.0;  // Seen from the squad's center
+pub const VISION_TURRET_SIGHT_RANGE: f32 = 200.0;
+pub const VISION_TOWER_SIGHT_RANGE: f32 = 150.0;
+pub const VISION_EYE_HEIGHT: f32 = 2.0;           // Eyes sit this far above the ground for line of sight
+pub const VISION_UPDATE_INTERVAL: f32 = 0.5;      // Seconds between visibility recomputes
+pub const FOG_OVERLAY_ALPHA: f32 = 0.55;          // Darkness of unseen terrain
+pub const FOG_OVERLAY_HEIGHT: f32 = 0.4;          // Overlay offset above the terrain surface
+
+// ===== MINIMAP =====
+
+pub const MINIMAP_SIZE: u32 = 220;                // Panel and image size in pixels (covers TERRAIN_SIZE)
+pub const MINIMAP_TOP: f32 = 50.0;                // Below the FPS counter
+pub const MINIMAP_MARGIN: f32 = 10.0;             // Gap to the right edge of the window
+pub const MINIMAP_REFRESH_INTERVAL: f32 = 0.1;    // Seconds between repaints
+
+// ===== AI COMMANDER =====
+
+/// Squads at or below this fraction of their full size count as broken (difficulty scales it)
+pub const AI_BROKEN_SQUAD_STRENGTH: f32 = 0.3;
+/// Enemies this close to the AI's own tower trigger a defensive response
+pub const AI_TOWER_DEFENSE_RADIUS: f32 = 150.0;
+/// Enemy turrets within this distance of a target make attacking it costlier
+pub const AI_TURRET_THREAT_RADIUS: f32 = 120.0;
+/// A turret is weighed like this many enemy infantry when comparing forces
+pub const AI_TURRET_THREAT_UNITS: f32 = 75.0;
+/// Squads within this distance of their rally/staging point have arrived
+pub const AI_ARRIVAL_RADIUS: f32 = 25.0;
+/// Re-issue an order only when its destination moved further than this
+pub const AI_REORDER_DISTANCE: f32 = 30.0;
+/// A new assault target must score this much higher to replace the current one
+pub const AI_TARGET_SWITCH_MARGIN: f32 = 0.2;
+/// Broken squads rally this far behind their own tower
+pub const AI_RALLY_DISTANCE: f32 = 40.0;
+/// Squads per line when the AI moves a large force (MULTI_SQUAD_SPACING apart)
+pub const AI_SQUADS_PER_LINE: usize = 10;
+/// Distance between successive lines of a large AI move
+pub const AI_LINE_DEPTH: f32 = 30.0;
+
+// ===== NAVIGATION =====
+
+pub const NAV_CELL_SIZE: f32 = 4.0;          // Nav grid cell size (200x200 cells on the 800u terrain)
+pub const NAV_SLOPE_COST: f32 = 4.0;         // Extra traversal cost per unit of slope (rise/run)
+pub const NAV_MAX_SLOPE: f32 = 1.2;          // Steeper cells are impassable (~50 degrees)
+pub const NAV_OBSTACLE_MARGIN: f32 = 3.0;    // Clearance kept around rocks and buildings
+pub const NAV_WAYPOINT_RADIUS: f32 = 10.0;   // Squad center this close to a waypoint moves on to the next
+pub const NAV_SMOOTHING_TOLERANCE: f32 = 1.1; // Shortcut a path when the straight line costs at most this much more
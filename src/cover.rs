@@ -0,0 +1,128 @@
+// Cover from rocks and other props
+//
+// Anything carrying a Cover component (large rocks today, any future prop that opts in)
+// shelters units standing within COVER_RANGE of its edge on the far side from the
+// shooter. calculate_hit_chance sees this as a lower target exposure (see HitModifiers).
+//
+// CoverMap buckets the cover objects into a coarse grid so the hitscan systems can test
+// every shot cheaply. It is rebuilt whenever cover objects appear or disappear.
+// Move orders that land near cover snap the squad behind it, facing the enemy.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::constants::*;
+
+/// A prop units can take cover behind (radius of its footprint in world units)
+#[derive(Component, Clone, Copy)]
+pub struct Cover {
+    pub radius: f32,
+}
+
+/// Every cover object on the map, bucketed by COVER_CELL_SIZE cells
+#[derive(Resource, Default)]
+pub struct CoverMap {
+    points: Vec<(Vec3, f32)>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl CoverMap {
+    fn cell(position: Vec3) -> (i32, i32) {
+        ((position.x / COVER_CELL_SIZE).floor() as i32, (position.z / COVER_CELL_SIZE).floor() as i32)
+    }
+
+    pub fn build(points: Vec<(Vec3, f32)>) -> Self {
+        let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (index, &(position, _)) in points.iter().enumerate() {
+            cells.entry(Self::cell(position)).or_default().push(index);
+        }
+        Self { points, cells }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Cover objects in the 3x3 cells around a position
+    fn nearby(&self, position: Vec3) -> impl Iterator<Item = (Vec3, f32)> + '_ {
+        let (cx, cz) = Self::cell(position);
+        (-1..=1)
+            .flat_map(move |dx| (-1..=1).map(move |dz| (cx + dx, cz + dz)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|&index| self.points[index])
+    }
+
+    /// Is a unit sheltered from fire coming from `direction` (XZ), with the shooter
+    /// `threat_distance` away? The cover must be close and roughly in line with the shooter.
+    pub fn is_covered_from(&self, unit_pos: Vec3, direction: Vec3, threat_distance: f32) -> bool {
+        let direction = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+        if direction == Vec3::ZERO {
+            return false;
+        }
+
+        self.nearby(unit_pos).any(|(cover_pos, radius)| {
+            let to_cover = Vec3::new(cover_pos.x - unit_pos.x, 0.0, cover_pos.z - unit_pos.z);
+            let distance = to_cover.length();
+            distance > 0.01
+                && distance <= radius + COVER_RANGE
+                && distance < threat_distance
+                && (to_cover / distance).dot(direction) >= COVER_MIN_ALIGNMENT
+        })
+    }
+
+    /// Is a unit sheltered from a shooter at `shooter_pos`?
+    pub fn is_covered(&self, unit_pos: Vec3, shooter_pos: Vec3) -> bool {
+        let to_shooter = shooter_pos - unit_pos;
+        self.is_covered_from(unit_pos, to_shooter, Vec3::new(to_shooter.x, 0.0, to_shooter.z).length())
+    }
+
+    /// Target exposure multiplier for calculate_hit_chance
+    pub fn exposure_modifier(&self, target_pos: Vec3, shooter_pos: Vec3) -> f32 {
+        if self.is_covered(target_pos, shooter_pos) { COVER_EXPOSURE } else { 1.0 }
+    }
+
+    /// Spot behind the nearest free cover object within COVER_SNAP_RADIUS of a move
+    /// destination, with the cover between the spot and the direction the squad will face.
+    /// Returns the cover's index (to mark it taken) and the XZ of the spot.
+    pub fn snap_spot(&self, destination: Vec3, facing: Vec3, taken: &[usize]) -> Option<(usize, Vec3)> {
+        let facing = Vec3::new(facing.x, 0.0, facing.z).normalize_or_zero();
+        if facing == Vec3::ZERO {
+            return None;
+        }
+
+        self.points.iter()
+            .enumerate()
+            .filter(|(index, _)| !taken.contains(index))
+            .map(|(index, &(position, radius))| {
+                let distance = Vec3::new(position.x - destination.x, 0.0, position.z - destination.z).length();
+                (index, position, radius, distance)
+            })
+            .filter(|&(_, _, _, distance)| distance <= COVER_SNAP_RADIUS)
+            .min_by(|a, b| a.3.total_cmp(&b.3))
+            .map(|(index, position, radius, _)| (index, position - facing * (radius + COVER_STANDOFF)))
+    }
+}
+
+/// Rebuild the cover map when cover objects are added or removed
+pub fn rebuild_cover_map(
+    mut cover_map: ResMut<CoverMap>,
+    cover_query: Query<(&Transform, &Cover)>,
+    added_cover: Query<(), Added<Cover>>,
+    mut removed_cover: RemovedComponents<Cover>,
+) {
+    let cover_removed = removed_cover.read().count() > 0;
+    if added_cover.is_empty() && !cover_removed {
+        return;
+    }
+
+    let points: Vec<(Vec3, f32)> = cover_query.iter()
+        .map(|(transform, cover)| (transform.translation, cover.radius))
+        .collect();
+    *cover_map = CoverMap::build(points);
+    info!("Cover map rebuilt: {} cover objects", cover_map.len());
+}
@@ -0,0 +1,383 @@
+// Economy - per-team credits and the production queue
+//
+// Every team earns CREDITS_PER_SECOND, plus CREDITS_PER_VICTORY_POINT for each victory point
+// it scores (capture points and secondary objectives, see capture.rs). Credits buy
+// production orders through GameCommand::QueueProduction: squads of any archetype (priced
+// by the archetype's cost and build_time), MG and heavy turrets, and artillery strikes.
+// An order is paid for when it is queued and refunded when it is cancelled.
+//
+// Each team works through its queue one order at a time at its Uplink Tower (the command
+// bunker in scenarios); while the team has no tower standing the queue waits. Finished
+// squads form up in front of the tower through spawn_single_squad, turrets are placed on a
+// ring around it through spawn_mg_turret_at / spawn_heavy_turret_at, and strikes are fired
+// with queue_artillery_barrage.
+//
+// The simulation is render-free; production hotkeys and the credits HUD are added by GamePlugin.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::archetype::UnitArchetypes;
+use crate::artillery::{queue_artillery_barrage, ArtilleryBarrage, ArtilleryState};
+use crate::constants::*;
+use crate::game_command::GameCommand;
+use crate::scenario::ScenarioState;
+use crate::setup::{create_team_materials, spawn_single_squad, UnitMaterials};
+use crate::terrain::{MapSwitchEvent, TerrainHeightmap};
+use crate::turrets::{spawn_heavy_turret_at, spawn_mg_turret_at, TurretType};
+use crate::types::*;
+
+/// Income and production, after capture points have scored and before the win condition
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Economy>()
+            .add_systems(FixedUpdate, (
+                economy_income_system,
+                production_system,
+            ).chain()
+                .after(crate::capture::capture_bonus_system)
+                .before(crate::objective::win_condition_system));
+    }
+}
+
+/// Something a team can spend credits on
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ProductionItem {
+    /// A squad of this archetype
+    Squad { archetype: String },
+    Turret(TurretType),
+    /// A scatter or line barrage, fired once its call-in time is up
+    Artillery(ArtilleryBarrage),
+}
+
+impl ProductionItem {
+    /// Credits the order costs
+    pub fn cost(&self, archetypes: &UnitArchetypes) -> f32 {
+        match self {
+            ProductionItem::Squad { archetype } => archetypes.get(archetype).cost,
+            ProductionItem::Turret(TurretType::MachineGun) => MG_TURRET_COST,
+            ProductionItem::Turret(TurretType::Heavy) => HEAVY_TURRET_COST,
+            ProductionItem::Artillery(_) => ARTILLERY_STRIKE_COST,
+        }
+    }
+
+    /// Seconds the order takes once it reaches the front of the queue
+    pub fn build_time(&self, archetypes: &UnitArchetypes) -> f32 {
+        match self {
+            ProductionItem::Squad { archetype } => archetypes.get(archetype).build_time,
+            ProductionItem::Turret(_) => TURRET_BUILD_TIME,
+            ProductionItem::Artillery(_) => ARTILLERY_CALL_IN_TIME,
+        }
+    }
+
+    /// Short name for the HUD and logs
+    pub fn label(&self, archetypes: &UnitArchetypes) -> String {
+        match self {
+            ProductionItem::Squad { archetype } => format!("{} squad", archetypes.get(archetype).name),
+            ProductionItem::Turret(TurretType::MachineGun) => "MG turret".to_string(),
+            ProductionItem::Turret(TurretType::Heavy) => "Heavy turret".to_string(),
+            ProductionItem::Artillery(ArtilleryBarrage::Line { .. }) => "Line barrage".to_string(),
+            ProductionItem::Artillery(_) => "Artillery strike".to_string(),
+        }
+    }
+}
+
+/// A paid order waiting in (or at the front of) a production queue
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProductionOrder {
+    pub item: ProductionItem,
+    /// Credits paid, refunded on cancel
+    pub cost: f32,
+    pub build_time: f32,
+    /// Seconds spent on the order so far
+    pub progress: f32,
+}
+
+impl ProductionOrder {
+    /// 0 to 1
+    pub fn fraction(&self) -> f32 {
+        if self.build_time > 0.0 { (self.progress / self.build_time).min(1.0) } else { 1.0 }
+    }
+}
+
+/// Credits and production queues, per team (Team::index)
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct Economy {
+    pub credits: [f32; Team::MAX],
+    pub queues: [Vec<ProductionOrder>; Team::MAX],
+    /// Victory points already paid out as credits
+    pub points_paid: [f32; Team::MAX],
+    /// Turrets produced so far (spreads them around the tower)
+    pub turrets_built: [u32; Team::MAX],
+}
+
+impl Default for Economy {
+    fn default() -> Self {
+        Self {
+            credits: [STARTING_CREDITS; Team::MAX],
+            queues: Default::default(),
+            points_paid: [0.0; Team::MAX],
+            turrets_built: [0; Team::MAX],
+        }
+    }
+}
+
+impl Economy {
+    /// Pay for an order and add it to the team's queue. Fails (leaving the credits alone)
+    /// when the team can't afford it or its queue is full.
+    pub fn queue(&mut self, team: Team, item: ProductionItem, archetypes: &UnitArchetypes) -> bool {
+        let (cost, label) = (item.cost(archetypes), item.label(archetypes));
+        let index = team.index();
+        if self.queues[index].len() >= PRODUCTION_QUEUE_MAX {
+            info!("Team {:?} production queue is full, can't add {}", team, label);
+            return false;
+        }
+        if self.credits[index] < cost {
+            info!("Team {:?} can't afford {} ({:.0} / {:.0} credits)", team, label, self.credits[index], cost);
+            return false;
+        }
+
+        self.credits[index] -= cost;
+        self.queues[index].push(ProductionOrder {
+            build_time: item.build_time(archetypes),
+            item,
+            cost,
+            progress: 0.0,
+        });
+        info!("Team {:?} queued {} for {:.0} credits", team, label, cost);
+        true
+    }
+
+    /// Drop the team's most recently queued order and refund it
+    pub fn cancel_last(&mut self, team: Team) -> Option<ProductionOrder> {
+        let order = self.queues[team.index()].pop()?;
+        self.credits[team.index()] += order.cost;
+        Some(order)
+    }
+}
+
+/// New battles start every team on STARTING_CREDITS with empty queues
+pub fn reset_economy_on_map_switch(
+    mut map_switch_events: EventReader<MapSwitchEvent>,
+    mut economy: ResMut<Economy>,
+) {
+    if map_switch_events.read().count() > 0 {
+        *economy = Economy::default();
+    }
+}
+
+// ===== SIMULATION =====
+
+/// Pay passive income and credits for newly scored victory points
+pub fn economy_income_system(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    diplomacy: Res<Diplomacy>,
+    mut economy: ResMut<Economy>,
+) {
+    if game_state.game_ended {
+        return;
+    }
+    let delta = time.delta_secs();
+
+    for &team in &diplomacy.teams {
+        let index = team.index();
+        // Points only drop when a new battle resets them
+        let scored = (game_state.points[index] - economy.points_paid[index]).max(0.0);
+        economy.points_paid[index] = game_state.points[index];
+        economy.credits[index] += CREDITS_PER_SECOND * delta + scored * CREDITS_PER_VICTORY_POINT;
+    }
+}
+
+/// Advance the order at the front of each team's queue and deliver it when done
+pub fn production_system(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mut economy: ResMut<Economy>,
+    mut commands: Commands,
+    (mut meshes, mut materials): (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>),
+    (mut squad_manager, mut archetypes, mut sim_rng, heightmap):
+        (ResMut<SquadManager>, ResMut<UnitArchetypes>, ResMut<SimRng>, Res<TerrainHeightmap>),
+    mut artillery_state: Option<ResMut<ArtilleryState>>,
+    tower_query: Query<(&Transform, &UplinkTower, &Health, Option<&ObjectiveTarget>)>,
+    // Produced squads share one material set per team
+    mut team_materials: Local<HashMap<Team, UnitMaterials>>,
+) {
+    if game_state.game_ended {
+        return;
+    }
+    let delta = time.delta_secs();
+
+    for team in Team::ALL {
+        let index = team.index();
+        if economy.queues[index].is_empty() {
+            continue;
+        }
+
+        // Production happens at the team's primary tower; without one the queue waits
+        let Some(tower_position) = tower_query.iter()
+            .find(|(_, tower, health, objective)| {
+                tower.team == team && !health.is_dead() && objective.map_or(true, |objective| objective.is_primary)
+            })
+            .map(|(transform, ..)| transform.translation)
+        else {
+            continue;
+        };
+
+        let order = &mut economy.queues[index][0];
+        order.progress += delta;
+        if order.progress < order.build_time {
+            continue;
+        }
+        let order = economy.queues[index].remove(0);
+
+        let forward = -team.home_direction();
+        match order.item {
+            ProductionItem::Squad { archetype } => {
+                let (x, z) = (
+                    tower_position.x + forward.x * PRODUCTION_SPAWN_DISTANCE,
+                    tower_position.z + forward.z * PRODUCTION_SPAWN_DISTANCE,
+                );
+                let position = Vec3::new(x, heightmap.sample_height(x, z), z);
+                let unit_materials = team_materials
+                    .entry(team)
+                    .or_insert_with(|| create_team_materials(&mut materials, team));
+                let squad_id = spawn_single_squad(
+                    &mut commands,
+                    &mut squad_manager,
+                    &mut archetypes,
+                    &archetype,
+                    &mut meshes,
+                    unit_materials,
+                    &mut materials,
+                    team,
+                    position,
+                    forward,
+                    &heightmap,
+                    &mut *sim_rng,
+                );
+                info!("Team {:?} produced a {} squad ({})", team, archetype, squad_id);
+            }
+            ProductionItem::Turret(turret_type) => {
+                // Alternate either side of the tower's front, working outwards
+                let built = economy.turrets_built[index];
+                economy.turrets_built[index] += 1;
+                let side = if built % 2 == 0 { 1.0 } else { -1.0 };
+                let angle = side * (built / 2 + 1) as f32 * PRODUCTION_TURRET_SPACING;
+                let direction = Quat::from_rotation_y(angle) * forward;
+                let (x, z) = (
+                    tower_position.x + direction.x * PRODUCTION_TURRET_DISTANCE,
+                    tower_position.z + direction.z * PRODUCTION_TURRET_DISTANCE,
+                );
+                let position = Vec3::new(x, heightmap.sample_height(x, z), z);
+                match turret_type {
+                    TurretType::MachineGun => spawn_mg_turret_at(&mut commands, &mut meshes, &mut materials, position, team),
+                    TurretType::Heavy => spawn_heavy_turret_at(&mut commands, &mut meshes, &mut materials, position, team),
+                };
+                info!("Team {:?} built a {:?} turret at {:?}", team, turret_type, position);
+            }
+            ProductionItem::Artillery(barrage) => match artillery_state.as_mut() {
+                Some(artillery_state) => {
+                    queue_artillery_barrage(artillery_state, barrage, Some(&*heightmap), &mut *sim_rng);
+                }
+                None => {
+                    // No artillery without ArtilleryPlugin (headless): give the credits back
+                    economy.credits[index] += order.cost;
+                }
+            },
+        }
+    }
+}
+
+// ===== PLAYER INPUT =====
+
+/// Production hotkeys for the player's team (see ScenarioState::player_team): Q squad, Shift+Q walker squad,
+/// R MG turret, Shift+R heavy turret, Backspace cancels the last order.
+/// Scatter and line barrages are queued by the artillery hotkeys (see artillery.rs).
+pub fn production_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    scenario_state: Res<ScenarioState>,
+    mut game_commands: EventWriter<GameCommand>,
+) {
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    let team = scenario_state.player_team();
+
+    let item = if keyboard.just_pressed(KeyCode::KeyQ) {
+        let archetype = if shift { WALKER_ARCHETYPE } else { DEFAULT_UNIT_ARCHETYPE };
+        Some(ProductionItem::Squad { archetype: archetype.to_string() })
+    } else if keyboard.just_pressed(KeyCode::KeyR) {
+        Some(ProductionItem::Turret(if shift { TurretType::Heavy } else { TurretType::MachineGun }))
+    } else {
+        None
+    };
+
+    if let Some(item) = item {
+        game_commands.write(GameCommand::QueueProduction { team, item });
+    }
+    if keyboard.just_pressed(KeyCode::Backspace) {
+        game_commands.write(GameCommand::CancelProduction { team });
+    }
+}
+
+// ===== PRESENTATION =====
+
+/// Credits and production queue readout
+#[derive(Component)]
+pub struct EconomyHudUI;
+
+/// Spawn the credits and queue readout (top center)
+pub fn spawn_economy_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.9, 0.5)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Percent(40.0),
+            ..default()
+        },
+        EconomyHudUI,
+    ));
+}
+
+/// The player's credits, income and production queue
+pub fn update_economy_hud(
+    economy: Res<Economy>,
+    archetypes: Res<UnitArchetypes>,
+    scenario_state: Res<ScenarioState>,
+    mut hud_query: Query<&mut Text, With<EconomyHudUI>>,
+) {
+    let index = scenario_state.player_team().index();
+    let mut lines = vec![format!("Credits: {:.0}", economy.credits[index])];
+
+    let queue = &economy.queues[index];
+    if queue.is_empty() {
+        lines.push("Production: idle (Q squad, Shift+Q walker, R MG turret, Shift+R heavy turret)".to_string());
+    } else {
+        let orders: Vec<String> = queue.iter().enumerate()
+            .map(|(position, order)| {
+                let label = order.item.label(&archetypes);
+                if position == 0 {
+                    format!("{} {:.0}%", label, order.fraction() * 100.0)
+                } else {
+                    label
+                }
+            })
+            .collect();
+        lines.push(format!("Production: {}  (Backspace cancels)", orders.join(" | ")));
+    }
+
+    let new_text = lines.join("\n");
+    for mut text in hud_query.iter_mut() {
+        if text.0 != new_text {
+            text.0 = new_text.clone();
+        }
+    }
+}
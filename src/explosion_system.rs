@@ -1,10 +1,14 @@
 // Explosion orchestration system - handles delayed explosions and visual effects
+//
+// Entities marked with PendingExplosion (tower cascades) are despawned by
+// resolve_pending_explosions_system in FixedUpdate. pending_explosion_system only
+// spawns the matching audio and VFX, so headless and windowed battles resolve alike.
 use bevy::prelude::*;
+use rand::seq::SliceRandom;
 use crate::constants::*;
 use crate::explosion_shader::{spawn_custom_shader_explosion, ExplosionAssets};
 use crate::particles::ExplosionParticleEffects;
-use crate::types::AudioAssets;
-use crate::types::UplinkTower;
+use crate::types::{AudioAssets, SimRng, UplinkTower};
 
 /// Component for entities waiting to explode after a delay
 #[derive(Component)]
@@ -25,10 +29,61 @@ pub struct ExplosionEffect {
     pub intensity: f32,
 }
 
-/// Maximum explosions to process per frame (prevents lag spikes)
+/// A pending explosion went off: the entity is gone, the VFX are still to be spawned
+#[derive(Event)]
+pub struct ExplosionEvent {
+    pub position: Vec3,
+    pub radius: f32,
+    pub is_tower: bool,
+}
+
+/// Maximum unit explosion VFX to spawn per frame (prevents lag spikes)
 const MAX_EXPLOSIONS_PER_FRAME: usize = 20;
 
-/// System that processes pending explosions after their delay timers expire
+/// Simulation half of the explosion cascade (FixedUpdate): ticks the delay timers and
+/// despawns the entities that are due, so results don't depend on frame rate. Every
+/// explosion is reported as an ExplosionEvent for pending_explosion_system to render.
+pub fn resolve_pending_explosions_system(
+    mut commands: Commands,
+    mut explosion_query: Query<(Entity, &mut PendingExplosion, &Transform, Option<&UplinkTower>)>,
+    mut explosion_events: EventWriter<ExplosionEvent>,
+    mut sim_rng: ResMut<SimRng>,
+    time: Res<Time>,
+) {
+    let mut towers_ready: Vec<ExplosionEvent> = Vec::new();
+    let mut units_ready: Vec<ExplosionEvent> = Vec::new();
+
+    for (entity, mut pending, transform, tower) in explosion_query.iter_mut() {
+        pending.delay_timer -= time.delta_secs();
+        if pending.delay_timer > 0.0 {
+            continue;
+        }
+
+        commands.entity(entity).try_despawn();
+        match tower {
+            Some(tower) => towers_ready.push(ExplosionEvent {
+                position: transform.translation,
+                radius: tower.destruction_radius * 0.5,
+                is_tower: true,
+            }),
+            None => units_ready.push(ExplosionEvent {
+                position: transform.translation,
+                radius: 8.0,
+                is_tower: false,
+            }),
+        }
+    }
+
+    // Shuffle unit explosions for visual variety (the VFX cap keeps the first ones)
+    units_ready.shuffle(&mut *sim_rng);
+
+    // Towers first (high priority, always shown)
+    explosion_events.write_batch(towers_ready);
+    explosion_events.write_batch(units_ready);
+}
+
+/// Presentation half of the explosion cascade: spawns the audio and VFX for this
+/// frame's ExplosionEvents, at most MAX_EXPLOSIONS_PER_FRAME unit explosions
 pub fn pending_explosion_system(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -40,102 +95,60 @@ pub fn pending_explosion_system(
     particle_effects: Option<Res<ExplosionParticleEffects>>,
     audio_assets: Res<AudioAssets>,
     asset_server: Res<AssetServer>,
-    mut explosion_query: Query<(Entity, &mut PendingExplosion, &Transform, Option<&UplinkTower>), With<PendingExplosion>>,
+    mut explosion_events: EventReader<ExplosionEvent>,
     time: Res<Time>,
 ) {
-    let mut ready_to_explode: Vec<(Entity, Vec3, f32, bool, f32)> = Vec::new();
-    let mut towers_ready: Vec<(Entity, Vec3, f32)> = Vec::new();
-    let mut total_pending = 0;
-
-    // Update timers and collect ready entities
-    for (entity, mut pending, transform, tower_component) in explosion_query.iter_mut() {
-        total_pending += 1;
-        let old_timer = pending.delay_timer;
-        pending.delay_timer -= time.delta_secs();
+    let mut unit_explosions = 0;
 
-        if pending.delay_timer <= 0.0 {
-            let is_tower = tower_component.is_some();
-            let explosion_radius = if is_tower {
-                tower_component.unwrap().destruction_radius * 0.5
-            } else {
-                8.0
-            };
-
-            debug!("⏰ Entity {:?} ready to explode: timer {:.3}s → {:.3}s",
-                   entity.index(), old_timer, pending.delay_timer);
-
-            if is_tower {
-                towers_ready.push((entity, transform.translation, explosion_radius));
-            } else {
-                ready_to_explode.push((entity, transform.translation, explosion_radius, is_tower, old_timer));
-            }
-        }
-    }
+    for event in explosion_events.read() {
+        if event.is_tower {
+            info!("🏰 Processing TOWER explosion at {:?}", event.position);
 
-    if total_pending > 0 || !ready_to_explode.is_empty() || !towers_ready.is_empty() {
-        info!("📊 EXPLOSION FRAME: {} total pending, {} units ready, {} towers ready",
-              total_pending, ready_to_explode.len(), towers_ready.len());
-    }
+            commands.spawn((
+                AudioPlayer::new(audio_assets.explosion_sound.clone()),
+                PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::Linear(crate::constants::VOLUME_EXPLOSION)),
+            ));
 
-    // Shuffle unit explosions for visual variety
-    use rand::seq::SliceRandom;
-    let mut rng = rand::thread_rng();
-    ready_to_explode.shuffle(&mut rng);
-
-    // Process towers first (high priority, always immediate)
-    for (entity, position, _explosion_radius) in towers_ready {
-        info!("🏰 Processing TOWER explosion at {:?}", position);
-
-        commands.spawn((
-            AudioPlayer::new(audio_assets.explosion_sound.clone()),
-            PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::Linear(crate::constants::VOLUME_EXPLOSION)),
-        ));
-
-        crate::wfx_spawn::spawn_combined_explosion(
-            &mut commands,
-            &mut meshes,
-            &mut additive_materials,
-            &mut smoke_materials,
-            &mut smoke_only_materials,
-            &asset_server,
-            position,
-            4.0,
-            1.5,
-        );
-
-        commands.entity(entity).despawn();
-    }
+            crate::wfx_spawn::spawn_combined_explosion(
+                &mut commands,
+                &mut meshes,
+                &mut additive_materials,
+                &mut smoke_materials,
+                &mut smoke_only_materials,
+                &asset_server,
+                event.position,
+                4.0,
+                1.5,
+            );
+            continue;
+        }
 
-    // Process unit explosions with frame limit
-    let num_to_process = ready_to_explode.len().min(MAX_EXPLOSIONS_PER_FRAME);
-    if num_to_process > 0 {
-        info!("💥 Processing {} unit explosions this frame (limit: {})", num_to_process, MAX_EXPLOSIONS_PER_FRAME);
-    }
+        unit_explosions += 1;
+        if unit_explosions > MAX_EXPLOSIONS_PER_FRAME {
+            continue;
+        }
 
-    for (entity, position, explosion_radius, is_tower, _old_timer) in ready_to_explode.iter().take(MAX_EXPLOSIONS_PER_FRAME) {
         if let Some(assets) = explosion_assets.as_ref() {
             spawn_custom_shader_explosion(
                 &mut commands,
                 &mut meshes,
                 &mut explosion_materials,
-                &assets,
+                assets,
                 particle_effects.as_ref().map(|p| p.as_ref()),
-                *position,
-                explosion_radius * 0.1,
+                event.position,
+                event.radius * 0.1,
                 1.0,
                 EXPLOSION_EFFECT_DURATION,
-                *is_tower,
+                false,
                 time.elapsed_secs_f64(),
             );
         } else {
             warn!("Cannot spawn unit explosion - ExplosionAssets not loaded");
         }
-
-        commands.entity(*entity).despawn();
     }
 
-    if ready_to_explode.len() > MAX_EXPLOSIONS_PER_FRAME {
-        warn!("⚠️ Skipped {} explosions this frame (over limit)", ready_to_explode.len() - MAX_EXPLOSIONS_PER_FRAME);
+    if unit_explosions > MAX_EXPLOSIONS_PER_FRAME {
+        warn!("⚠️ Skipped {} explosion effects this frame (over limit)", unit_explosions - MAX_EXPLOSIONS_PER_FRAME);
     }
 }
 
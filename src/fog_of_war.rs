@@ -0,0 +1,299 @@
+// Fog of war and per-team line-of-sight visibility
+//
+// TeamVision keeps one grid per team over the terrain (VISION_CELL_SIZE cells). A cell is
+// visible to a team when one of its squads, turrets or uplink towers (or an ally's) has the
+// cell within sight range and a clear line of sight over the TerrainHeightmap, using the
+// same sampling as hitscan fire (has_line_of_sight).
+//
+// The grids are recomputed every VISION_UPDATE_INTERVAL in FixedUpdate, counted in SimTicks so
+// the cadence restarts with each battle and targeting stays deterministic (replays included):
+// units only pick enemy units and turrets their team has spotted.
+//
+// FogOfWarPlugin is the player's (Team A's) view of it: hostile squads, turrets, tracers and
+// laser bolts outside vision are hidden, and a terrain-hugging overlay darkens everything unseen.
+
+use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+
+use crate::combat::has_line_of_sight;
+use crate::constants::*;
+use crate::formation::{CommanderMarker, RankChevron};
+use crate::terrain::TerrainHeightmap;
+use crate::types::*;
+
+/// Which parts of the map each team can currently see
+#[derive(Resource)]
+pub struct TeamVision {
+    width: usize,
+    grids: [Vec<bool>; Team::MAX],
+}
+
+impl Default for TeamVision {
+    fn default() -> Self {
+        let width = (TERRAIN_SIZE / VISION_CELL_SIZE).ceil() as usize;
+        Self {
+            width,
+            grids: std::array::from_fn(|_| vec![false; width * width]),
+        }
+    }
+}
+
+impl TeamVision {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Grid cell (x, z) containing a world position, clamped to the map
+    fn cell(&self, position: Vec3) -> (usize, usize) {
+        let half_size = TERRAIN_SIZE / 2.0;
+        let max = (self.width - 1) as f32;
+        (
+            ((position.x + half_size) / VISION_CELL_SIZE).clamp(0.0, max) as usize,
+            ((position.z + half_size) / VISION_CELL_SIZE).clamp(0.0, max) as usize,
+        )
+    }
+
+    /// World-space center of a grid cell (y = 0)
+    pub fn cell_center(&self, x: usize, z: usize) -> Vec3 {
+        let half_size = TERRAIN_SIZE / 2.0;
+        Vec3::new(
+            (x as f32 + 0.5) * VISION_CELL_SIZE - half_size,
+            0.0,
+            (z as f32 + 0.5) * VISION_CELL_SIZE - half_size,
+        )
+    }
+
+    /// Can `team` see this position?
+    pub fn is_visible(&self, team: Team, position: Vec3) -> bool {
+        let (x, z) = self.cell(position);
+        self.is_cell_visible(team, x, z)
+    }
+
+    pub fn is_cell_visible(&self, team: Team, x: usize, z: usize) -> bool {
+        self.grids[team.index()][z * self.width + x]
+    }
+}
+
+/// Recompute every team's visibility grid from its squads, turrets and towers
+pub fn update_team_vision_system(
+    time: Res<Time>,
+    sim_tick: Res<SimTick>,
+    mut vision: ResMut<TeamVision>,
+    diplomacy: Res<Diplomacy>,
+    squad_manager: Res<SquadManager>,
+    turret_query: Query<(&GlobalTransform, &TurretBase)>,
+    tower_query: Query<(&GlobalTransform, &UplinkTower, &Health)>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+) {
+    let ticks_per_refresh = ((VISION_UPDATE_INTERVAL / time.delta_secs()).round() as u64).max(1);
+    if sim_tick.0 % ticks_per_refresh != 0 {
+        return;
+    }
+
+    let hm = heightmap.as_deref();
+    let ground = |position: Vec3| hm.map_or(position.y, |hm| hm.sample_height(position.x, position.z));
+
+    // Eyes: (team, eye position, sight range)
+    let eyes: Vec<(Team, Vec3, f32)> = squad_manager.squads.values()
+        .filter(|squad| !squad.members.is_empty())
+        .map(|squad| (squad.team, squad.center_position, VISION_SQUAD_SIGHT_RANGE))
+        .chain(turret_query.iter().map(|(transform, turret)| (turret.team, transform.translation(), VISION_TURRET_SIGHT_RANGE)))
+        .chain(tower_query.iter()
+            .filter(|(_, _, health)| !health.is_dead())
+            .map(|(transform, tower, _)| (tower.team, transform.translation(), VISION_TOWER_SIGHT_RANGE)))
+        .map(|(team, position, range)| {
+            (team, Vec3::new(position.x, ground(position) + VISION_EYE_HEIGHT, position.z), range)
+        })
+        .collect();
+
+    let width = vision.width;
+    let mut own_sight: [Vec<bool>; Team::MAX] = std::array::from_fn(|_| vec![false; width * width]);
+    let cell_reach = (VISION_SQUAD_SIGHT_RANGE.max(VISION_TURRET_SIGHT_RANGE).max(VISION_TOWER_SIGHT_RANGE)
+        / VISION_CELL_SIZE).ceil() as i32;
+
+    for &(team, eye, range) in &eyes {
+        let grid = &mut own_sight[team.index()];
+        let (eye_x, eye_z) = vision.cell(eye);
+        let reach = ((range / VISION_CELL_SIZE).ceil() as i32).min(cell_reach);
+
+        for dz in -reach..=reach {
+            for dx in -reach..=reach {
+                let x = eye_x as i32 + dx;
+                let z = eye_z as i32 + dz;
+                if x < 0 || z < 0 || x >= width as i32 || z >= width as i32 {
+                    continue;
+                }
+                let index = z as usize * width + x as usize;
+                if grid[index] {
+                    continue;
+                }
+
+                // Look at a unit standing in the middle of the cell
+                let center = vision.cell_center(x as usize, z as usize);
+                let target = Vec3::new(center.x, ground(center) + 1.0, center.z);
+                if Vec2::new(target.x - eye.x, target.z - eye.z).length() <= range
+                    && has_line_of_sight(eye, target, hm)
+                {
+                    grid[index] = true;
+                }
+            }
+        }
+    }
+
+    // Allies share what they see
+    for team in Team::ALL {
+        let grid = &mut vision.grids[team.index()];
+        grid.iter_mut().for_each(|cell| *cell = false);
+        for &ally in &diplomacy.teams {
+            if ally == team || diplomacy.is_allied(team, ally) {
+                for (cell, &seen) in grid.iter_mut().zip(&own_sight[ally.index()]) {
+                    *cell |= seen;
+                }
+            }
+        }
+    }
+}
+
+// ===== PLAYER VIEW =====
+
+/// Hides what the player can't see and draws the fog overlay (windowed game only)
+pub struct FogOfWarPlugin;
+
+impl Plugin for FogOfWarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (fog_hide_system, fog_overlay_system));
+    }
+}
+
+/// Terrain overlay darkening the cells the player can't see
+#[derive(Component)]
+pub struct FogOverlay;
+
+/// Hide hostile units, turrets, tracers and laser bolts outside the player's vision
+pub fn fog_hide_system(
+    vision: Res<TeamVision>,
+    diplomacy: Res<Diplomacy>,
+    mut hideables: ParamSet<(
+        Query<(&Transform, &BattleDroid, &mut Visibility), With<SquadMember>>,
+        Query<(&GlobalTransform, &TurretBase, &mut Visibility)>,
+        Query<(&HitscanTracer, &mut Visibility)>,
+        Query<(&Transform, &LaserProjectile, &mut Visibility)>,
+        // Commander markers and rank chevrons sit above their (possibly hidden) commander
+        Query<(&Transform, &mut Visibility), Or<(With<CommanderMarker>, With<RankChevron>)>>,
+    )>,
+) {
+    let player = Team::A;
+    let shown = |team: Team, position: Vec3| {
+        if !diplomacy.is_hostile(player, team) || vision.is_visible(player, position) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        }
+    };
+
+    for (transform, droid, mut visibility) in hideables.p0().iter_mut() {
+        visibility.set_if_neq(shown(droid.team, transform.translation));
+    }
+    for (transform, turret, mut visibility) in hideables.p1().iter_mut() {
+        visibility.set_if_neq(shown(turret.team, transform.translation()));
+    }
+    for (tracer, mut visibility) in hideables.p2().iter_mut() {
+        let position = tracer.start_pos.lerp(tracer.end_pos, tracer.progress.clamp(0.0, 1.0));
+        visibility.set_if_neq(shown(tracer.team, position));
+    }
+    for (transform, laser, mut visibility) in hideables.p3().iter_mut() {
+        visibility.set_if_neq(shown(laser.team, transform.translation));
+    }
+    for (transform, mut visibility) in hideables.p4().iter_mut() {
+        let seen = vision.is_visible(player, transform.translation);
+        visibility.set_if_neq(if seen { Visibility::Inherited } else { Visibility::Hidden });
+    }
+}
+
+/// Build or refresh the fog overlay mesh whenever vision or terrain changes
+pub fn fog_overlay_system(
+    mut commands: Commands,
+    vision: Res<TeamVision>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    overlay_query: Query<&Mesh3d, With<FogOverlay>>,
+) {
+    let terrain_changed = heightmap.as_ref().is_some_and(|hm| hm.is_changed());
+    if !vision.is_changed() && !terrain_changed {
+        return;
+    }
+
+    let (positions, colors, indices) = build_fog_overlay(&vision, heightmap.as_deref());
+
+    if let Some(mesh) = overlay_query.single().ok().and_then(|handle| meshes.get_mut(&handle.0)) {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        return;
+    }
+
+    let vertex_count = positions.len();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.5, 0.5]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+
+    commands.spawn((
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::WHITE,
+            unlit: true,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        })),
+        Transform::default(),
+        FogOverlay,
+        Name::new("FogOverlay"),
+    ));
+}
+
+/// Overlay vertices sit on the cell corners just above the terrain; each corner's fog is
+/// the share of its neighbouring cells the player can't see, so the edges are soft
+fn build_fog_overlay(vision: &TeamVision, heightmap: Option<&TerrainHeightmap>) -> (Vec<[f32; 3]>, Vec<[f32; 4]>, Vec<u32>) {
+    let width = vision.width();
+    let corners = width + 1;
+    let half_size = TERRAIN_SIZE / 2.0;
+
+    let mut positions = Vec::with_capacity(corners * corners);
+    let mut colors = Vec::with_capacity(corners * corners);
+    for z in 0..corners {
+        for x in 0..corners {
+            let world_x = x as f32 * VISION_CELL_SIZE - half_size;
+            let world_z = z as f32 * VISION_CELL_SIZE - half_size;
+            let y = heightmap.map_or(0.0, |hm| hm.sample_height(world_x, world_z)) + FOG_OVERLAY_HEIGHT;
+            positions.push([world_x, y, world_z]);
+
+            let mut cells = 0;
+            let mut hidden = 0;
+            for (cell_x, cell_z) in [(x.wrapping_sub(1), z.wrapping_sub(1)), (x, z.wrapping_sub(1)), (x.wrapping_sub(1), z), (x, z)] {
+                if cell_x < width && cell_z < width {
+                    cells += 1;
+                    if !vision.is_cell_visible(Team::A, cell_x, cell_z) {
+                        hidden += 1;
+                    }
+                }
+            }
+            let fog = hidden as f32 / cells.max(1) as f32;
+            colors.push([0.0, 0.0, 0.0, fog * FOG_OVERLAY_ALPHA]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(width * width * 6);
+    for z in 0..width {
+        for x in 0..width {
+            let i = (z * corners + x) as u32;
+            let row = corners as u32;
+            indices.extend([i, i + row, i + 1, i + 1, i + row, i + row + 1]);
+        }
+    }
+
+    (positions, colors, indices)
+}
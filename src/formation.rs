@@ -1,60 +1,187 @@
 // Formation systems module
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use crate::types::*;
 use crate::constants::*;
 
+/// Squad simulation: formation upkeep, casualties, commander promotion, morale, marching and unit collision
+pub struct SquadPlugin;
+
+impl Plugin for SquadPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SquadManager::new())
+            .add_systems(FixedUpdate, (
+                // Formation and squad management systems run first
+                squad_formation_system,
+                squad_casualty_management_system,
+                squad_rotation_system,
+                crate::commander::commander_promotion_system,
+                crate::morale::squad_morale_system.after(crate::commander::commander_promotion_system),
+                crate::morale::squad_suppression_system,
+            ))
+            .add_systems(FixedUpdate, (
+                // Movement tracking for accuracy system (must run before animate_march)
+                crate::movement::update_movement_tracker,
+                // Animation and movement systems run after formation corrections
+                crate::movement::animate_march.after(crate::movement::update_movement_tracker),
+                // Unit-to-unit collision resolution (M2TW-style mass-based pushing)
+                crate::collision::unit_collision_system.after(crate::movement::animate_march),
+            ));
+    }
+}
+
 // Formation calculation functions
+//
+// Member slots are identified by (row, column) in the SQUAD_DEPTH x SQUAD_WIDTH grid whatever
+// the formation: each formation lays the same 50 slots out in its own shape, so changing
+// formation moves every member to its slot's new spot. The commander always keeps the
+// rear-center grid slot, which each layout swaps with its own commander spot.
+
+/// Grid slot held by the squad commander
+const COMMANDER_SLOT: (usize, usize) = (SQUAD_DEPTH - 1, SQUAD_WIDTH / 2);
+
 pub fn calculate_formation_offset(
     formation_type: FormationType,
     row: usize,
     column: usize,
     facing_direction: Vec3,
 ) -> Vec3 {
-    match formation_type {
-        FormationType::Rectangle => calculate_rectangle_offset(row, column, facing_direction),
-    }
-}
+    let slot = row * SQUAD_WIDTH + column;
+    let commander_slot = COMMANDER_SLOT.0 * SQUAD_WIDTH + COMMANDER_SLOT.1;
+    let commander_spot = formation_commander_spot(formation_type);
+    let index = if slot == commander_slot {
+        commander_spot
+    } else if slot == commander_spot {
+        commander_slot
+    } else {
+        slot
+    };
 
-fn calculate_rectangle_offset(row: usize, column: usize, facing_direction: Vec3) -> Vec3 {
-    // Standard rectangular formation (10 wide x 5 deep)
-    // Row 0 is front (faces facing_direction), row 4 is rear (commander position)
-    let x_offset = (column as f32 - (SQUAD_WIDTH as f32 - 1.0) / 2.0) * SQUAD_HORIZONTAL_SPACING;
-    let z_offset = (row as f32 - (SQUAD_DEPTH as f32 - 1.0) / 2.0) * SQUAD_VERTICAL_SPACING;
+    // (lateral, depth) with row 0 / depth < 0 at the front
+    let (lateral, depth) = formation_layout(formation_type, index);
 
     // Calculate perpendicular direction for width
     let right = Vec3::new(facing_direction.z, 0.0, -facing_direction.x).normalize();
 
-    // Negate z_offset so row 0 is in front (positive facing_direction) and commander is rear
-    right * x_offset - facing_direction * z_offset
+    // Negate depth so the front faces facing_direction and the commander is at the rear
+    right * lateral - facing_direction * depth
+}
+
+/// Offset of a member slot in the squad's current shape, blending between the old and
+/// new formation while the squad reshapes
+pub fn squad_slot_offset(squad: &Squad, slot: (usize, usize)) -> Vec3 {
+    let current = calculate_formation_offset(squad.formation_type, slot.0, slot.1, squad.facing_direction);
+    if squad.formation_blend >= 1.0 {
+        return current;
+    }
+    let previous = calculate_formation_offset(squad.previous_formation, slot.0, slot.1, squad.facing_direction);
+    let t = squad.formation_blend.clamp(0.0, 1.0);
+    previous.lerp(current, t * t * (3.0 - 2.0 * t)) // Smoothstep
 }
 
-pub fn assign_formation_positions(formation_type: FormationType) -> Vec<(usize, usize)> {
+/// Layout index where each formation puts its commander
+fn formation_commander_spot(formation_type: FormationType) -> usize {
     match formation_type {
-        FormationType::Rectangle => {
-            let mut positions = Vec::new();
-            for row in 0..SQUAD_DEPTH {
-                for col in 0..SQUAD_WIDTH {
-                    positions.push((row, col));
-                    if positions.len() >= SQUAD_SIZE {
-                        break;
-                    }
-                }
-                if positions.len() >= SQUAD_SIZE {
-                    break;
-                }
-            }
-            positions
-        },
+        FormationType::Rectangle | FormationType::Loose => COMMANDER_SLOT.0 * SQUAD_WIDTH + COMMANDER_SLOT.1,
+        FormationType::Line => FORMATION_LINE_WIDTH + FORMATION_LINE_WIDTH / 2, // Second rank, center
+        FormationType::Column => SQUAD_SIZE - FORMATION_COLUMN_WIDTH + FORMATION_COLUMN_WIDTH / 2, // Last rank, center
+        FormationType::Wedge => SQUAD_SIZE - 1,  // Alone behind the widest rank
+        FormationType::Square => 48,             // Middle of the square
     }
 }
 
-pub fn get_commander_position(formation_type: FormationType) -> (usize, usize) {
+/// Position of layout index `index` as (lateral, depth) in world units
+fn formation_layout(formation_type: FormationType, index: usize) -> (f32, f32) {
     match formation_type {
-        FormationType::Rectangle => {
-            // Rear-center: back row, middle column
-            (SQUAD_DEPTH - 1, SQUAD_WIDTH / 2)
-        },
+        // Standard rectangular formation (10 wide x 5 deep)
+        FormationType::Rectangle => grid_layout(index, SQUAD_WIDTH, 1.0),
+        FormationType::Line => grid_layout(index, FORMATION_LINE_WIDTH, 1.0),
+        FormationType::Column => grid_layout(index, FORMATION_COLUMN_WIDTH, 1.0),
+        FormationType::Wedge => {
+            // Rank r holds 2r+1 men: a point at the front widening toward the rear.
+            // The last, partial rank is centered behind the wedge.
+            let rank = (index as f32).sqrt().floor() as usize;
+            let ranks = (SQUAD_SIZE as f32).sqrt().ceil();
+            let count = (2 * rank + 1).min(SQUAD_SIZE - rank * rank);
+            let position = (index - rank * rank) as f32;
+            (
+                (position - (count as f32 - 1.0) / 2.0) * SQUAD_HORIZONTAL_SPACING,
+                (rank as f32 - (ranks - 1.0) / 2.0) * SQUAD_VERTICAL_SPACING,
+            )
+        }
+        FormationType::Square => {
+            // 4 faces of 12 around a hollow center, plus 2 in the middle
+            const FACE: usize = 12;
+            if index >= FACE * 4 {
+                return (0.0, (index - FACE * 4) as f32 * SQUAD_VERTICAL_SPACING);
+            }
+            let half = FACE as f32 / 2.0;
+            let t = (index % FACE) as f32 - half;
+            let (lateral, depth) = match index / FACE {
+                0 => (t, -half),  // Front
+                1 => (half, t),   // Right
+                2 => (-t, half),  // Rear
+                _ => (-half, -t), // Left
+            };
+            (lateral * SQUAD_HORIZONTAL_SPACING, depth * SQUAD_HORIZONTAL_SPACING)
+        }
+        FormationType::Loose => {
+            // Rectangle at wider spacing, with a fixed per-slot jitter so it doesn't look drilled
+            let (lateral, depth) = grid_layout(index, SQUAD_WIDTH, FORMATION_LOOSE_SPACING);
+            let jitter_x = ((index * 37 + 11) % 17) as f32 / 16.0 - 0.5;
+            let jitter_z = ((index * 53 + 7) % 19) as f32 / 18.0 - 0.5;
+            (
+                lateral + jitter_x * SQUAD_HORIZONTAL_SPACING,
+                depth + jitter_z * SQUAD_VERTICAL_SPACING,
+            )
+        }
+    }
+}
+
+/// Rows of `width` men, centered on the squad position
+fn grid_layout(index: usize, width: usize, spacing_scale: f32) -> (f32, f32) {
+    let ranks = SQUAD_SIZE.div_ceil(width);
+    let column = (index % width) as f32;
+    let rank = (index / width) as f32;
+    (
+        (column - (width as f32 - 1.0) / 2.0) * SQUAD_HORIZONTAL_SPACING * spacing_scale,
+        (rank - (ranks as f32 - 1.0) / 2.0) * SQUAD_VERTICAL_SPACING * spacing_scale,
+    )
+}
+
+/// Member slots for a new squad. Slots are the same for every formation (see above).
+pub fn assign_formation_positions(_formation_type: FormationType) -> Vec<(usize, usize)> {
+    let mut positions = Vec::new();
+    for row in 0..SQUAD_DEPTH {
+        for col in 0..SQUAD_WIDTH {
+            positions.push((row, col));
+            if positions.len() >= SQUAD_SIZE {
+                return positions;
+            }
+        }
+    }
+    positions
+}
+
+/// Slot held by the commander (rear-center in every formation)
+pub fn get_commander_position(_formation_type: FormationType) -> (usize, usize) {
+    COMMANDER_SLOT
+}
+
+/// Order squads to change formation; members move to their new slots over
+/// the next 1/FORMATION_RESHAPE_SPEED seconds (see squad_formation_system)
+pub fn execute_set_formation(squad_manager: &mut SquadManager, squad_ids: &[u32], formation: FormationType) {
+    let mut changed = 0;
+    for &squad_id in squad_ids {
+        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
+            if squad.formation_type != formation {
+                squad.set_formation(formation);
+                changed += 1;
+            }
+        }
+    }
+    if changed > 0 {
+        info!("{} squads reforming into {:?}", changed, formation);
     }
 }
 
@@ -62,18 +189,22 @@ pub fn get_commander_position(formation_type: FormationType) -> (usize, usize) {
 pub fn squad_formation_system(
     time: Res<Time>,
     mut squad_manager: ResMut<SquadManager>,
-    mut unit_query: Query<(&mut Transform, &SquadMember, &mut FormationOffset, &BattleDroid), With<BattleDroid>>,
-    mut last_update_time: Local<f32>,
+    mut unit_query: Query<(&mut Transform, &SquadMember, &mut FormationOffset, &mut BattleDroid)>,
+    sim_tick: Res<SimTick>,
 ) {
-    // Only update squad centers periodically, not every frame
-    let current_time = time.elapsed_secs();
-    let should_update_centers = if current_time - *last_update_time < 0.1 {
-        // Update only 10 times per second
-        false
-    } else {
-        *last_update_time = current_time;
-        true
-    };
+    // Advance formation changes; members of reshaping squads are walked to their blended slots
+    let mut reshaping: Vec<u32> = Vec::new();
+    for (squad_id, squad) in squad_manager.squads.iter_mut() {
+        if squad.formation_blend < 1.0 {
+            squad.formation_blend = (squad.formation_blend + time.delta_secs() * FORMATION_RESHAPE_SPEED).min(1.0);
+            reshaping.push(*squad_id);
+        }
+    }
+
+    // Only update squad centers periodically (10 times per second), not every tick.
+    // Counted in ticks so the cadence restarts with each battle.
+    let ticks_per_update = ((0.1 / time.delta_secs()).round() as u64).max(1);
+    let should_update_centers = sim_tick.0 % ticks_per_update == 0;
     
     if should_update_centers {
         // Calculate current average positions of units per squad
@@ -112,19 +243,19 @@ pub fn squad_formation_system(
     }
     
     // Now update unit formation positions with cached formation offsets
-    for (mut transform, squad_member, mut formation_offset, droid) in unit_query.iter_mut() {
+    for (mut transform, squad_member, mut formation_offset, mut droid) in unit_query.iter_mut() {
         if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
             // Only update formation targets when not retreating to prevent interference
             if !droid.returning_to_spawn {
-                // Recalculate formation offset with current facing direction
-                let new_offset = calculate_formation_offset(
-                    squad.formation_type,
-                    squad_member.formation_position.0,
-                    squad_member.formation_position.1,
-                    squad.facing_direction,
-                );
+                // Recalculate formation offset with current facing direction and shape
+                let new_offset = squad_slot_offset(squad, squad_member.formation_position);
                 formation_offset.local_offset = new_offset;
 
+                if reshaping.contains(&squad_member.squad_id) {
+                    let target_xz = squad.target_position + new_offset;
+                    droid.target_position = Vec3::new(target_xz.x, droid.spawn_position.y, target_xz.z);
+                }
+
                 // Use target_position as the formation anchor for clean final alignment
                 let correct_target_position = squad.target_position + formation_offset.local_offset;
                 formation_offset.target_world_position = correct_target_position;
@@ -204,7 +335,7 @@ pub fn squad_rotation_system(
     let delta_time = time.delta_secs();
 
     // Collect squads that need rotation updates
-    let mut squads_to_update: Vec<(u32, Vec3)> = Vec::new();
+    let mut squads_to_update: Vec<u32> = Vec::new();
 
     for (squad_id, squad) in squad_manager.squads.iter_mut() {
         // Check if squad needs to rotate
@@ -226,25 +357,19 @@ pub fn squad_rotation_system(
             squad.facing_direction = (rotation * squad.facing_direction).normalize();
 
             // Mark this squad for unit position update
-            squads_to_update.push((*squad_id, squad.facing_direction));
+            squads_to_update.push(*squad_id);
         }
     }
 
     // Update unit target positions for rotated squads
-    for (squad_id, new_facing) in squads_to_update {
+    for squad_id in squads_to_update {
         if let Some(squad) = squad_manager.get_squad(squad_id) {
             let target_pos = squad.target_position;
-            let formation_type = squad.formation_type;
 
             for (mut droid, squad_member) in droid_query.iter_mut() {
                 if squad_member.squad_id == squad_id && !droid.returning_to_spawn {
                     // Recalculate formation offset with new facing direction
-                    let new_offset = calculate_formation_offset(
-                        formation_type,
-                        squad_member.formation_position.0,
-                        squad_member.formation_position.1,
-                        new_facing,
-                    );
+                    let new_offset = squad_slot_offset(squad, squad_member.formation_position);
 
                     // Preserve unit's spawn Y height when updating target position
                     let target_xz = target_pos + new_offset;
@@ -264,15 +389,9 @@ pub fn squad_casualty_management_system(
 ) {
     // Handle unit deaths/removals
     for entity in removed_units.read() {
-        if let Ok(squad_member) = squad_query.get(entity) {
+        if squad_query.get(entity).is_ok() {
+            // Counts the loss against squad morale; squad_morale_system decides whether it breaks
             squad_manager.remove_unit_from_squad(entity);
-            
-            // Check if we need to reform the squad
-            if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
-                if squad.members.len() < 10 { // Less than 20% strength
-                    // Could trigger squad merge or retreat behavior here
-                }
-            }
         }
     }
 }
@@ -280,45 +399,46 @@ pub fn squad_casualty_management_system(
 // Squad movement control system (separate from formation switching)
 pub fn squad_movement_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut squad_manager: ResMut<SquadManager>,
-    mut droid_query: Query<&mut BattleDroid, With<SquadMember>>,
+    mut game_commands: EventWriter<crate::game_command::GameCommand>,
 ) {
-    let mut should_advance = false;
-    let mut should_retreat = false;
-    
-    if keyboard_input.just_pressed(KeyCode::KeyT) {
-        should_advance = true;
+    let advance = if keyboard_input.just_pressed(KeyCode::KeyT) {
         info!("All squads advance!");
+        true
     } else if keyboard_input.just_pressed(KeyCode::KeyH) {
-        should_retreat = true;
         info!("All squads retreat!");
-    }
-    
-    if should_advance || should_retreat {
-        // Update all squad target positions
-        for squad in squad_manager.squads.values_mut() {
-            if should_advance {
-                squad.target_position = squad.center_position + squad.facing_direction * MARCH_DISTANCE;
-            } else {
-                squad.target_position = squad.center_position;
-            }
+        false
+    } else {
+        return;
+    };
+
+    game_commands.write(crate::game_command::GameCommand::MarchAll { advance });
+}
+
+/// Order every squad to advance along its team direction, or retreat to spawn
+pub fn march_all_squads<'a>(
+    squad_manager: &mut SquadManager,
+    droids: impl Iterator<Item = Mut<'a, BattleDroid>>,
+    advance: bool,
+) {
+    // Update all squad target positions (routing squads don't listen)
+    for squad in squad_manager.squads.values_mut().filter(|squad| !squad.is_routing()) {
+        if advance {
+            squad.set_direct_target(squad.center_position + squad.facing_direction * MARCH_DISTANCE);
+        } else {
+            squad.set_direct_target(squad.center_position);
         }
-        
-        // Update individual unit targets
-        for mut droid in droid_query.iter_mut() {
-            if should_advance {
-                // Calculate advance direction based on team facing
-                let team_direction = if droid.team == Team::A {
-                    Vec3::new(1.0, 0.0, 0.0) // Team A faces right
-                } else {
-                    Vec3::new(-1.0, 0.0, 0.0) // Team B faces left
-                };
-                droid.target_position = droid.spawn_position + team_direction * MARCH_DISTANCE;
-                droid.returning_to_spawn = false;
-            } else {
-                droid.target_position = droid.spawn_position;
-                droid.returning_to_spawn = true;
-            }
+    }
+
+    // Update individual unit targets
+    for mut droid in droids {
+        if advance {
+            // Advance away from the team's own side, toward the center
+            let team_direction = -droid.team.home_direction();
+            droid.target_position = droid.spawn_position + team_direction * MARCH_DISTANCE;
+            droid.returning_to_spawn = false;
+        } else {
+            droid.target_position = droid.spawn_position;
+            droid.returning_to_spawn = true;
         }
     }
 }
@@ -357,10 +477,7 @@ pub fn commander_visual_marker_system(
             
             if !has_marker {
                 // Create debug marker above commander
-                let marker_color = match droid.team {
-                    Team::A => Color::srgb(1.0, 1.0, 0.0), // Bright yellow for Team A commanders
-                    Team::B => Color::srgb(1.0, 0.0, 0.0), // Bright red for Team B commanders
-                };
+                let marker_color = droid.team.palette().commander_marker;
                 
                 let marker_mesh = meshes.add(Cuboid::new(0.5, 0.5, 0.5));
                 let marker_material = materials.add(StandardMaterial {
@@ -402,4 +519,70 @@ pub fn update_commander_markers_system(
             }
         }
     }
-} 
\ No newline at end of file
+} 
+
+/// Veterancy chevrons above a squad's commander. Rank belongs to the squad, so the
+/// chevron moves to whoever is promoted to lead it.
+#[derive(Component)]
+pub struct RankChevron {
+    squad_id: u32,
+    rank: u8,
+}
+
+pub fn rank_chevron_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    squad_manager: Res<SquadManager>,
+    commander_query: Query<&Transform, (With<BattleDroid>, Without<RankChevron>)>,
+    mut chevron_query: Query<(Entity, &mut Transform, &RankChevron)>,
+) {
+    let mut has_chevron = HashSet::new();
+
+    // Follow the current commander; drop chevrons of wiped-out squads or outdated ranks
+    for (chevron_entity, mut chevron_transform, chevron) in chevron_query.iter_mut() {
+        let squad = squad_manager.get_squad(chevron.squad_id);
+        let commander_transform = squad
+            .and_then(|squad| squad.commander)
+            .and_then(|commander| commander_query.get(commander).ok());
+
+        match (squad, commander_transform) {
+            (Some(squad), Some(commander_transform)) if squad.rank() == chevron.rank => {
+                chevron_transform.translation = commander_transform.translation + Vec3::new(0.0, RANK_CHEVRON_HEIGHT, 0.0);
+                chevron_transform.rotation *= Quat::from_rotation_y(0.02);
+                has_chevron.insert(squad.id);
+            }
+            _ => commands.entity(chevron_entity).despawn(),
+        }
+    }
+
+    // New chevrons for squads that just earned a rank or lost the unit carrying theirs
+    for squad in squad_manager.squads.values() {
+        let rank = squad.rank();
+        if rank == 0 || has_chevron.contains(&squad.id) {
+            continue;
+        }
+        let Some(commander_transform) = squad.commander.and_then(|commander| commander_query.get(commander).ok()) else {
+            continue;
+        };
+
+        let chevron_color = Color::srgb(1.0, 0.8, 0.2);
+        let chevron_material = materials.add(StandardMaterial {
+            base_color: chevron_color,
+            emissive: chevron_color.into(),
+            unlit: true,
+            cull_mode: None, // Flat mesh, visible from both sides as it spins
+            ..default()
+        });
+
+        commands.spawn((
+            Mesh3d(crate::procedural_meshes::create_rank_chevron_mesh(&mut meshes, rank)),
+            MeshMaterial3d(chevron_material),
+            Transform::from_translation(commander_transform.translation + Vec3::new(0.0, RANK_CHEVRON_HEIGHT, 0.0)),
+            RankChevron {
+                squad_id: squad.id,
+                rank,
+            },
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+// Command layer between input and simulation
+//
+// Input systems (selection, artillery, turret placement, hotkeys) never mutate gameplay
+// state directly: they emit GameCommand events, and execute_game_commands applies them.
+// Anything else that wants to give orders - replay playback, AI, scripted triggers -
+// writes the same events, so every source goes through one code path.
+// Routing squads (see morale.rs) ignore squad orders until they rally.
+//
+// All emitters run in PlayerInputSet (or are otherwise ordered before the executor), so
+// commands apply on the frame they are issued, between two simulation ticks.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::archetype::UnitArchetypes;
+use crate::artillery::{queue_artillery_barrage, ArtilleryBarrage, ArtilleryState};
+use crate::cover::CoverMap;
+use crate::economy::{Economy, ProductionItem};
+use crate::navigation::NavGrid;
+use crate::scenario::{ScenarioState, WaveManager, WaveState};
+use crate::selection::SelectionState;
+use crate::terrain::{MapPreset, TerrainConfig, TerrainHeightmap};
+use crate::turrets::TurretType;
+use crate::types::*;
+
+/// Registers GameCommand and the executor
+pub struct GameCommandPlugin;
+
+impl Plugin for GameCommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GameCommand>()
+            .configure_sets(Update, PlayerInputSet.before(execute_game_commands))
+            .add_systems(Update, execute_game_commands);
+    }
+}
+
+/// Live command sources: player input, gameplay debug hotkeys and AI commanders.
+/// Runs before the executor; disabled during replay playback.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerInputSet;
+
+/// An order for the simulation, from the player or any other controller
+#[derive(Event, Clone, Debug, Serialize, Deserialize)]
+pub enum GameCommand {
+    /// Move squads to a destination (groups keep their shape, others spread into a line).
+    /// Without a facing, squads face the destination. Teleports during the preparation phase.
+    MoveSquads {
+        squad_ids: Vec<u32>,
+        destination: Vec3,
+        facing: Option<Vec3>,
+        mode: MovementMode,
+    },
+    /// Turn squads in place
+    SetFacing { squad_ids: Vec<u32>, facing: Vec3 },
+    /// Move / AttackMove / Hold for every unit in the squads
+    SetMovementMode { squad_ids: Vec<u32>, mode: MovementMode },
+    /// Reshape squads into a new formation
+    SetFormation { squad_ids: Vec<u32>, formation: FormationType },
+    FireArtillery(ArtilleryBarrage),
+    /// Place a turret during the preparation phase (uses the turret budget)
+    PlaceTurret { position: Vec3, turret_type: TurretType },
+    /// Remove the most recently placed turret during the preparation phase
+    UndoTurretPlacement,
+    Group { squad_ids: Vec<u32> },
+    Ungroup { squad_ids: Vec<u32> },
+    /// End the preparation phase and start the first assault
+    StartWaves,
+    /// All squads advance (true) or retreat to spawn (false)
+    MarchAll { advance: bool },
+    SwitchMap(MapPreset),
+    /// Pay for a squad, turret or artillery strike and add it to the team's production queue
+    QueueProduction { team: Team, item: ProductionItem },
+    /// Drop the team's most recently queued production order and refund it
+    CancelProduction { team: Team },
+}
+
+/// Apply every GameCommand issued this frame, in order
+pub fn execute_game_commands(
+    mut commands: Commands,
+    mut game_commands: EventReader<GameCommand>,
+    mut squad_manager: ResMut<SquadManager>,
+    mut selection_state: ResMut<SelectionState>,
+    mut droid_query: Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+    (nav_grid, cover_map): (Res<NavGrid>, Res<CoverMap>),
+    mut artillery_state: ResMut<ArtilleryState>,
+    scenario_state: Option<Res<ScenarioState>>,
+    mut wave_manager: Option<ResMut<WaveManager>>,
+    mut terrain_config: ResMut<TerrainConfig>,
+    mut sim_rng: ResMut<SimRng>,
+    (mut economy, archetypes): (ResMut<Economy>, Res<UnitArchetypes>),
+) {
+    let hm = heightmap.as_ref().map(|h| h.as_ref());
+
+    for command in game_commands.read() {
+        match command {
+            GameCommand::MoveSquads { squad_ids, destination, facing, mode } => {
+                // Preparation phase moves are instant
+                let is_prep_phase = scenario_state.as_ref()
+                    .zip(wave_manager.as_ref())
+                    .map(|(ss, wm)| ss.active && wm.wave_state == WaveState::Preparation)
+                    .unwrap_or(false);
+                let squad_ids = &squad_manager.obedient_squads(squad_ids);
+
+                crate::selection::execute_move_squads(
+                    &mut commands,
+                    &mut squad_manager,
+                    &mut selection_state,
+                    &mut droid_query,
+                    &mut meshes,
+                    &mut materials,
+                    squad_ids,
+                    *destination,
+                    *facing,
+                    *mode,
+                    hm,
+                    Some(&*nav_grid),
+                    Some(&*cover_map),
+                    is_prep_phase,
+                );
+            }
+            GameCommand::SetFacing { squad_ids, facing } => {
+                let squad_ids = &squad_manager.obedient_squads(squad_ids);
+                crate::selection::execute_set_facing(&mut squad_manager, &mut droid_query, squad_ids, *facing, hm);
+            }
+            GameCommand::SetMovementMode { squad_ids, mode } => {
+                let squad_ids = &squad_manager.obedient_squads(squad_ids);
+                crate::selection::execute_set_movement_mode(&mut droid_query, squad_ids, *mode);
+            }
+            GameCommand::SetFormation { squad_ids, formation } => {
+                let squad_ids = &squad_manager.obedient_squads(squad_ids);
+                crate::formation::execute_set_formation(&mut squad_manager, squad_ids, *formation);
+            }
+            GameCommand::FireArtillery(barrage) => {
+                queue_artillery_barrage(&mut artillery_state, *barrage, hm, &mut *sim_rng);
+            }
+            GameCommand::PlaceTurret { position, turret_type } => {
+                if let Some(wave_manager) = wave_manager.as_mut() {
+                    crate::scenario::place_turret(&mut commands, &mut meshes, &mut materials, wave_manager, *turret_type, *position);
+                }
+            }
+            GameCommand::UndoTurretPlacement => {
+                if let Some(wave_manager) = wave_manager.as_mut() {
+                    crate::scenario::undo_turret_placement(&mut commands, wave_manager);
+                }
+            }
+            GameCommand::Group { squad_ids } => {
+                crate::selection::execute_group(&mut selection_state, &squad_manager, squad_ids);
+            }
+            GameCommand::Ungroup { squad_ids } => {
+                crate::selection::execute_ungroup(&mut selection_state, squad_ids);
+            }
+            GameCommand::StartWaves => {
+                if let (Some(wave_manager), Some(scenario_state)) = (wave_manager.as_mut(), scenario_state.as_ref()) {
+                    crate::scenario::end_preparation(wave_manager, &scenario_state.definition);
+                }
+            }
+            GameCommand::MarchAll { advance } => {
+                let routing: Vec<u32> = squad_manager.squads.values()
+                    .filter(|squad| squad.is_routing())
+                    .map(|squad| squad.id)
+                    .collect();
+                crate::formation::march_all_squads(
+                    &mut squad_manager,
+                    droid_query.iter_mut()
+                        .filter(|(_, _, member, ..)| !routing.contains(&member.squad_id))
+                        .map(|(_, droid, ..)| droid),
+                    *advance,
+                );
+            }
+            GameCommand::SwitchMap(preset) => {
+                terrain_config.requested_map = Some(*preset);
+            }
+            GameCommand::QueueProduction { team, item } => {
+                economy.queue(*team, item.clone(), &archetypes);
+            }
+            GameCommand::CancelProduction { team } => {
+                if let Some(order) = economy.cancel_last(*team) {
+                    info!("Team {:?} cancelled {}, {:.0} credits refunded", team, order.item.label(&archetypes), order.cost);
+                }
+            }
+        }
+    }
+}
@@ -409,6 +409,20 @@ fn create_bottom_pivot_quad(size: f32) -> Mesh {
 
 // ===== ASSET LOADING =====
 
+/// Create the 3 debris mesh variants using Bevy's primitive shapes
+/// UE5 parts.md: 3 mesh variants randomly selected, size 5-7 units
+/// Shared with the render_debris_sprites tool so the baked sprites match
+pub fn create_debris_meshes(meshes: &mut Assets<Mesh>) -> [Handle<Mesh>; 3] {
+    [
+        // Variant 0: Small cube (chunky rock)
+        meshes.add(Cuboid::new(1.0, 0.8, 0.6)),
+        // Variant 1: Flat slab (debris piece)
+        meshes.add(Cuboid::new(1.2, 0.4, 0.8)),
+        // Variant 2: Elongated piece (shrapnel)
+        meshes.add(Cuboid::new(0.5, 0.5, 1.4)),
+    ]
+}
+
 pub fn setup_ground_explosion_assets(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -417,16 +431,7 @@ pub fn setup_ground_explosion_assets(
 ) {
     info!("🌋 Loading ground explosion assets...");
 
-    // Create 3 debris mesh variants using Bevy's primitive shapes
-    // UE5 parts.md: 3 mesh variants randomly selected, size 5-7 units
-    let debris_meshes = [
-        // Variant 0: Small cube (chunky rock)
-        meshes.add(Cuboid::new(1.0, 0.8, 0.6)),
-        // Variant 1: Flat slab (debris piece)
-        meshes.add(Cuboid::new(1.2, 0.4, 0.8)),
-        // Variant 2: Elongated piece (shrapnel)
-        meshes.add(Cuboid::new(0.5, 0.5, 1.4)),
-    ];
+    let debris_meshes = create_debris_meshes(&mut meshes);
 
     // Debris material - dark brown/grey unlit look
     // UE5: Color range (0.26, 0.17, 0.05) brown to (0.44, 0.42, 0.42) grey
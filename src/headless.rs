@@ -0,0 +1,187 @@
+// Headless mode - resolves a full battle without a window or renderer
+//
+// Usage: cargo run --release -- --headless [--seed <u64>] [--tick-rate <hz>]
+//
+// Every army is given an AttackMove order toward the nearest enemy Uplink Tower and the
+// simulation is stepped one fixed tick per update as fast as the CPU allows. When the
+// battle is decided (see WinConditions, plus a hard cap of HEADLESS_MAX_BATTLE_SECS) the
+// winner and per-team casualties are printed. Capture points are in play as on skirmish
+// maps. --teams sets up free-for-all and alliance battles.
+// The same seed always produces the same result.
+// Replays (--record, --replay) and scenarios (--scenario) are windowed-only and ignored here.
+
+use std::time::Duration;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::cli::CliArgs;
+use crate::constants::*;
+use crate::explosion_system::PendingExplosion;
+use crate::formation::squad_slot_offset;
+use crate::simulation::SimulationPlugin;
+use crate::terrain::TerrainHeightmap;
+use crate::types::*;
+
+/// Battles still unresolved after this much simulated time are reported as a draw
+const HEADLESS_MAX_BATTLE_SECS: f32 = 1800.0;
+/// Interval between progress log lines (simulated seconds)
+const HEADLESS_PROGRESS_INTERVAL: f32 = 30.0;
+
+/// Starting strength per team, captured on the first update after spawning
+#[derive(Resource, Default)]
+struct BattleReport {
+    initial: [usize; Team::MAX],
+    last_progress_log: f32,
+}
+
+/// Build and run the headless app. Returns once the battle is resolved.
+pub fn run(args: &CliArgs) {
+    if args.record.is_some() || args.replay.is_some() {
+        eprintln!("--record and --replay are not supported in headless mode, ignoring");
+    }
+    if args.scenario.is_some() {
+        eprintln!("--scenario is not supported in headless mode, ignoring");
+    }
+
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .add_plugins((
+            bevy::log::LogPlugin::default(),
+            bevy::transform::TransformPlugin, // GlobalTransform propagation (targeting uses world positions)
+            AssetPlugin::default(),
+        ))
+        .insert_resource(args.sim_rng())
+        .insert_resource(args.diplomacy())
+        .insert_resource(args.win_conditions())
+        // Spawn helpers still create mesh/material handles - register storage for them
+        // even though nothing is ever rendered
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<crate::shield::ShieldMaterial>()
+        .init_asset::<crate::explosion_shader::ExplosionMaterial>()
+        // Advance exactly one fixed tick per update
+        .insert_resource(args.fixed_time())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / args.tick_rate)))
+        .insert_resource(TerrainHeightmap::flat(TERRAIN_SIZE, -1.0))
+        .add_plugins(SimulationPlugin)
+        .init_resource::<BattleReport>()
+        .add_systems(Startup, (
+            crate::objective::spawn_uplink_towers,
+            crate::setup::spawn_army_with_squads,
+            crate::capture::spawn_skirmish_capture_points,
+        ))
+        .add_systems(PostStartup, issue_attack_orders)
+        .add_systems(Update, battle_report_system)
+        .run();
+}
+
+/// Send every squad toward the nearest enemy tower in AttackMove mode so the armies engage
+fn issue_attack_orders(
+    mut squad_manager: ResMut<SquadManager>,
+    heightmap: Res<TerrainHeightmap>,
+    diplomacy: Res<Diplomacy>,
+    tower_query: Query<(&Transform, &UplinkTower)>,
+    mut droid_query: Query<(&mut BattleDroid, &SquadMember, &mut MovementMode)>,
+) {
+    let team_count = diplomacy.teams.len();
+    for squad in squad_manager.squads.values_mut() {
+        let home = squad.team.home_position(team_count);
+        let Some(enemy_tower_pos) = tower_query.iter()
+            .filter(|(_, tower)| diplomacy.is_hostile(squad.team, tower.team))
+            .map(|(transform, _)| transform.translation)
+            .min_by(|a, b| a.distance_squared(home).total_cmp(&b.distance_squared(home)))
+        else {
+            continue;
+        };
+
+        // Keep the squad's lateral offset from its army's center so squads keep their spacing
+        let forward = Vec3::new(enemy_tower_pos.x - home.x, 0.0, enemy_tower_pos.z - home.z).normalize_or_zero();
+        let right = Vec3::new(forward.z, 0.0, -forward.x);
+        let lateral = right * right.dot(squad.center_position - home);
+        squad.target_position = Vec3::new(enemy_tower_pos.x, squad.center_position.y, enemy_tower_pos.z) + lateral;
+    }
+
+    for (mut droid, squad_member, mut mode) in droid_query.iter_mut() {
+        let Some(squad) = squad_manager.get_squad(squad_member.squad_id) else {
+            continue;
+        };
+
+        let offset = squad_slot_offset(squad, squad_member.formation_position);
+        let target_xz = squad.target_position + offset;
+        let target_y = heightmap.sample_height(target_xz.x, target_xz.z);
+
+        droid.target_position = Vec3::new(target_xz.x, target_y, target_xz.z);
+        droid.returning_to_spawn = false;
+        *mode = MovementMode::AttackMove;
+    }
+
+    info!("Headless: {} squads ordered to attack-move on the enemy tower", squad_manager.squads.len());
+}
+
+/// Log progress, then print the result and exit once the battle is decided
+fn battle_report_system(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    diplomacy: Res<Diplomacy>,
+    sim_rng: Res<SimRng>,
+    mut report: ResMut<BattleReport>,
+    droid_query: Query<&BattleDroid, Without<TurretRotatingAssembly>>,
+    pending_query: Query<(), With<PendingExplosion>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let mut alive = [0usize; Team::MAX];
+    for droid in droid_query.iter() {
+        alive[droid.team.index()] += 1;
+    }
+    let strengths = || diplomacy.teams.iter()
+        .map(|team| format!("Team {:?}: {} alive", team, alive[team.index()]))
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    // First update after startup: record starting strength
+    if report.initial.iter().all(|&count| count == 0) {
+        report.initial = alive;
+        info!("Headless battle started: {}", strengths());
+        return;
+    }
+
+    let elapsed = time.elapsed_secs();
+    if elapsed - report.last_progress_log >= HEADLESS_PROGRESS_INTERVAL {
+        report.last_progress_log = elapsed;
+        info!("[{:.0}s] {}", elapsed, strengths());
+    }
+
+    // Wait for the tower destruction cascade to finish so casualties include it
+    let resolved = game_state.game_ended && pending_query.is_empty();
+    let timed_out = elapsed >= HEADLESS_MAX_BATTLE_SECS;
+    if !resolved && !timed_out {
+        return;
+    }
+
+    let reason = game_state.end_reason.as_deref().unwrap_or("battle over");
+    let result = match game_state.winner {
+        Some(team) => {
+            let allies: Vec<String> = diplomacy.teams.iter()
+                .filter(|&&other| other != team && diplomacy.is_allied(other, team))
+                .map(|other| format!("{:?}", other))
+                .collect();
+            if allies.is_empty() {
+                format!("Team {:?} wins ({})", team, reason)
+            } else {
+                format!("Team {:?} and {} win ({})", team, allies.join(", "), reason)
+            }
+        }
+        None if game_state.game_ended => format!("Draw ({})", reason),
+        None => "Draw (time limit reached)".to_string(),
+    };
+
+    println!("===== BATTLE RESULT =====");
+    println!("{} after {:.1}s simulated (seed {})", result, elapsed, sim_rng.seed);
+    for team in &diplomacy.teams {
+        let (initial, surviving) = (report.initial[team.index()], alive[team.index()]);
+        println!("Team {:?} casualties: {} / {} ({} surviving)",
+            team, initial.saturating_sub(surviving), initial, surviving);
+    }
+
+    exit.write(AppExit::Success);
+}
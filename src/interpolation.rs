@@ -0,0 +1,114 @@
+// Transform interpolation between fixed simulation ticks
+//
+// Gameplay runs in FixedUpdate, so droid and projectile transforms only change once
+// per tick. Rendering-only systems (billboards, flipbooks, UI, camera) run every frame
+// in Update and would see units stutter whenever the frame rate and tick rate differ.
+//
+// Each tick the authoritative (simulated) transform is recorded. After the fixed loop
+// the visible Transform is blended between the last two ticks by the overstep fraction,
+// and restored to the simulated value before the next tick so gameplay never reads an
+// interpolated position. Transforms written outside the simulation (e.g. map switch
+// repositioning) are detected and treated as teleports.
+
+use bevy::prelude::*;
+use bevy::app::RunFixedMainLoopSystem;
+
+use crate::types::{BattleDroid, LaserProjectile};
+
+pub struct TransformInterpolationPlugin;
+
+impl Plugin for TransformInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(RunFixedMainLoop, attach_transform_interpolation.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop))
+            .add_systems(FixedFirst, restore_simulation_transforms)
+            .add_systems(FixedLast, record_simulation_transforms)
+            .add_systems(RunFixedMainLoop, interpolate_transforms.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop));
+    }
+}
+
+/// Simulated transforms of the last two ticks, plus the blended value last written
+#[derive(Component, Clone, Copy)]
+pub struct TransformInterpolation {
+    pub previous: Transform,
+    pub current: Transform,
+    /// Transform written by interpolate_transforms; anything else means an external teleport
+    rendered: Transform,
+}
+
+impl TransformInterpolation {
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            previous: transform,
+            current: transform,
+            rendered: transform,
+        }
+    }
+}
+
+/// Give newly spawned droids and projectiles interpolation state (runs every frame,
+/// so entities spawned by input systems between ticks are covered too)
+fn attach_transform_interpolation(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), (Or<(With<BattleDroid>, With<LaserProjectile>)>, Without<TransformInterpolation>)>,
+) {
+    for (entity, transform) in query.iter() {
+        commands.entity(entity).try_insert(TransformInterpolation::new(*transform));
+    }
+}
+
+/// Start of tick: put the simulated transform back and sync GlobalTransform for root
+/// entities, so targeting and hitscan read tick-exact world positions
+fn restore_simulation_transforms(
+    mut query: Query<(&mut Transform, &mut GlobalTransform, &mut TransformInterpolation, Has<ChildOf>)>,
+) {
+    for (mut transform, mut global, mut interpolation, is_child) in query.iter_mut() {
+        if *transform != interpolation.rendered {
+            // Moved outside the simulation (or an earlier tick this frame) - keep it
+            interpolation.current = *transform;
+        } else {
+            *transform = interpolation.current;
+        }
+        interpolation.previous = interpolation.current;
+
+        // Children (turret assemblies) keep last frame's propagation; their parents are static
+        if !is_child {
+            *global = GlobalTransform::from(*transform);
+        }
+    }
+}
+
+/// End of tick: record the simulated result
+fn record_simulation_transforms(
+    mut query: Query<(&Transform, &mut TransformInterpolation)>,
+) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.current = *transform;
+    }
+}
+
+/// After the fixed loop: blend the visible transform between the last two ticks
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        if *transform != interpolation.rendered && *transform != interpolation.current {
+            // Teleported since last frame without a tick in between - snap
+            interpolation.previous = *transform;
+            interpolation.current = *transform;
+        }
+
+        let previous = interpolation.previous;
+        let current = interpolation.current;
+        let blended = Transform {
+            translation: previous.translation.lerp(current.translation, alpha),
+            rotation: previous.rotation.slerp(current.rotation, alpha),
+            scale: previous.scale.lerp(current.scale, alpha),
+        };
+
+        *transform = blended;
+        interpolation.rendered = blended;
+    }
+}
@@ -0,0 +1,254 @@
+//! Bevy RTS battle simulation library
+//!
+//! Gameplay is split into plugins that can be mixed and matched:
+//! - `SquadPlugin`, `CombatPlugin`, `ObjectivePlugin`, `CapturePlugin`, `EconomyPlugin`: render-free simulation in `FixedUpdate`
+//!   (bundled as `SimulationPlugin`, used by headless mode)
+//! - `SelectionPlugin`, `ArtilleryPlugin`, `FogOfWarPlugin`, `MinimapPlugin`: player input, command feedback, fog of war and the minimap
+//! - `GamePlugin`: the full windowed game (everything above plus VFX, audio, UI and save/load)
+//! - `AiCommanderPlugin`: computer opponent for either team, added alongside `GamePlugin`
+//!
+//! Tools under src/bin can reuse the core types directly, e.g. `SquadManager`,
+//! `TerrainHeightmap`, `calculate_hit_chance` and `spawn_single_squad`.
+//!
+//! Unit types are data: each file in assets/units defines an archetype (see `archetype`).
+
+pub mod constants;
+pub mod types;
+pub mod math_utils;
+pub mod combat;
+pub mod formation;
+pub mod movement;
+pub mod setup;
+pub mod commander;
+pub mod objective;
+pub mod capture;
+pub mod economy;
+pub mod procedural_meshes;
+pub mod turrets;
+pub mod explosion_shader;
+pub mod explosion_system;
+pub mod particles;
+pub mod wfx_materials;
+pub mod wfx_spawn;
+pub mod selection;
+pub mod terrain;
+pub mod terrain_decor;
+pub mod shield;
+pub mod decals;
+pub mod scenario;
+pub mod ground_explosion;
+pub mod area_damage;
+pub mod artillery;
+pub mod collision;
+pub mod simulation;
+pub mod headless;
+pub mod cli;
+pub mod interpolation;
+pub mod replay;
+pub mod save_game;
+pub mod game_command;
+pub mod ai;
+pub mod navigation;
+pub mod archetype;
+pub mod morale;
+pub mod cover;
+pub mod fog_of_war;
+pub mod minimap;
+
+use bevy::prelude::*;
+
+pub use ai::AiCommanderPlugin;
+pub use archetype::UnitArchetypes;
+pub use artillery::ArtilleryPlugin;
+pub use combat::{calculate_hit_chance, CombatPlugin};
+pub use formation::SquadPlugin;
+pub use objective::ObjectivePlugin;
+pub use capture::CapturePlugin;
+pub use economy::EconomyPlugin;
+pub use selection::SelectionPlugin;
+pub use setup::spawn_single_squad;
+pub use simulation::SimulationPlugin;
+pub use terrain::TerrainHeightmap;
+pub use types::{SimRng, SquadManager};
+
+use explosion_shader::ExplosionShaderPlugin;
+use particles::ParticleEffectsPlugin;
+use terrain::TerrainPlugin;
+use terrain_decor::TerrainDecorPlugin;
+use wfx_materials::{SmokeScrollMaterial, AdditiveMaterial, SmokeOnlyMaterial};
+use shield::ShieldPlugin;
+use decals::DecalPlugin;
+use scenario::ScenarioPlugin;
+use fog_of_war::FogOfWarPlugin;
+use minimap::MinimapPlugin;
+use save_game::SaveGamePlugin;
+use game_command::{GameCommandPlugin, PlayerInputSet};
+
+/// The full windowed game. Expects DefaultPlugins to already be added.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+            .add_plugins(ExplosionShaderPlugin)
+            .add_plugins(ParticleEffectsPlugin)
+            .add_plugins(TerrainPlugin)
+            .add_plugins(TerrainDecorPlugin)
+            .add_plugins(ShieldPlugin)
+            .add_plugins(DecalPlugin)
+            .add_plugins(ScenarioPlugin)
+            .add_plugins(GameCommandPlugin)
+            .add_plugins(SimulationPlugin)
+            .add_plugins(SelectionPlugin)
+            .add_plugins(ArtilleryPlugin)
+            .add_plugins(FogOfWarPlugin)
+            .add_plugins(MinimapPlugin)
+            .add_plugins(SaveGamePlugin)
+            .add_plugins(MaterialPlugin::<SmokeScrollMaterial>::default())
+            .add_plugins(MaterialPlugin::<AdditiveMaterial>::default())
+            .add_plugins(MaterialPlugin::<SmokeOnlyMaterial>::default())
+            .add_plugins(MaterialPlugin::<ground_explosion::FlipbookMaterial>::default())
+            .add_plugins(MaterialPlugin::<turrets::HealthBarMaterial>::default())
+            .add_plugins(MaterialPlugin::<objective::ShieldBarMaterial>::default())
+            .insert_resource(types::ExplosionDucking::default())
+            .insert_resource(objective::ExplosionDebugMode::default())
+            .insert_resource(ground_explosion::GroundExplosionDebugMenu::default())
+            .add_systems(Startup, (
+                setup::setup_scene,
+                objective::spawn_uplink_towers,
+                objective::spawn_debug_mode_ui,
+                capture::spawn_capture_hud,
+                economy::spawn_economy_hud,
+                combat::setup_laser_assets,
+                ground_explosion::setup_ground_explosion_assets,
+                ground_explosion::setup_ground_explosion_debug_ui,
+            ))
+            // Army spawning runs after terrain is ready (terrain spawns in TerrainPlugin's Startup)
+            .add_systems(Startup, setup::spawn_army_with_squads.after(terrain::spawn_initial_terrain))
+            .add_systems(Startup, capture::spawn_skirmish_capture_points.after(terrain::spawn_initial_terrain))
+            // Turret spawning runs after terrain is ready
+            .add_systems(Startup, (
+                turrets::spawn_functional_turret.after(terrain::spawn_initial_terrain),
+                turrets::spawn_mg_turret.after(terrain::spawn_initial_terrain),
+            ))
+            .add_systems(Update, (
+                // Map switching - respawn turrets when terrain changes
+                turrets::respawn_turrets_on_map_switch,
+                // Debug turret toggle (M=MG, H=Heavy when debug mode active)
+                turrets::debug_turret_toggle_system.in_set(PlayerInputSet),
+                // Turret health bars
+                turrets::spawn_turret_health_bars,
+                turrets::update_turret_health_bars,
+                // Turret death explosions (destruction itself is simulated in ObjectivePlugin)
+                turrets::turret_death_system,
+            ))
+            .add_systems(Update, (
+                // Squad input and commander visuals (formation/movement simulation lives in SquadPlugin)
+                formation::squad_movement_system.in_set(PlayerInputSet),
+                commander::commander_visual_update_system,
+                // Commander debug markers (glowing cubes above commanders)
+                formation::commander_visual_marker_system,
+                formation::update_commander_markers_system,
+                formation::rank_chevron_system, // Veterancy chevrons follow the squad's commander
+                movement::update_fps_display,
+                movement::rts_camera_movement,
+            ))
+            .add_systems(Update, (
+                // Combat presentation (targeting and hitscan live in CombatPlugin)
+                combat::mg_burst_audio_sync_system, // Fade burst audio when firing stops (after this frame's ticks)
+                combat::audio_fade_out_system,    // Apply audio fade-outs and despawn silent clips
+                combat::explosion_ducking_system, // Dip gunfire while explosions play
+                // Debug volley (F) spawns live lasers: off during replay recording and playback
+                combat::volley_fire_system
+                    .in_set(PlayerInputSet)
+                    .run_if(not(resource_exists::<replay::ReplayRecorder>)),
+                combat::update_hitscan_tracers,   // Update visual tracers
+            ))
+            .add_systems(FixedUpdate, (
+                // Projectile flight and hits are gameplay, so they tick with the simulation
+                combat::update_projectiles,
+                // Shield collision detection runs BEFORE unit collision
+                shield::shield_collision_system.after(combat::update_projectiles),
+                objective::tower_targeting_system.after(shield::shield_collision_system),
+                combat::turret_rotation_system,
+            ).before(combat::collision_detection_system))
+            .add_systems(Update, (
+                shield::shield_health_visual_system,
+                shield::animate_shields,
+                shield::debug_destroy_enemy_shield.in_set(PlayerInputSet), // Debug: Press '0' to destroy enemy shield
+                combat::visualize_collision_spheres_system, // Debug visualization
+            ))
+            .add_systems(Update, (
+                // Objective presentation (pending explosions resolve in ObjectivePlugin)
+                objective::pending_explosion_system,
+                objective::explosion_effect_system,
+                objective::update_debug_mode_ui,
+                objective::debug_explosion_hotkey_system.in_set(PlayerInputSet),
+                objective::debug_warfx_test_system,
+                objective::debug_ground_explosion_system,
+                objective::debug_spawn_shield_system.in_set(PlayerInputSet),
+                // Tower and shield health bars
+                objective::spawn_tower_health_bars,
+                objective::update_tower_health_bars,
+            ))
+            .add_systems(Update, (
+                // Capture points: skirmish layout per map, rings, progress bars and the points HUD
+                capture::respawn_capture_points_on_map_switch,
+                capture::spawn_capture_point_visuals,
+                capture::update_capture_point_visuals,
+                capture::update_capture_hud,
+            ))
+            .add_systems(Update, (
+                // Credits and production: new battles reset the economy, hotkeys queue orders
+                economy::reset_economy_on_map_switch,
+                economy::production_input_system.in_set(PlayerInputSet),
+                economy::update_economy_hud,
+            ))
+            .add_systems(Update, (
+                // War FX explosion animations
+                wfx_spawn::update_warfx_explosions,
+                wfx_spawn::animate_explosion_flames,
+                wfx_spawn::animate_warfx_billboards,
+                wfx_spawn::animate_warfx_smoke_billboards,
+                wfx_spawn::animate_explosion_billboards,
+                wfx_spawn::animate_smoke_only_billboards,
+                wfx_spawn::animate_glow_sparkles,
+            ))
+            .add_systems(Update, (
+                // Ground explosion animations (UE5 Niagara-style)
+                ground_explosion::animate_flipbook_sprites,
+                ground_explosion::update_velocity_aligned_billboards,
+                ground_explosion::update_camera_facing_billboards,
+                ground_explosion::update_smoke_physics,
+                ground_explosion::update_dirt_physics,
+                ground_explosion::update_smoke_scale,
+                ground_explosion::update_fireball_scale,
+                ground_explosion::update_fireball_uv_zoom,
+                ground_explosion::update_dirt_scale,
+                ground_explosion::update_dirt_alpha,
+                ground_explosion::update_dirt001_scale,
+                ground_explosion::update_dirt001_alpha,
+            ))
+            .add_systems(Update, (
+                // Ground explosion animations continued
+                ground_explosion::update_dust_scale,
+                ground_explosion::update_dust_alpha,
+                ground_explosion::update_wisp_physics,
+                ground_explosion::update_wisp_scale,
+                ground_explosion::update_wisp_alpha,
+                ground_explosion::update_smoke_color,
+                // Spark HDR color curves and physics
+                ground_explosion::update_spark_color,
+                ground_explosion::update_spark_l_color,
+                ground_explosion::update_spark_l_physics,
+                // Parts debris (3D mesh) physics and scale
+                ground_explosion::update_parts_physics,
+                ground_explosion::update_parts_scale,
+                ground_explosion::animate_additive_sprites,
+                ground_explosion::update_impact_lights,
+                ground_explosion::cleanup_ground_explosions,
+                ground_explosion::ground_explosion_debug_menu_system,
+                ground_explosion::update_ground_explosion_debug_ui,
+            ));
+    }
+}
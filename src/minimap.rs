@@ -0,0 +1,337 @@
+// Minimap: overview of the whole battlefield (top-right, under the FPS counter)
+//
+// The minimap is a CPU-painted image shown in a UI node. The terrain layer is shaded from
+// the TerrainHeightmap and cached until the terrain changes; every MINIMAP_REFRESH_INTERVAL
+// it is copied and overlaid with the player's fog of war, squads (Team::shield_color),
+// uplink towers, turrets, scenario spawn points and the camera's view of the ground.
+// Hostile squads and turrets only appear where the player can see them.
+//
+// Left-click (or drag) moves RtsCamera.focus_point; right-click orders the selected squads
+// to move there (Shift for Attack Move). World clicks under the minimap are ignored by the
+// selection, move, placement and artillery input systems (see cursor_over_minimap).
+
+use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::PrimaryWindow;
+
+use crate::constants::*;
+use crate::fog_of_war::TeamVision;
+use crate::game_command::{GameCommand, PlayerInputSet};
+use crate::scenario::SpawnPointMarker;
+use crate::selection::SelectionState;
+use crate::terrain::TerrainHeightmap;
+use crate::types::*;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_minimap)
+            .add_systems(Update, (
+                minimap_camera_system,
+                minimap_move_command_system.in_set(PlayerInputSet),
+                minimap_render_system,
+            ));
+    }
+}
+
+/// The minimap image and its cached terrain layer
+#[derive(Resource)]
+pub struct Minimap {
+    image: Handle<Image>,
+    terrain_layer: Vec<[u8; 4]>,
+    refresh_timer: Timer,
+}
+
+#[derive(Component)]
+pub struct MinimapUI;
+
+/// Is the cursor over the minimap panel? (world clicks there are ignored)
+pub fn cursor_over_minimap(window: &Window) -> bool {
+    window.cursor_position().is_some_and(|cursor| minimap_pixel(window, cursor).is_some())
+}
+
+/// Minimap pixel under a window position, if it is inside the panel
+fn minimap_pixel(window: &Window, cursor: Vec2) -> Option<Vec2> {
+    let left = window.width() - MINIMAP_MARGIN - MINIMAP_SIZE as f32;
+    let local = cursor - Vec2::new(left, MINIMAP_TOP);
+    let size = MINIMAP_SIZE as f32;
+    (local.x >= 0.0 && local.y >= 0.0 && local.x < size && local.y < size).then_some(local)
+}
+
+/// World XZ (y = 0) for a minimap pixel; north (-Z) is up
+fn pixel_to_world(pixel: Vec2) -> Vec3 {
+    let scale = TERRAIN_SIZE / MINIMAP_SIZE as f32;
+    Vec3::new(pixel.x * scale - TERRAIN_SIZE / 2.0, 0.0, pixel.y * scale - TERRAIN_SIZE / 2.0)
+}
+
+/// Minimap pixel for a world position (may lie outside the image)
+fn world_to_pixel(position: Vec3) -> (i32, i32) {
+    let scale = MINIMAP_SIZE as f32 / TERRAIN_SIZE;
+    (
+        ((position.x + TERRAIN_SIZE / 2.0) * scale) as i32,
+        ((position.z + TERRAIN_SIZE / 2.0) * scale) as i32,
+    )
+}
+
+fn spawn_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = images.add(Image::new_fill(
+        Extent3d { width: MINIMAP_SIZE, height: MINIMAP_SIZE, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    ));
+
+    commands.spawn((
+        ImageNode::new(image.clone()),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(MINIMAP_TOP),
+            right: Val::Px(MINIMAP_MARGIN),
+            width: Val::Px(MINIMAP_SIZE as f32),
+            height: Val::Px(MINIMAP_SIZE as f32),
+            ..default()
+        },
+        MinimapUI,
+    ));
+
+    commands.insert_resource(Minimap {
+        image,
+        terrain_layer: Vec::new(),
+        refresh_timer: Timer::from_seconds(MINIMAP_REFRESH_INTERVAL, TimerMode::Repeating),
+    });
+}
+
+/// World position under the cursor, if it is over the minimap
+fn minimap_cursor_world(window: &Window, heightmap: Option<&TerrainHeightmap>) -> Option<Vec3> {
+    let pixel = window.cursor_position().and_then(|cursor| minimap_pixel(window, cursor))?;
+    let mut world_pos = pixel_to_world(pixel);
+    world_pos.y = heightmap.map_or(0.0, |hm| hm.sample_height(world_pos.x, world_pos.z));
+    Some(world_pos)
+}
+
+/// Left-click/drag on the minimap: jump the camera there
+pub fn minimap_camera_system(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut RtsCamera>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+) {
+    if !mouse_button.pressed(MouseButton::Left) {
+        return;
+    }
+    let Ok(window) = window_query.single() else { return };
+    let Some(world_pos) = minimap_cursor_world(window, heightmap.as_deref()) else { return };
+
+    if let Ok(mut camera) = camera_query.single_mut() {
+        camera.focus_point = world_pos;
+    }
+}
+
+/// Right-click on the minimap: move the selected squads there (Shift = Attack Move)
+pub fn minimap_move_command_system(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    selection_state: Res<SelectionState>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+    mut game_commands: EventWriter<GameCommand>,
+) {
+    if !mouse_button.just_pressed(MouseButton::Right) || selection_state.selected_squads.is_empty() {
+        return;
+    }
+    let Ok(window) = window_query.single() else { return };
+    let Some(destination) = minimap_cursor_world(window, heightmap.as_deref()) else { return };
+
+    let shift_held = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    game_commands.write(GameCommand::MoveSquads {
+        squad_ids: selection_state.selected_squads.clone(),
+        destination,
+        facing: None,
+        mode: if shift_held { MovementMode::AttackMove } else { MovementMode::Move },
+    });
+}
+
+/// Repaint the minimap image
+pub fn minimap_render_system(
+    time: Res<Time>,
+    minimap: Option<ResMut<Minimap>>,
+    mut images: ResMut<Assets<Image>>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+    (vision, diplomacy, squad_manager): (Res<TeamVision>, Res<Diplomacy>, Res<SquadManager>),
+    tower_query: Query<(&GlobalTransform, &UplinkTower, &Health)>,
+    turret_query: Query<(&GlobalTransform, &TurretBase)>,
+    spawn_point_query: Query<&GlobalTransform, With<SpawnPointMarker>>,
+    camera_query: Query<(&Camera, &GlobalTransform, &RtsCamera)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Some(mut minimap) = minimap else { return };
+
+    let terrain_changed = heightmap.as_ref().is_some_and(|hm| hm.is_changed());
+    if terrain_changed || minimap.terrain_layer.is_empty() {
+        minimap.terrain_layer = shade_terrain(heightmap.as_deref());
+    }
+
+    minimap.refresh_timer.tick(time.delta());
+    if !minimap.refresh_timer.just_finished() && !terrain_changed {
+        return;
+    }
+
+    let Some(image) = images.get_mut(&minimap.image) else { return };
+    let Some(data) = image.data.as_mut() else { return };
+    let mut canvas = Canvas { data: data.as_mut_slice() };
+
+    // Terrain, darkened where the player can't see
+    for (index, &color) in minimap.terrain_layer.iter().enumerate() {
+        let pixel = Vec2::new((index as u32 % MINIMAP_SIZE) as f32 + 0.5, (index as u32 / MINIMAP_SIZE) as f32 + 0.5);
+        let color = if vision.is_visible(Team::A, pixel_to_world(pixel)) {
+            color
+        } else {
+            [color[0] / 2, color[1] / 2, color[2] / 2, 255]
+        };
+        canvas.data[index * 4..index * 4 + 4].copy_from_slice(&color);
+    }
+
+    let spotted = |team: Team, position: Vec3| !diplomacy.is_hostile(Team::A, team) || vision.is_visible(Team::A, position);
+
+    for position in spawn_point_query.iter() {
+        canvas.diamond(world_to_pixel(position.translation()), 4, [255, 160, 40, 255]);
+    }
+    for (transform, tower, health) in tower_query.iter() {
+        let color = if health.is_dead() { [90, 90, 90, 255] } else { rgba(tower.team.shield_color()) };
+        canvas.square(world_to_pixel(transform.translation()), 4, [255, 255, 255, 255]);
+        canvas.square(world_to_pixel(transform.translation()), 3, color);
+    }
+    for (transform, turret) in turret_query.iter() {
+        if spotted(turret.team, transform.translation()) {
+            canvas.square(world_to_pixel(transform.translation()), 2, [230, 230, 230, 255]);
+        }
+    }
+    for squad in squad_manager.squads.values() {
+        if squad.members.is_empty() || !spotted(squad.team, squad.center_position) {
+            continue;
+        }
+        canvas.square(world_to_pixel(squad.center_position), 1, rgba(squad.team.shield_color()));
+    }
+
+    // Camera view: where the screen corners meet the ground
+    if let (Ok((camera, camera_transform, rts_camera)), Ok(window)) = (camera_query.single(), window_query.single()) {
+        let corners = [
+            Vec2::ZERO,
+            Vec2::new(window.width(), 0.0),
+            Vec2::new(window.width(), window.height()),
+            Vec2::new(0.0, window.height()),
+        ];
+        let ground: Vec<(i32, i32)> = corners.iter()
+            .filter_map(|&corner| camera.viewport_to_world(camera_transform, corner).ok())
+            .map(|ray| {
+                let direction = *ray.direction;
+                let distance = if direction.y < -0.01 {
+                    ((rts_camera.focus_point.y - ray.origin.y) / direction.y).min(TERRAIN_SIZE)
+                } else {
+                    TERRAIN_SIZE
+                };
+                world_to_pixel(ray.origin + direction * distance)
+            })
+            .collect();
+        for (i, &start) in ground.iter().enumerate() {
+            canvas.line(start, ground[(i + 1) % ground.len()], [255, 255, 255, 255]);
+        }
+    }
+}
+
+/// Height-tinted, hill-shaded terrain (light from the north-west)
+fn shade_terrain(heightmap: Option<&TerrainHeightmap>) -> Vec<[u8; 4]> {
+    let size = MINIMAP_SIZE as usize;
+    let step = TERRAIN_SIZE / MINIMAP_SIZE as f32;
+    let height_at = |x: usize, z: usize| {
+        let world = pixel_to_world(Vec2::new(x as f32 + 0.5, z as f32 + 0.5));
+        heightmap.map_or(0.0, |hm| hm.sample_height(world.x, world.z))
+    };
+
+    let heights: Vec<f32> = (0..size * size).map(|index| height_at(index % size, index / size)).collect();
+    let (min, max) = heights.iter().fold((f32::MAX, f32::MIN), |(min, max), &h| (min.min(h), max.max(h)));
+    let range = (max - min).max(1.0);
+
+    (0..size * size)
+        .map(|index| {
+            let (x, z) = (index % size, index / size);
+            let here = heights[index];
+            let west = heights[z * size + x.saturating_sub(1)];
+            let north = heights[z.saturating_sub(1) * size + x];
+            let slope_light = (((here - west) + (here - north)) / step * 0.5).clamp(-0.4, 0.4);
+            let elevation = (here - min) / range;
+
+            let brightness = (0.55 + elevation * 0.35 + slope_light).clamp(0.1, 1.0);
+            [
+                (brightness * 190.0) as u8,
+                (brightness * 165.0) as u8,
+                (brightness * 120.0) as u8,
+                255,
+            ]
+        })
+        .collect()
+}
+
+fn rgba(color: Color) -> [u8; 4] {
+    color.to_srgba().to_u8_array()
+}
+
+/// Pixel drawing on the minimap image
+struct Canvas<'a> {
+    data: &'a mut [u8],
+}
+
+impl Canvas<'_> {
+    fn plot(&mut self, (x, y): (i32, i32), color: [u8; 4]) {
+        let size = MINIMAP_SIZE as i32;
+        if x < 0 || y < 0 || x >= size || y >= size {
+            return;
+        }
+        let index = (y * size + x) as usize * 4;
+        self.data[index..index + 4].copy_from_slice(&[color[0], color[1], color[2], 255]);
+    }
+
+    fn square(&mut self, (x, y): (i32, i32), half: i32, color: [u8; 4]) {
+        for dy in -half..=half {
+            for dx in -half..=half {
+                self.plot((x + dx, y + dy), color);
+            }
+        }
+    }
+
+    fn diamond(&mut self, (x, y): (i32, i32), half: i32, color: [u8; 4]) {
+        for dy in -half..=half {
+            let width = half - dy.abs();
+            for dx in -width..=width {
+                self.plot((x + dx, y + dy), color);
+            }
+        }
+    }
+
+    /// Bresenham line
+    fn line(&mut self, (x0, y0): (i32, i32), (x1, y1): (i32, i32), color: [u8; 4]) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+        // Far-off corners are clamped so the loop stays short
+        let limit = 8 * MINIMAP_SIZE as i32;
+        for _ in 0..limit {
+            self.plot((x, y), color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Squad morale, routing and suppression
+//
+// Every squad has a morale value from 0 to 1. It drops when the squad takes casualties,
+// loses its commander, is caught near an explosion, or when its (or an ally's) uplink
+// tower falls. It only recovers near steady allied squads or a standing allied tower.
+//
+// A squad whose morale falls to MORALE_BREAK_THRESHOLD routs: its units run back to
+// their spawn positions and the squad ignores orders (see execute_game_commands) for at
+// least MORALE_MIN_ROUT_TIME seconds. It rallies where it stands once its morale has
+// climbed back to MORALE_RALLY_THRESHOLD.
+//
+// Suppression (0 to 1) is the short-term effect of incoming fire. Near misses (see
+// hitscan_fire_system), Continuous MG fire and nearby explosions pile it on; it wears off
+// at SUPPRESSION_DECAY_RATE. Suppressed squads shoot worse and march slower.
+
+use bevy::prelude::*;
+
+use crate::archetype::UnitArchetypes;
+use crate::constants::*;
+use crate::formation::squad_slot_offset;
+use crate::types::*;
+
+/// Update morale from this tick's shocks and recovery, then break or rally squads
+pub fn squad_morale_system(
+    time: Res<Time>,
+    mut squad_manager: ResMut<SquadManager>,
+    archetypes: Res<UnitArchetypes>,
+    diplomacy: Res<Diplomacy>,
+    game_state: Res<GameState>,
+    mut towers_seen: Local<usize>,
+    mut area_damage_events: EventReader<AreaDamageEvent>,
+    tower_query: Query<(&Transform, &UplinkTower, &Health)>,
+    mut droid_query: Query<(&mut BattleDroid, &SquadMember, &mut MovementMode)>,
+) {
+    let delta = time.delta_secs();
+
+    // Towers that fell since the last tick (the list is cleared when a new battle starts)
+    if game_state.destroyed_towers.len() < *towers_seen {
+        *towers_seen = 0;
+    }
+    let fallen_towers: Vec<Team> = game_state.destroyed_towers[*towers_seen..].to_vec();
+    *towers_seen = game_state.destroyed_towers.len();
+
+    let blasts: Vec<(Vec3, f32)> = area_damage_events.read().map(|event| (event.position, event.scale)).collect();
+
+    let standing_towers: Vec<(Team, Vec3)> = tower_query.iter()
+        .filter(|(_, _, health)| !health.is_dead())
+        .map(|(transform, tower, _)| (tower.team, transform.translation))
+        .collect();
+
+    // Steady squads reassure their neighbours; routing ones don't
+    let steady_squads: Vec<(u32, Team, Vec3)> = squad_manager.squads.values()
+        .filter(|squad| !squad.members.is_empty() && !squad.is_routing())
+        .map(|squad| (squad.id, squad.team, squad.center_position))
+        .collect();
+
+    let mut breaking: Vec<u32> = Vec::new();
+    let mut rallying: Vec<u32> = Vec::new();
+
+    for squad in squad_manager.squads.values_mut() {
+        if squad.members.is_empty() {
+            continue;
+        }
+
+        // Shocks
+        let full_size = archetypes.get(&squad.archetype).squad_size.max(1) as f32;
+        let mut loss = squad.casualties as f32 / full_size * MORALE_CASUALTY_WEIGHT;
+        squad.casualties = 0;
+
+        if squad.commander_lost {
+            loss += MORALE_COMMANDER_LOSS;
+            squad.commander_lost = false;
+        }
+
+        for &(position, scale) in &blasts {
+            if squad.center_position.distance(position) < MORALE_EXPLOSION_RADIUS * scale {
+                loss += MORALE_EXPLOSION_LOSS * scale;
+            }
+        }
+
+        for &team in &fallen_towers {
+            if diplomacy.is_allied(squad.team, team) {
+                loss += MORALE_TOWER_LOSS;
+            }
+        }
+
+        // Recovery
+        let near_tower = standing_towers.iter().any(|&(team, position)| {
+            diplomacy.is_allied(squad.team, team) && squad.center_position.distance(position) < MORALE_TOWER_RADIUS
+        });
+        let near_friends = steady_squads.iter().any(|&(id, team, position)| {
+            id != squad.id && diplomacy.is_allied(squad.team, team)
+                && squad.center_position.distance(position) < MORALE_FRIENDLY_RADIUS
+        });
+        let recovery = if near_tower || near_friends { MORALE_RECOVERY_RATE * delta } else { 0.0 };
+
+        squad.morale = (squad.morale - loss + recovery).clamp(0.0, 1.0);
+
+        // Break or rally
+        match squad.rout_timer {
+            None if squad.morale <= MORALE_BREAK_THRESHOLD => {
+                squad.rout_timer = Some(0.0);
+                breaking.push(squad.id);
+            }
+            Some(elapsed) if elapsed + delta >= MORALE_MIN_ROUT_TIME && squad.morale >= MORALE_RALLY_THRESHOLD => {
+                squad.rout_timer = None;
+                squad.set_direct_target(squad.center_position);
+                rallying.push(squad.id);
+            }
+            Some(elapsed) => squad.rout_timer = Some(elapsed + delta),
+            None => {}
+        }
+    }
+
+    if breaking.is_empty() && rallying.is_empty() {
+        return;
+    }
+
+    for &squad_id in &breaking {
+        info!("Squad {} is routing!", squad_id);
+    }
+    for &squad_id in &rallying {
+        info!("Squad {} has rallied", squad_id);
+    }
+
+    // Routing units run for spawn without stopping to fight; rallied units hold where the squad stands
+    for (mut droid, squad_member, mut mode) in droid_query.iter_mut() {
+        if breaking.contains(&squad_member.squad_id) {
+            droid.target_position = droid.spawn_position;
+            droid.returning_to_spawn = true;
+            *mode = MovementMode::Move;
+        } else if rallying.contains(&squad_member.squad_id) {
+            if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
+                let target_xz = squad.target_position + squad_slot_offset(squad, squad_member.formation_position);
+                droid.target_position = Vec3::new(target_xz.x, droid.spawn_position.y, target_xz.z);
+                droid.returning_to_spawn = false;
+            }
+        }
+    }
+}
+
+/// Wear suppression off over time and pile it onto squads near this tick's explosions
+pub fn squad_suppression_system(
+    time: Res<Time>,
+    mut squad_manager: ResMut<SquadManager>,
+    mut area_damage_events: EventReader<AreaDamageEvent>,
+) {
+    let decay = SUPPRESSION_DECAY_RATE * time.delta_secs();
+    let blasts: Vec<(Vec3, f32)> = area_damage_events.read().map(|event| (event.position, event.scale)).collect();
+
+    for squad in squad_manager.squads.values_mut() {
+        squad.suppression = (squad.suppression - decay).max(0.0);
+
+        for &(position, scale) in &blasts {
+            if squad.center_position.distance(position) < SUPPRESSION_EXPLOSION_RADIUS * scale {
+                squad.suppress(SUPPRESSION_PER_EXPLOSION * scale);
+            }
+        }
+    }
+}
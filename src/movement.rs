@@ -2,6 +2,7 @@
 use bevy::prelude::*;
 use bevy::input::mouse::{MouseScrollUnit, MouseWheel, MouseMotion};
 use crate::types::*;
+use crate::archetype::UnitStats;
 use crate::constants::*;
 use crate::terrain::TerrainHeightmap;
 
@@ -36,7 +37,7 @@ pub fn animate_march(
     squad_manager: Res<SquadManager>,
     heightmap: Option<Res<TerrainHeightmap>>,
     spatial_grid: Res<SpatialGrid>,
-    mut query: Query<(Entity, &mut BattleDroid, &mut Transform, &SquadMember, &MovementMode, &CombatUnit), (Without<KnockbackState>, Without<RagdollDeath>)>,
+    mut query: Query<(Entity, &mut BattleDroid, &mut Transform, &SquadMember, &MovementMode, &CombatUnit, &UnitStats, Option<&Health>), (Without<KnockbackState>, Without<RagdollDeath>)>,
 ) {
     let time_seconds = time.elapsed_secs();
     let delta_time = time.delta_secs();
@@ -44,12 +45,12 @@ pub fn animate_march(
     // Collect all droid positions first for soft avoidance lookups (avoids query conflicts)
     // Only build HashMap if soft avoidance is enabled
     let droid_positions: std::collections::HashMap<Entity, Vec3> = if SOFT_AVOIDANCE_STRENGTH > 0.0 {
-        query.iter().map(|(e, _, t, _, _, _)| (e, t.translation)).collect()
+        query.iter().map(|(e, _, t, ..)| (e, t.translation)).collect()
     } else {
         std::collections::HashMap::new()
     };
 
-    for (entity, droid, mut transform, squad_member, movement_mode, combat_unit) in query.iter_mut() {
+    for (entity, droid, mut transform, squad_member, movement_mode, combat_unit, stats, health) in query.iter_mut() {
         // Check movement mode - Hold always stops, AttackMove stops when engaged
         let mode_allows_movement = match movement_mode {
             MovementMode::Hold => false,
@@ -90,8 +91,18 @@ pub fn animate_march(
                 current_target.z - transform.translation.z,
             ).normalize_or_zero();
 
-            // Calculate effective speed with soft avoidance
-            let mut effective_speed = MARCH_SPEED * droid.march_speed;
+            // Calculate effective speed (wounded units limp, suppressed squads crawl) with soft avoidance
+            let wound_speed = health.map_or(1.0, |h| h.wound_state().speed_modifier());
+            let suppression_speed = squad_manager.get_squad(squad_member.squad_id)
+                .map_or(1.0, |squad| squad.suppression_speed_modifier());
+            let mut effective_speed = stats.speed * droid.march_speed * wound_speed * suppression_speed;
+
+            // Slow turners (vehicles) pivot toward the target before driving off
+            if stats.turn_rate.is_finite() {
+                let forward = transform.rotation * Vec3::Z;
+                let heading = Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero();
+                effective_speed *= heading.dot(horizontal_dir).max(0.0);
+            }
 
             // Soft avoidance - slow down when near other units (configurable, can be turned off)
             if SOFT_AVOIDANCE_STRENGTH > 0.0 {
@@ -140,7 +151,8 @@ pub fn animate_march(
             let sway = (time_seconds * droid.march_speed * 2.0 + droid.march_offset).sin() * 0.01;
             if horizontal_dir.length() > 0.1 {
                 let forward_rotation = Quat::from_rotation_y(horizontal_dir.x.atan2(horizontal_dir.z));
-                transform.rotation = forward_rotation * Quat::from_rotation_y(sway);
+                let target_rotation = forward_rotation * Quat::from_rotation_y(sway);
+                transform.rotation = transform.rotation.rotate_towards(target_rotation, stats.turn_rate * delta_time);
             }
         } else {
             // When stationary, still update terrain height (important for map switching)
@@ -154,8 +166,9 @@ pub fn animate_march(
                 let facing = squad.facing_direction;
                 if facing.length() > 0.1 {
                     let target_rotation = Quat::from_rotation_y(facing.x.atan2(facing.z));
-                    // Smoothly interpolate toward target rotation
-                    transform.rotation = transform.rotation.slerp(target_rotation, 5.0 * delta_time);
+                    // Smoothly interpolate toward target rotation, no faster than the unit can turn
+                    let eased = transform.rotation.slerp(target_rotation, 5.0 * delta_time);
+                    transform.rotation = transform.rotation.rotate_towards(eased, stats.turn_rate * delta_time);
                 }
             }
         }
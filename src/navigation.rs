@@ -0,0 +1,392 @@
+// Navigation grid for squad movement
+//
+// NavGrid covers the terrain with square cells. Each cell's traversal cost grows with the
+// terrain slope (sampled from TerrainHeightmap); cells that are too steep, or covered by a
+// Rock or a BuildingCollider (towers, turrets, buildings), are blocked. The grid is rebuilt
+// whenever the heightmap or the set of obstacles changes.
+//
+// A move order builds one flow field (a Dijkstra integration field) toward its destination.
+// Each ordered squad walks down the field from where it stands, and the resulting cell path
+// is smoothed into a handful of waypoints. Squad::target_position is always the waypoint the
+// squad is currently heading for; Squad::path holds the rest, ending at the squad's slot.
+
+use bevy::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::constants::*;
+use crate::terrain::TerrainHeightmap;
+use crate::terrain_decor::Rock;
+use crate::types::*;
+
+/// Nav grid upkeep and waypoint following
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGrid>()
+            // Rebuilt before commands run so orders always route on the current grid
+            .add_systems(Update, rebuild_nav_grid.before(crate::game_command::execute_game_commands))
+            .add_systems(FixedUpdate, advance_squad_waypoints
+                .after(crate::formation::squad_formation_system)
+                .before(crate::movement::animate_march));
+    }
+}
+
+const NEIGHBORS: [(i32, i32); 8] = [
+    (1, 0), (-1, 0), (0, 1), (0, -1),
+    (1, 1), (1, -1), (-1, 1), (-1, -1),
+];
+
+/// Traversal cost per cell (f32::INFINITY = blocked)
+#[derive(Resource, Default)]
+pub struct NavGrid {
+    /// Cells per side (0 until the first build)
+    pub width: usize,
+    pub cell_size: f32,
+    pub half_size: f32,
+    pub costs: Vec<f32>,
+}
+
+/// Cost to reach the goal from every cell
+pub struct FlowField {
+    goal: usize,
+    integration: Vec<f32>,
+}
+
+impl NavGrid {
+    /// Build from the terrain plus circular obstacles (position, radius)
+    pub fn build(heightmap: &TerrainHeightmap, obstacles: &[(Vec3, f32)]) -> Self {
+        let width = ((heightmap.terrain_size / NAV_CELL_SIZE).ceil() as usize).max(1);
+        let cell_size = heightmap.terrain_size / width as f32;
+        let half_size = heightmap.terrain_size / 2.0;
+        let mut grid = Self { width, cell_size, half_size, costs: vec![1.0; width * width] };
+
+        // Slope cost
+        for index in 0..grid.costs.len() {
+            let center = grid.cell_center(index);
+            let dx = heightmap.sample_height(center.x + cell_size, center.z)
+                - heightmap.sample_height(center.x - cell_size, center.z);
+            let dz = heightmap.sample_height(center.x, center.z + cell_size)
+                - heightmap.sample_height(center.x, center.z - cell_size);
+            let slope = dx.abs().max(dz.abs()) / (2.0 * cell_size);
+
+            grid.costs[index] = if slope > NAV_MAX_SLOPE {
+                f32::INFINITY
+            } else {
+                1.0 + slope * NAV_SLOPE_COST
+            };
+        }
+
+        // Static obstacles
+        for &(position, radius) in obstacles {
+            let reach = radius + NAV_OBSTACLE_MARGIN;
+            let (min_x, min_z) = grid.cell_coords(position - Vec3::new(reach, 0.0, reach));
+            let (max_x, max_z) = grid.cell_coords(position + Vec3::new(reach, 0.0, reach));
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    let index = z * width + x;
+                    let center = grid.cell_center(index);
+                    let dx = center.x - position.x;
+                    let dz = center.z - position.z;
+                    if dx * dx + dz * dz <= reach * reach {
+                        grid.costs[index] = f32::INFINITY;
+                    }
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn is_built(&self) -> bool {
+        self.width > 0
+    }
+
+    /// Grid coordinates of a world position (clamped to the grid)
+    fn cell_coords(&self, position: Vec3) -> (usize, usize) {
+        let max = (self.width - 1) as f32;
+        let x = ((position.x + self.half_size) / self.cell_size).floor().clamp(0.0, max);
+        let z = ((position.z + self.half_size) / self.cell_size).floor().clamp(0.0, max);
+        (x as usize, z as usize)
+    }
+
+    fn cell_index(&self, position: Vec3) -> usize {
+        let (x, z) = self.cell_coords(position);
+        z * self.width + x
+    }
+
+    fn cell_center(&self, index: usize) -> Vec3 {
+        let x = (index % self.width) as f32;
+        let z = (index / self.width) as f32;
+        Vec3::new(
+            (x + 0.5) * self.cell_size - self.half_size,
+            0.0,
+            (z + 0.5) * self.cell_size - self.half_size,
+        )
+    }
+
+    fn is_walkable(&self, index: usize) -> bool {
+        self.costs[index].is_finite()
+    }
+
+    /// Neighbors of a cell with the distance to them (in cells).
+    /// Diagonals can't cut the corner of a blocked cell.
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = (usize, f32)> + '_ {
+        let x = (index % self.width) as i32;
+        let z = (index / self.width) as i32;
+        let width = self.width as i32;
+
+        NEIGHBORS.iter().filter_map(move |&(dx, dz)| {
+            let (nx, nz) = (x + dx, z + dz);
+            if nx < 0 || nz < 0 || nx >= width || nz >= width {
+                return None;
+            }
+            let neighbor = (nz * width + nx) as usize;
+            if !self.is_walkable(neighbor) {
+                return None;
+            }
+            if dx != 0 && dz != 0 {
+                let side_a = (z * width + nx) as usize;
+                let side_b = (nz * width + x) as usize;
+                if !self.is_walkable(side_a) || !self.is_walkable(side_b) {
+                    return None;
+                }
+                Some((neighbor, std::f32::consts::SQRT_2))
+            } else {
+                Some((neighbor, 1.0))
+            }
+        })
+    }
+
+    /// Closest walkable cell to `index` (itself if walkable)
+    fn nearest_walkable(&self, index: usize) -> Option<usize> {
+        if self.is_walkable(index) {
+            return Some(index);
+        }
+        let x = (index % self.width) as i32;
+        let z = (index / self.width) as i32;
+        let width = self.width as i32;
+
+        // Search outward ring by ring
+        for ring in 1..width {
+            let mut best: Option<(usize, i32)> = None;
+            for dz in -ring..=ring {
+                for dx in -ring..=ring {
+                    if dx.abs() != ring && dz.abs() != ring {
+                        continue;
+                    }
+                    let (nx, nz) = (x + dx, z + dz);
+                    if nx < 0 || nz < 0 || nx >= width || nz >= width {
+                        continue;
+                    }
+                    let neighbor = (nz * width + nx) as usize;
+                    let distance = dx * dx + dz * dz;
+                    if self.is_walkable(neighbor) && best.map_or(true, |(_, d)| distance < d) {
+                        best = Some((neighbor, distance));
+                    }
+                }
+            }
+            if let Some((cell, _)) = best {
+                return Some(cell);
+            }
+        }
+        None
+    }
+
+    /// Integration field toward `goal` (snapped to the nearest walkable cell)
+    pub fn flow_field(&self, goal: Vec3) -> Option<FlowField> {
+        if !self.is_built() {
+            return None;
+        }
+        let goal = self.nearest_walkable(self.cell_index(goal))?;
+
+        let mut integration = vec![f32::INFINITY; self.costs.len()];
+        let mut open = BinaryHeap::new();
+        integration[goal] = 0.0;
+        open.push(OpenCell { cost: 0.0, index: goal });
+
+        while let Some(OpenCell { cost, index }) = open.pop() {
+            if cost > integration[index] {
+                continue;
+            }
+            for (neighbor, distance) in self.neighbors(index) {
+                let step = distance * self.cell_size * (self.costs[index] + self.costs[neighbor]) * 0.5;
+                let next_cost = cost + step;
+                if next_cost < integration[neighbor] {
+                    integration[neighbor] = next_cost;
+                    open.push(OpenCell { cost: next_cost, index: neighbor });
+                }
+            }
+        }
+
+        Some(FlowField { goal, integration })
+    }
+
+    /// Waypoints from `start` to `end` following the flow field, ending with `end` itself.
+    /// Falls back to a straight line when `start` can't reach the field's goal.
+    pub fn route(&self, field: &FlowField, start: Vec3, end: Vec3) -> Vec<Vec3> {
+        let Some(mut cell) = self.nearest_walkable(self.cell_index(start)) else {
+            return vec![end];
+        };
+        if !field.integration[cell].is_finite() {
+            return vec![end];
+        }
+
+        // Walk downhill through the integration field
+        let mut cells = vec![cell];
+        while cell != field.goal && cells.len() < self.costs.len() {
+            let next = self.neighbors(cell)
+                .map(|(neighbor, _)| neighbor)
+                .min_by(|&a, &b| field.integration[a].total_cmp(&field.integration[b]).then(a.cmp(&b)));
+            match next {
+                Some(next) if field.integration[next] < field.integration[cell] => {
+                    cell = next;
+                    cells.push(cell);
+                }
+                _ => break,
+            }
+        }
+
+        // String-pull: skip ahead to the furthest cell the straight line reaches without
+        // costing noticeably more than the path (so shortcuts don't climb hills)
+        let mut waypoints = Vec::new();
+        let mut anchor = 0;
+        while anchor + 1 < cells.len() {
+            let from = self.cell_center(cells[anchor]);
+            let mut furthest = anchor + 1;
+            for candidate in anchor + 2..cells.len() {
+                let path_cost = field.integration[cells[anchor]] - field.integration[cells[candidate]];
+                let line_cost = self.segment_cost(from, self.cell_center(cells[candidate]));
+                if line_cost > path_cost * NAV_SMOOTHING_TOLERANCE + self.cell_size {
+                    break;
+                }
+                furthest = candidate;
+            }
+            waypoints.push(self.cell_center(cells[furthest]));
+            anchor = furthest;
+        }
+
+        // The last waypoint is the goal cell; head straight for the squad's own slot instead
+        // when nothing blocks the way there
+        if waypoints.last().is_some() {
+            let previous = waypoints.len().checked_sub(2).map_or(start, |i| waypoints[i]);
+            if self.segment_cost(previous, end).is_finite() {
+                waypoints.pop();
+            }
+        }
+        waypoints.push(end);
+        waypoints
+    }
+
+    /// Cost of walking a straight line (infinite if it crosses a blocked cell)
+    fn segment_cost(&self, from: Vec3, to: Vec3) -> f32 {
+        let delta = Vec3::new(to.x - from.x, 0.0, to.z - from.z);
+        let length = delta.length();
+        let steps = ((length / (self.cell_size * 0.5)).ceil() as usize).max(1);
+        let step_length = length / steps as f32;
+
+        let mut cost = 0.0;
+        for step in 0..steps {
+            let point = from + delta * ((step as f32 + 0.5) / steps as f32);
+            cost += self.costs[self.cell_index(point)] * step_length;
+        }
+        cost
+    }
+}
+
+/// Min-heap entry for the flow field search
+#[derive(PartialEq)]
+struct OpenCell {
+    cost: f32,
+    index: usize,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed: BinaryHeap pops the cheapest cell first
+        other.cost.total_cmp(&self.cost).then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ===== SYSTEMS =====
+
+/// Rebuild the grid when the terrain or the static obstacles change
+pub fn rebuild_nav_grid(
+    mut nav_grid: ResMut<NavGrid>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+    rock_query: Query<&Transform, With<Rock>>,
+    building_query: Query<(&Transform, &BuildingCollider)>,
+    added_rocks: Query<(), Added<Rock>>,
+    added_buildings: Query<(), Added<BuildingCollider>>,
+    mut removed_rocks: RemovedComponents<Rock>,
+    mut removed_buildings: RemovedComponents<BuildingCollider>,
+) {
+    let Some(heightmap) = heightmap else { return };
+
+    let rocks_removed = removed_rocks.read().count() > 0;
+    let buildings_removed = removed_buildings.read().count() > 0;
+    let dirty = !nav_grid.is_built()
+        || heightmap.is_changed()
+        || !added_rocks.is_empty()
+        || !added_buildings.is_empty()
+        || rocks_removed
+        || buildings_removed;
+    if !dirty {
+        return;
+    }
+
+    // Rock meshes are unit spheres scaled per rock
+    let obstacles: Vec<(Vec3, f32)> = rock_query.iter()
+        .map(|transform| (transform.translation, transform.scale.x))
+        .chain(building_query.iter().map(|(transform, collider)| (transform.translation, collider.radius)))
+        .collect();
+
+    *nav_grid = NavGrid::build(&heightmap, &obstacles);
+    let blocked = nav_grid.costs.iter().filter(|cost| !cost.is_finite()).count();
+    info!("Nav grid rebuilt: {}x{} cells, {} blocked, {} obstacles", nav_grid.width, nav_grid.width, blocked, obstacles.len());
+}
+
+/// Move squads on to their next waypoint once they reach the current one
+pub fn advance_squad_waypoints(
+    mut squad_manager: ResMut<SquadManager>,
+    heightmap: Option<Res<TerrainHeightmap>>,
+    mut droid_query: Query<(&mut BattleDroid, &SquadMember, &FormationOffset)>,
+) {
+    let mut advanced: Vec<u32> = Vec::new();
+    for (squad_id, squad) in squad_manager.squads.iter_mut() {
+        let dx = squad.center_position.x - squad.target_position.x;
+        let dz = squad.center_position.z - squad.target_position.z;
+        if squad.path.is_empty() || (dx * dx + dz * dz).sqrt() > NAV_WAYPOINT_RADIUS {
+            continue;
+        }
+        if let Some(next) = squad.path.pop_front() {
+            squad.target_position = next;
+            advanced.push(*squad_id);
+        }
+    }
+    if advanced.is_empty() {
+        return;
+    }
+
+    for (mut droid, squad_member, formation_offset) in droid_query.iter_mut() {
+        if droid.returning_to_spawn || !advanced.contains(&squad_member.squad_id) {
+            continue;
+        }
+        let Some(squad) = squad_manager.get_squad(squad_member.squad_id) else { continue };
+
+        let target_xz = squad.target_position + formation_offset.local_offset;
+        let target_y = heightmap.as_ref()
+            .map(|hm| hm.sample_height(target_xz.x, target_xz.z))
+            .unwrap_or(droid.target_position.y);
+        droid.target_position = Vec3::new(target_xz.x, target_y, target_xz.z);
+    }
+}
@@ -11,6 +11,34 @@ use crate::constants::*;
 use crate::procedural_meshes::*;
 use crate::shield::{spawn_shield, ShieldMaterial, ShieldConfig, Shield, DestroyedShield};
 
+/// Objective simulation: shield state, tower destruction and the win condition
+/// Shield/tower visuals and health bars are registered separately (render-only)
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameState::default())
+            .init_resource::<WinConditions>()
+            .add_event::<crate::turrets::TurretDestroyedEvent>()
+            .add_event::<crate::explosion_system::ExplosionEvent>()
+            .init_resource::<ShieldConfig>()
+            .add_systems(FixedUpdate, (
+                crate::shield::shield_destruction_check_system, // Handles hitscan shield destruction
+                crate::shield::shield_regeneration_system,
+                crate::shield::shield_impact_flash_system,
+                crate::shield::shield_tower_death_system,
+                crate::shield::shield_respawn_system,
+            ).before(crate::combat::collision_detection_system))
+            .add_systems(FixedUpdate, (
+                tower_destruction_system,
+                // Despawns here decide battles once towers no longer end them (VFX follow in Update)
+                crate::turrets::turret_destruction_system,
+                crate::explosion_system::resolve_pending_explosions_system,
+                win_condition_system,
+            ).chain());
+    }
+}
+
 // ===== TOWER CREATION =====
 
 pub fn spawn_uplink_towers(
@@ -19,103 +47,81 @@ pub fn spawn_uplink_towers(
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut shield_materials: ResMut<Assets<ShieldMaterial>>,
     shield_config: Res<ShieldConfig>,
+    diplomacy: Res<Diplomacy>,
 ) {
-    let tower_mesh = create_uplink_tower_mesh(&mut meshes);
-    
-    // Team A tower material (blue/cyan sci-fi glow)
-    let team_a_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.2, 0.6, 0.9),
-        emissive: Color::srgb(0.1, 0.3, 0.6).into(),
-        metallic: 0.8,
-        perceptual_roughness: 0.2,
-        ..default()
-    });
-    
-    // Team B tower material (red/orange sci-fi glow)
-    let team_b_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.3, 0.2),
-        emissive: Color::srgb(0.6, 0.2, 0.1).into(),
-        metallic: 0.8,
-        perceptual_roughness: 0.2,
-        ..default()
-    });
-    
-    // Spawn Team A tower (left side, behind army)
-    let team_a_pos = Vec3::new(-BATTLEFIELD_SIZE / 2.0 - 30.0, 0.0, 0.0);
-    commands.spawn((
-        Mesh3d(tower_mesh.clone()),
-        MeshMaterial3d(team_a_material),
-        Transform::from_translation(team_a_pos)
-            .with_scale(Vec3::splat(1.0)),
-        UplinkTower {
-            team: Team::A,
-            destruction_radius: TOWER_DESTRUCTION_RADIUS,
-        },
-        ObjectiveTarget {
-            team: Team::A,
-            is_primary: true,
-        },
-        Health::new(TOWER_MAX_HEALTH),
-        crate::types::BuildingCollider { radius: 5.0 }, // Collision radius for laser blocking
-    ));
+    let team_count = diplomacy.teams.len();
 
-    // Spawn shield for Team A tower
-    spawn_shield(
-        &mut commands,
-        &mut meshes,
-        &mut shield_materials,
-        team_a_pos,
-        50.0, // Shield radius (covers tower and surrounding area)
-        Team::A.shield_color(),
-        Team::A,
-        &shield_config,
-    );
-
-    // Spawn Team B tower (right side, behind army)
-    let team_b_pos = Vec3::new(BATTLEFIELD_SIZE / 2.0 + 30.0, 0.0, 0.0);
+    for &team in &diplomacy.teams {
+        // Each tower stands behind its team's army
+        let tower_pos = team.home_position(team_count) + team.home_direction() * 30.0;
+        spawn_uplink_tower(&mut commands, &mut meshes, &mut materials, team, tower_pos);
+
+        spawn_shield(
+            &mut commands,
+            &mut meshes,
+            &mut shield_materials,
+            tower_pos,
+            50.0, // Shield radius (covers tower and surrounding area)
+            team.shield_color(),
+            team,
+            &shield_config,
+        );
+    }
+
+    info!("Spawned Uplink Towers with shields for {} teams", team_count);
+}
+
+/// Spawn a single uplink tower (primary objective) for a team
+/// Returns the tower entity
+pub fn spawn_uplink_tower(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    team: Team,
+    position: Vec3,
+) -> Entity {
     commands.spawn((
-        Mesh3d(tower_mesh),
-        MeshMaterial3d(team_b_material),
-        Transform::from_translation(team_b_pos)
+        Mesh3d(create_uplink_tower_mesh(meshes)),
+        MeshMaterial3d(create_tower_material(materials, team)),
+        Transform::from_translation(position)
             .with_scale(Vec3::splat(1.0)),
         UplinkTower {
-            team: Team::B,
+            team,
             destruction_radius: TOWER_DESTRUCTION_RADIUS,
         },
         ObjectiveTarget {
-            team: Team::B,
+            team,
             is_primary: true,
         },
         Health::new(TOWER_MAX_HEALTH),
+        ArmorClass::Structure,
         crate::types::BuildingCollider { radius: 5.0 }, // Collision radius for laser blocking
-    ));
+    )).id()
+}
 
-    // Spawn shield for Team B tower
-    spawn_shield(
-        &mut commands,
-        &mut meshes,
-        &mut shield_materials,
-        team_b_pos,
-        50.0, // Shield radius (covers tower and surrounding area)
-        Team::B.shield_color(),
-        Team::B,
-        &shield_config,
-    );
-
-    info!("Spawned Uplink Towers with shields for both teams");
+/// Sci-fi glow material for a team's uplink tower
+pub fn create_tower_material(materials: &mut Assets<StandardMaterial>, team: Team) -> Handle<StandardMaterial> {
+    materials.add(StandardMaterial {
+        base_color: team.palette().tower,
+        emissive: team.palette().tower_emissive.into(),
+        metallic: 0.8,
+        perceptual_roughness: 0.2,
+        ..default()
+    })
 }
 
 // ===== TOWER TARGETING & DAMAGE =====
 
 pub fn tower_targeting_system(
-    mut tower_query: Query<(&Transform, &mut Health, &UplinkTower), With<UplinkTower>>,
+    mut tower_query: Query<(&Transform, &mut Health, &UplinkTower, &ArmorClass), With<UplinkTower>>,
     laser_query: Query<(&Transform, &LaserProjectile), With<LaserProjectile>>,
+    diplomacy: Res<Diplomacy>,
     _commands: Commands,
 ) {
-    for (tower_transform, mut tower_health, tower) in tower_query.iter_mut() {
+    for (tower_transform, mut tower_health, tower, armor) in tower_query.iter_mut() {
         for (laser_transform, laser_projectile) in laser_query.iter() {
             // Only enemy lasers can damage towers
-            if laser_projectile.team == tower.team {
+            if diplomacy.is_allied(laser_projectile.team, tower.team) {
                 continue;
             }
             
@@ -123,7 +129,7 @@ pub fn tower_targeting_system(
             
             // Tower collision detection (larger collision radius due to size)
             if distance < TOWER_BASE_WIDTH {
-                tower_health.damage(25.0); // Moderate damage per laser hit
+                tower_health.damage(laser_projectile.weapon.damage_against(laser_projectile.damage, *armor));
                 
                 // TODO: Add hit effect/particle system here
                 
@@ -140,20 +146,31 @@ pub fn tower_targeting_system(
 
 pub fn tower_destruction_system(
     mut commands: Commands,
-    tower_query: Query<(Entity, &Transform, &UplinkTower, &Health), (With<UplinkTower>, Without<PendingExplosion>)>,
+    tower_query: Query<(Entity, &Transform, &UplinkTower, &Health, Option<&ObjectiveTarget>), (With<UplinkTower>, Without<PendingExplosion>)>,
     droid_query: Query<(Entity, &Transform, &BattleDroid), With<BattleDroid>>,
     particle_effects: Option<Res<crate::particles::ExplosionParticleEffects>>,
     time: Res<Time>,
     mut game_state: ResMut<GameState>,
+    diplomacy: Res<Diplomacy>,
 ) {
     let current_time = time.elapsed_secs_f64();
 
-    for (tower_entity, tower_transform, tower, tower_health) in tower_query.iter() {
+    for (tower_entity, tower_transform, tower, tower_health, objective) in tower_query.iter() {
         if tower_health.is_dead() {
             info!("Processing tower destruction for team {:?}", tower.team);
 
-            // Mark game as ended
-            game_state.tower_destroyed(tower.team);
+            if objective.map_or(true, |objective| objective.is_primary) {
+                // win_condition_system ends the game once only one alliance still has a tower
+                game_state.tower_destroyed(tower.team);
+            } else {
+                // Secondary objectives only score victory points for the other side
+                for &team in &diplomacy.teams {
+                    if diplomacy.is_hostile(team, tower.team) {
+                        game_state.points[team.index()] += SECONDARY_OBJECTIVE_POINTS;
+                    }
+                }
+                info!("Secondary objective of team {:?} destroyed", tower.team);
+            }
 
             // Find and despawn all friendly units within destruction radius
             // Spawn a death flash at each unit position
@@ -211,19 +228,77 @@ pub fn tower_destruction_system(
 
 // Explosion systems moved to src/explosion_system.rs
 // Re-export for backwards compatibility
-pub use crate::explosion_system::{pending_explosion_system, explosion_effect_system, PendingExplosion};
+pub use crate::explosion_system::{pending_explosion_system, explosion_effect_system, resolve_pending_explosions_system, PendingExplosion};
 
 // ===== WIN CONDITION SYSTEM =====
 
+/// End the battle by towers destroyed, victory points or time limit (see WinConditions).
+/// Scenarios decide their own points and time rules (victory_defeat_check_system).
 pub fn win_condition_system(
-    game_state: Res<GameState>,
+    mut game_state: ResMut<GameState>,
+    diplomacy: Res<Diplomacy>,
+    win_conditions: Res<WinConditions>,
+    time: Res<Time>,
+    scenario_state: Option<Res<crate::scenario::ScenarioState>>,
 ) {
-    // Only log the victory message when the state first changes
-    if game_state.game_ended && game_state.is_changed() {
-        if let Some(winner) = game_state.winner {
-            info!("🎉 VICTORY! Team {:?} wins the battle! 🎉", winner);
-            // TODO: Display victory screen, stop unit AI, etc.
+    if game_state.game_ended {
+        return;
+    }
+    game_state.battle_time += time.delta_secs();
+
+    let in_scenario = scenario_state.is_some_and(|state| state.active);
+    // Strongest alliance by points, by its first team in Diplomacy order
+    let points_leader = || diplomacy.teams.iter()
+        .copied()
+        .map(|team| (team, game_state.alliance_points(team, &diplomacy)))
+        .fold(None, |best: Option<(Team, f32)>, (team, points)| match best {
+            Some((_, best_points)) if best_points >= points => best,
+            _ => Some((team, points)),
+        });
+
+    let result = if let Some(winner) = game_state.last_alliance_standing(&diplomacy).filter(|_| win_conditions.towers) {
+        Some((winner, "enemy towers destroyed".to_string()))
+    } else if in_scenario {
+        None
+    } else if let Some((team, points)) = points_leader()
+        .filter(|&(_, points)| win_conditions.points_to_win.is_some_and(|target| points >= target))
+    {
+        Some((Some(team), format!("reached {:.0} points", points)))
+    } else if win_conditions.time_limit.is_some_and(|limit| game_state.battle_time >= limit) {
+        // Most points wins; a tie on points goes to the side with more towers standing
+        let score = |team: Team| {
+            let towers = diplomacy.teams.iter()
+                .filter(|&&other| diplomacy.is_allied(other, team) && !game_state.destroyed_towers.contains(&other))
+                .count();
+            (game_state.alliance_points(team, &diplomacy), towers)
+        };
+        let best = diplomacy.teams.iter().copied().map(score).fold((f32::MIN, 0), |best, s| {
+            if s.0 > best.0 || (s.0 == best.0 && s.1 > best.1) { s } else { best }
+        });
+        let leaders: Vec<Team> = diplomacy.teams.iter().copied().filter(|&team| score(team) == best).collect();
+        let winner = leaders.first().copied()
+            .filter(|&first| leaders.iter().all(|&team| diplomacy.is_allied(team, first)));
+        Some((winner, "time limit reached".to_string()))
+    } else {
+        None
+    };
+
+    let Some((winner, reason)) = result else { return };
+    game_state.end(winner, reason.clone());
+
+    match winner {
+        Some(winner) => {
+            let allies: Vec<Team> = diplomacy.teams.iter()
+                .copied()
+                .filter(|&team| team != winner && diplomacy.is_allied(team, winner))
+                .collect();
+            if allies.is_empty() {
+                info!("🎉 VICTORY! Team {:?} wins the battle ({})! 🎉", winner, reason);
+            } else {
+                info!("🎉 VICTORY! Team {:?} and allies {:?} win the battle ({})! 🎉", winner, allies, reason);
+            }
         }
+        None => info!("The battle ends in a draw ({})", reason),
     }
 }
 
@@ -256,16 +331,17 @@ pub fn spawn_debug_mode_ui(mut commands: Commands) {
 pub fn debug_explosion_hotkey_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut tower_query: Query<(&UplinkTower, &mut Health), With<UplinkTower>>,
+    diplomacy: Res<Diplomacy>,
 ) {
     if keyboard_input.just_pressed(KeyCode::KeyE) {
-        info!("🔥 DEBUG: Explosion hotkey pressed! Setting Team B tower health to 0...");
+        info!("🔥 DEBUG: Explosion hotkey pressed! Setting an enemy tower's health to 0...");
 
-        // Find Team B tower and set health to 0
+        // Find a tower hostile to the player (Team A) and set health to 0
         // tower_destruction_system will handle the rest
         for (tower, mut tower_health) in tower_query.iter_mut() {
-            if tower.team == Team::B {
+            if diplomacy.is_hostile(Team::A, tower.team) && !tower_health.is_dead() {
                 tower_health.current = 0.0;
-                info!("🔥 DEBUG: Team B tower health set to 0");
+                info!("🔥 DEBUG: Team {:?} tower health set to 0", tower.team);
                 break;
             }
         }
@@ -593,14 +669,7 @@ pub fn debug_spawn_shield_system(
         // Create tower mesh (same as spawn_uplink_towers)
         let tower_mesh = create_uplink_tower_mesh(&mut meshes);
 
-        // Team B tower material (red/orange sci-fi glow)
-        let tower_material = standard_materials.add(StandardMaterial {
-            base_color: Color::srgb(0.9, 0.3, 0.2),
-            emissive: Color::srgb(0.6, 0.2, 0.1).into(),
-            metallic: 0.8,
-            perceptual_roughness: 0.2,
-            ..default()
-        });
+        let tower_material = create_tower_material(&mut standard_materials, team);
 
         // Spawn the tower
         commands.spawn((
@@ -616,6 +685,7 @@ pub fn debug_spawn_shield_system(
                 is_primary: false, // Debug tower is not primary objective
             },
             Health::new(crate::constants::TOWER_MAX_HEALTH),
+            ArmorClass::Structure,
             crate::types::BuildingCollider { radius: 5.0 },
         ));
 
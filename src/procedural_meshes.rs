@@ -4,6 +4,8 @@
 //! - Uplink towers
 //! - Heavy turrets (base and rotating assembly)
 //! - MG turrets (base and rotating assembly)
+//! - Heavy walkers (unit mesh, see archetype::UnitMesh)
+//! - Veterancy rank chevrons (shown above squad commanders)
 
 use bevy::prelude::*;
 use bevy::render::mesh::{Indices, PrimitiveTopology};
@@ -1168,3 +1170,312 @@ pub fn create_mg_turret_barrel_mesh(meshes: &mut ResMut<Assets<Mesh>>) -> Handle
 
     meshes.add(mesh)
 }
+
+// ============================================================================
+// WALKER MESH
+// ============================================================================
+
+/// Create procedural mesh for the heavy walker (two legs, armored hull, turret gun)
+/// Origin is at the feet and the gun points along +Z, the direction units march in
+pub fn create_walker_mesh(meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD);
+
+    let mut vertices = Vec::new();
+    let mut normals = Vec::new();
+    let mut indices = Vec::new();
+
+    // Helper: Add Box with sharp edges
+    fn add_box(
+        vertices: &mut Vec<[f32; 3]>,
+        normals: &mut Vec<[f32; 3]>,
+        indices: &mut Vec<u32>,
+        center: Vec3,
+        size: Vec3,
+    ) {
+        let hw = size.x / 2.0;
+        let hh = size.y / 2.0;
+        let hd = size.z / 2.0;
+
+        let raw_verts = [
+            [center.x - hw, center.y - hh, center.z - hd],
+            [center.x + hw, center.y - hh, center.z - hd],
+            [center.x + hw, center.y - hh, center.z + hd],
+            [center.x - hw, center.y - hh, center.z + hd],
+            [center.x - hw, center.y + hh, center.z - hd],
+            [center.x + hw, center.y + hh, center.z - hd],
+            [center.x + hw, center.y + hh, center.z + hd],
+            [center.x - hw, center.y + hh, center.z + hd],
+        ];
+
+        // Corrected winding for all faces (CCW)
+        let faces = [
+            ([0.0, -1.0, 0.0], [0, 1, 2, 3]), // Bottom
+            ([0.0, 1.0, 0.0], [4, 7, 6, 5]),  // Top - FLIPPED
+            ([-1.0, 0.0, 0.0], [0, 3, 7, 4]), // Left
+            ([1.0, 0.0, 0.0], [1, 5, 6, 2]),  // Right
+            ([0.0, 0.0, -1.0], [0, 4, 5, 1]), // Back
+            ([0.0, 0.0, 1.0], [3, 2, 6, 7]),  // Front
+        ];
+
+        for (normal, vert_indices) in faces {
+            let face_base = vertices.len() as u32;
+            for &idx in &vert_indices {
+                vertices.push(raw_verts[idx]);
+                normals.push(normal);
+            }
+            indices.push(face_base); indices.push(face_base + 1); indices.push(face_base + 2);
+            indices.push(face_base); indices.push(face_base + 2); indices.push(face_base + 3);
+        }
+    }
+
+    // Helper: Add Cylinder (Y-aligned) with flat shading for caps
+    fn add_cylinder(
+        vertices: &mut Vec<[f32; 3]>,
+        normals: &mut Vec<[f32; 3]>,
+        indices: &mut Vec<u32>,
+        center: Vec3,
+        radius: f32,
+        height: f32,
+        segments: u32,
+    ) {
+        let half_height = height / 2.0;
+
+        // 1. Side Faces
+        let side_base = vertices.len() as u32;
+        for i in 0..segments {
+            let angle = (i as f32 / segments as f32) * 2.0 * PI;
+            let n = [angle.cos(), 0.0, angle.sin()];
+
+            // Bottom ring vertex
+            vertices.push([center.x + n[0] * radius, center.y - half_height, center.z + n[2] * radius]);
+            normals.push(n);
+
+            // Top ring vertex
+            vertices.push([center.x + n[0] * radius, center.y + half_height, center.z + n[2] * radius]);
+            normals.push(n);
+        }
+
+        for i in 0..segments {
+            let next = (i + 1) % segments;
+            let b = side_base + i * 2;
+            let t = side_base + i * 2 + 1;
+            let bn = side_base + next * 2;
+            let tn = side_base + next * 2 + 1;
+
+            // Fixed winding order (CCW) for Y-Axis Cylinder
+            indices.push(b); indices.push(t); indices.push(tn);
+            indices.push(b); indices.push(tn); indices.push(bn);
+        }
+
+        // 2. Bottom Cap
+        let bot_center_idx = vertices.len() as u32;
+        vertices.push([center.x, center.y - half_height, center.z]);
+        normals.push([0.0, -1.0, 0.0]);
+
+        let bot_ring_start = vertices.len() as u32;
+        for i in 0..segments {
+            let angle = (i as f32 / segments as f32) * 2.0 * PI;
+            let x = angle.cos() * radius;
+            let z = angle.sin() * radius;
+            vertices.push([center.x + x, center.y - half_height, center.z + z]);
+            normals.push([0.0, -1.0, 0.0]);
+        }
+
+        for i in 0..segments {
+            let next = (i + 1) % segments;
+            indices.push(bot_center_idx);
+            indices.push(bot_ring_start + next);
+            indices.push(bot_ring_start + i);
+        }
+
+        // 3. Top Cap
+        let top_center_idx = vertices.len() as u32;
+        vertices.push([center.x, center.y + half_height, center.z]);
+        normals.push([0.0, 1.0, 0.0]);
+
+        let top_ring_start = vertices.len() as u32;
+        for i in 0..segments {
+            let angle = (i as f32 / segments as f32) * 2.0 * PI;
+            let x = angle.cos() * radius;
+            let z = angle.sin() * radius;
+            vertices.push([center.x + x, center.y + half_height, center.z + z]);
+            normals.push([0.0, 1.0, 0.0]);
+        }
+
+        for i in 0..segments {
+            let next = (i + 1) % segments;
+            indices.push(top_center_idx);
+            // FLIPPED winding for Top Cap to be visible (+Y)
+            indices.push(top_ring_start + next);
+            indices.push(top_ring_start + i);
+        }
+    }
+
+    // Helper: Add Cylinder (Z-aligned)
+    fn add_z_cylinder(
+        vertices: &mut Vec<[f32; 3]>,
+        normals: &mut Vec<[f32; 3]>,
+        indices: &mut Vec<u32>,
+        center: Vec3,
+        radius: f32,
+        length: f32,
+        segments: u32,
+    ) {
+        let base = vertices.len() as u32;
+        let half_len = length / 2.0;
+
+        for i in 0..segments {
+            let angle = (i as f32 / segments as f32) * 2.0 * PI;
+            let n = [angle.cos(), angle.sin(), 0.0];
+
+            vertices.push([center.x + n[0] * radius, center.y + n[1] * radius, center.z - half_len]);
+            normals.push(n);
+            vertices.push([center.x + n[0] * radius, center.y + n[1] * radius, center.z + half_len]);
+            normals.push(n);
+        }
+
+        for i in 0..segments {
+            let next = (i + 1) % segments;
+            let b = base + i * 2;
+            let t = base + i * 2 + 1;
+            let bn = base + next * 2;
+            let tn = base + next * 2 + 1;
+
+            // Reverted winding order (b, tn, t) for Outward faces
+            indices.push(b); indices.push(tn); indices.push(t);
+            indices.push(b); indices.push(bn); indices.push(tn);
+        }
+
+        // 2. Back Cap
+        let back_center_idx = vertices.len() as u32;
+        vertices.push([center.x, center.y, center.z - half_len]);
+        normals.push([0.0, 0.0, -1.0]);
+
+        let back_ring_start = vertices.len() as u32;
+        for i in 0..segments {
+            let angle = (i as f32 / segments as f32) * 2.0 * PI;
+            let x = angle.cos() * radius;
+            let y = angle.sin() * radius;
+            vertices.push([center.x + x, center.y + y, center.z - half_len]);
+            normals.push([0.0, 0.0, -1.0]);
+        }
+
+        for i in 0..segments {
+            let next = (i + 1) % segments;
+            indices.push(back_center_idx);
+            indices.push(back_ring_start + next);
+            indices.push(back_ring_start + i);
+        }
+
+        // 3. Front Cap
+        let front_center_idx = vertices.len() as u32;
+        vertices.push([center.x, center.y, center.z + half_len]);
+        normals.push([0.0, 0.0, 1.0]);
+
+        let front_ring_start = vertices.len() as u32;
+        for i in 0..segments {
+            let angle = (i as f32 / segments as f32) * 2.0 * PI;
+            let x = angle.cos() * radius;
+            let y = angle.sin() * radius;
+            vertices.push([center.x + x, center.y + y, center.z + half_len]);
+            normals.push([0.0, 0.0, 1.0]);
+        }
+
+        for i in 0..segments {
+            let next = (i + 1) % segments;
+            indices.push(front_center_idx);
+            indices.push(front_ring_start + i);
+            indices.push(front_ring_start + next);
+        }
+    }
+
+    // 1. Feet (wide pads so it reads as planted)
+    for x in [-1.0, 1.0] {
+        add_box(&mut vertices, &mut normals, &mut indices,
+            Vec3::new(x, 0.15, 0.1),
+            Vec3::new(0.8, 0.3, 1.2));
+    }
+
+    // 2. Legs
+    for x in [-1.0, 1.0] {
+        add_box(&mut vertices, &mut normals, &mut indices,
+            Vec3::new(x, 1.15, 0.0),
+            Vec3::new(0.45, 1.7, 0.55));
+    }
+
+    // 3. Hip bar joining the legs
+    add_box(&mut vertices, &mut normals, &mut indices,
+        Vec3::new(0.0, 2.2, 0.0),
+        Vec3::new(2.6, 0.45, 0.9));
+
+    // 4. Armored hull
+    add_box(&mut vertices, &mut normals, &mut indices,
+        Vec3::new(0.0, 3.0, 0.0),
+        Vec3::new(2.2, 1.2, 2.4));
+
+    // 5. Turret cupola
+    add_cylinder(&mut vertices, &mut normals, &mut indices,
+        Vec3::new(0.0, 3.85, -0.2),
+        0.8, 0.5, 12);
+
+    // 6. Main gun - Extending Forward (+Z)
+    add_z_cylinder(&mut vertices, &mut normals, &mut indices,
+        Vec3::new(0.0, 3.85, 1.6), 0.15, 2.4, 10);
+
+    // 7. Muzzle Brake
+    add_z_cylinder(&mut vertices, &mut normals, &mut indices,
+        Vec3::new(0.0, 3.85, 2.9), 0.25, 0.3, 10);
+
+    // Add UVs
+    let uvs: Vec<[f32; 2]> = (0..vertices.len()).map(|_| [0.5, 0.5]).collect();
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+
+    meshes.add(mesh)
+}
+
+// ============================================================================
+// RANK CHEVRON MESH
+// ============================================================================
+
+/// Create a flat stack of upward-pointing chevrons, one per veterancy rank
+/// Lies in the XY plane (draw it double-sided); origin at the bottom of the lowest chevron
+pub fn create_rank_chevron_mesh(meshes: &mut Assets<Mesh>, rank: u8) -> Handle<Mesh> {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD);
+
+    let mut vertices: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+
+    let half_width = 0.6;
+    let rise = 0.4;      // Tip height above the arm ends
+    let thickness = 0.18;
+    let spacing = 0.3;
+
+    for i in 0..rank {
+        let base_y = i as f32 * spacing;
+
+        // Each arm is a slanted strip from the tip down to one side
+        for side in [-1.0f32, 1.0] {
+            let base = vertices.len() as u32;
+            vertices.push([0.0, base_y + rise + thickness, 0.0]);
+            vertices.push([side * half_width, base_y + thickness, 0.0]);
+            vertices.push([side * half_width, base_y, 0.0]);
+            vertices.push([0.0, base_y + rise, 0.0]);
+            normals.extend([[0.0, 0.0, 1.0]; 4]);
+            indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+    }
+
+    let uvs: Vec<[f32; 2]> = (0..vertices.len()).map(|_| [0.5, 0.5]).collect();
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+
+    meshes.add(mesh)
+}
@@ -0,0 +1,290 @@
+// Replay recording and playback
+//
+// Usage: cargo run --release -- --record battle.ron
+//        cargo run --release -- --replay battle.ron
+//
+// A replay stores the simulation seed, tick rate, teams, win conditions, map and scenario
+// plus every GameCommand, stamped with the SimTick it was executed on. Because all
+// outcome-affecting randomness goes through SimRng and gameplay runs in FixedUpdate,
+// re-issuing the same commands on the same ticks reproduces the battle exactly.
+//
+// Commands carry world positions rather than raw mouse input, so playback does not depend
+// on camera position or window size. Playback advances exactly one tick per frame and
+// disables player input.
+//
+// Switching to a map that starts a new battle (Firebase Delta, debug maps, any map a scenario
+// starts on) restarts the recording. Other Flat/RollingHills switches keep the current units
+// and are recorded in-stream.
+// Debug hotkeys are not recorded. AI commander orders are, so AI battles replay without the AI.
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::CliArgs;
+use crate::game_command::{execute_game_commands, GameCommand, PlayerInputSet};
+use crate::scenario::{map_starts_new_battle, ScenarioLibrary, ScenarioState};
+use crate::terrain::{MapPreset, MapSwitchEvent, TerrainConfig};
+use crate::types::{Diplomacy, SimRng, SimTick, Team, WinConditions};
+
+/// Bumped whenever ReplayFile or GameCommand changes shape
+pub const REPLAY_FORMAT_VERSION: u32 = 4;
+
+/// Records (--record) or plays back (--replay) game commands
+pub struct ReplayPlugin {
+    record: Option<String>,
+    playback: Option<ReplayFile>,
+}
+
+impl ReplayPlugin {
+    /// Build from the command line. A replay being played back overrides the seed, tick
+    /// rate, teams, win conditions and scenario in `args` so the simulation matches the recording.
+    pub fn from_args(args: &mut CliArgs) -> Result<Self, String> {
+        if args.record.is_some() && args.replay.is_some() {
+            return Err("--record and --replay cannot be used together".to_string());
+        }
+
+        let playback = match &args.replay {
+            Some(path) => {
+                let file = ReplayFile::load(path)?;
+                args.seed = Some(file.seed);
+                args.tick_rate = file.tick_rate;
+                args.alliances = file.alliances.clone();
+                args.win_conditions = file.win_conditions.clone();
+                args.scenario = file.scenario.clone();
+                println!("Playing back replay {} ({} commands, map {:?})", path, file.commands.len(), file.map);
+                Some(file)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            record: args.record.clone(),
+            playback,
+        })
+    }
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = &self.record {
+            app.insert_resource(ReplayRecorder::new(path.clone()))
+                .add_systems(PostUpdate, record_replay_commands);
+        }
+
+        if let Some(file) = &self.playback {
+            // One tick per frame: commands land on exactly the tick they were recorded on
+            let timestep = std::time::Duration::from_secs_f64(1.0 / file.tick_rate);
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep))
+                .insert_resource(ReplayPlayback::new(file.clone()))
+                .configure_sets(Update, PlayerInputSet.run_if(player_input_enabled))
+                .add_systems(Startup, request_replay_map)
+                .add_systems(Update, (
+                    start_replay_on_map_switch.after(crate::terrain::handle_map_switch_units),
+                    replay_playback_system.before(execute_game_commands),
+                ));
+        }
+    }
+}
+
+// ===== FILE FORMAT =====
+
+/// A command and the tick it was executed on
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub tick: u64,
+    pub command: GameCommand,
+}
+
+/// On-disk replay (RON)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub version: u32,
+    pub seed: u64,
+    pub tick_rate: f64,
+    /// Alliances taking part (see --teams)
+    pub alliances: Vec<Vec<Team>>,
+    /// How the battle could be won (see --points-to-win, --time-limit, --no-tower-victory)
+    pub win_conditions: WinConditions,
+    /// Map the recorded battle was played on
+    pub map: MapPreset,
+    /// Scenario running on that map, if any (requested again on playback)
+    pub scenario: Option<String>,
+    pub commands: Vec<ReplayEntry>,
+}
+
+impl ReplayFile {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read replay {}: {}", path, e))?;
+        let file: ReplayFile = ron::from_str(&text)
+            .map_err(|e| format!("Failed to parse replay {}: {}", path, e))?;
+
+        if file.version != REPLAY_FORMAT_VERSION {
+            return Err(format!(
+                "Replay {} has format version {}, expected {}",
+                path, file.version, REPLAY_FORMAT_VERSION
+            ));
+        }
+        Ok(file)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| format!("Failed to serialize replay: {}", e))?;
+        std::fs::write(path, text).map_err(|e| format!("Failed to write replay {}: {}", path, e))
+    }
+}
+
+// ===== RECORDING =====
+
+/// Commands recorded for the current battle
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    path: String,
+    map: MapPreset,
+    scenario: Option<String>,
+    commands: Vec<ReplayEntry>,
+    /// Needs writing to disk
+    dirty: bool,
+}
+
+impl ReplayRecorder {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            map: TerrainConfig::default().current_map,
+            scenario: None,
+            commands: Vec::new(),
+            dirty: true,
+        }
+    }
+}
+
+/// Stamp this frame's commands with the current tick and write the replay.
+/// The file is rewritten whenever it changes so it survives the game being killed.
+fn record_replay_commands(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut map_switch_events: EventReader<MapSwitchEvent>,
+    mut game_commands: EventReader<GameCommand>,
+    sim_tick: Res<SimTick>,
+    sim_rng: Res<SimRng>,
+    diplomacy: Res<Diplomacy>,
+    win_conditions: Res<WinConditions>,
+    fixed_time: Res<Time<Fixed>>,
+    (scenario_library, scenario_state): (Option<Res<ScenarioLibrary>>, Option<Res<ScenarioState>>),
+) {
+    // A new battle restarts the recording; anything issued before the switch is moot
+    let new_battle = |map| map_starts_new_battle(map, scenario_library.as_deref(), scenario_state.as_deref());
+    if let Some(event) = map_switch_events.read().filter(|event| new_battle(event.new_map)).last() {
+        info!("Replay recording restarted for map {:?}", event.new_map);
+        recorder.map = event.new_map;
+        // The scenario (if any) was started by scenario_initialization_system earlier this frame
+        recorder.scenario = scenario_state.as_ref().and_then(|state| state.scenario_id.clone());
+        recorder.commands.clear();
+        recorder.dirty = true;
+        game_commands.clear();
+    }
+
+    for command in game_commands.read() {
+        recorder.commands.push(ReplayEntry {
+            tick: sim_tick.0,
+            command: command.clone(),
+        });
+        recorder.dirty = true;
+    }
+
+    if !recorder.dirty {
+        return;
+    }
+    recorder.dirty = false;
+
+    let file = ReplayFile {
+        version: REPLAY_FORMAT_VERSION,
+        seed: sim_rng.seed,
+        tick_rate: 1.0 / fixed_time.timestep().as_secs_f64(),
+        alliances: diplomacy.alliances(),
+        win_conditions: win_conditions.clone(),
+        map: recorder.map,
+        scenario: recorder.scenario.clone(),
+        commands: recorder.commands.clone(),
+    };
+    if let Err(e) = file.save(&recorder.path) {
+        error!("{}", e);
+    }
+}
+
+// ===== PLAYBACK =====
+
+/// Replay being played back
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    file: ReplayFile,
+    /// Index of the next command to apply
+    cursor: usize,
+    /// Whether the recorded map is loaded (commands wait until then)
+    started: bool,
+}
+
+impl ReplayPlayback {
+    fn new(file: ReplayFile) -> Self {
+        let started = file.map == TerrainConfig::default().current_map;
+        Self { file, cursor: 0, started }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.cursor >= self.file.commands.len()
+    }
+}
+
+/// Run condition for player input (and AI): disabled while a replay is playing back.
+/// Control returns to the player once every recorded command has been applied.
+pub fn player_input_enabled(playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.map_or(true, |playback| playback.finished())
+}
+
+/// Load the map the replay was recorded on
+fn request_replay_map(playback: Res<ReplayPlayback>, mut config: ResMut<TerrainConfig>) {
+    if !playback.started {
+        config.requested_map = Some(playback.file.map);
+    }
+}
+
+/// The recorded map has loaded (and reset the tick counter) - start applying commands
+fn start_replay_on_map_switch(
+    mut playback: ResMut<ReplayPlayback>,
+    mut map_switch_events: EventReader<MapSwitchEvent>,
+) {
+    for event in map_switch_events.read() {
+        if !playback.started && event.new_map == playback.file.map {
+            playback.started = true;
+            info!("Replay started on map {:?}", event.new_map);
+        }
+    }
+}
+
+/// Issue every recorded command due on the current tick
+fn replay_playback_system(
+    mut playback: ResMut<ReplayPlayback>,
+    sim_tick: Res<SimTick>,
+    mut game_commands: EventWriter<GameCommand>,
+) {
+    if !playback.started || playback.finished() {
+        return;
+    }
+
+    while let Some(entry) = playback.file.commands.get(playback.cursor) {
+        if entry.tick > sim_tick.0 {
+            break;
+        }
+        if entry.tick < sim_tick.0 {
+            warn!("Replay command for tick {} issued late on tick {}", entry.tick, sim_tick.0);
+        }
+        game_commands.write(entry.command.clone());
+        playback.cursor += 1;
+    }
+
+    if playback.finished() {
+        info!("Replay finished on tick {} - player control restored", sim_tick.0);
+    }
+}
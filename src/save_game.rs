@@ -0,0 +1,839 @@
+// Save and load of a battle in progress
+//
+// F8 writes the battle to savegame.ron, F9 loads it back. The save is a versioned RON file
+// holding the squads (with every member's transform, health, movement mode and formation
+// slot), towers, turrets, shields, capture points, wave progress, scenario triggers, squad
+// groups, credits and production queues, the map, the running scenario and the game state
+// (including victory points).
+//
+// Loading switches to the saved map first when needed and waits for it to finish loading
+// (scenario and debug maps spawn their own units, which the load then replaces). Entities
+// are rebuilt the same way the game spawns them: squads through spawn_single_squad (with
+// their saved ids), turrets through spawn_mg_turret_at / spawn_heavy_turret_at and shields
+// through spawn_shield_with_hp; the saved state is then written over the fresh entities.
+// Towers belong to the map, so the ones standing are matched up and get their saved health;
+// capture points are matched by name and get their saved owner and progress.
+//
+// Units pick new combat targets after a load, and the simulation RNG is not saved, so a
+// loaded battle does not replay the original one shot for shot. For the same reason loading
+// is refused while recording a replay (--record): the load bypasses GameCommand, so the
+// recording could not reproduce it.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::archetype::UnitArchetypes;
+use crate::artillery::ArtilleryState;
+use crate::capture::CapturePoint;
+use crate::economy::Economy;
+use crate::game_command::PlayerInputSet;
+use crate::replay::ReplayRecorder;
+use crate::scenario::{spawn_command_bunker, CommandBunker, ScenarioState, TriggerState, WaveEnemy, WaveManager, WaveState};
+use crate::selection::{SelectionState, SquadGroup};
+use crate::setup::{create_team_materials, spawn_single_squad};
+use crate::shield::{spawn_shield_with_hp, DestroyedShield, Shield, ShieldConfig, ShieldMaterial};
+use crate::terrain::{handle_map_switch_units, MapPreset, MapSwitchEvent, TerrainConfig, TerrainHeightmap};
+use crate::turrets::{spawn_turret_at, TurretType};
+use crate::types::*;
+
+/// Bumped whenever SaveFile changes shape
+pub const SAVE_FORMAT_VERSION: u32 = 5;
+
+/// Where F8/F9 save and load
+pub const QUICKSAVE_PATH: &str = "savegame.ron";
+
+/// F8 saves the battle, F9 loads it (windowed game only)
+pub struct SaveGamePlugin;
+
+impl Plugin for SaveGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveGameRequests>()
+            .add_systems(Update, (
+                save_game_hotkey_system.in_set(PlayerInputSet),
+                write_save_system,
+                read_save_system,
+                apply_loaded_save_system.after(handle_map_switch_units),
+            ).chain());
+    }
+}
+
+// ===== FILE FORMAT =====
+
+/// On-disk save (RON)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub map: MapPreset,
+    /// Scenario running when the battle was saved; it starts again with the map
+    pub scenario: Option<String>,
+    pub game_state: GameState,
+    pub next_squad_id: u32,
+    /// Every squad, including wiped-out ones (they keep their veterancy record)
+    pub squads: Vec<SavedSquad>,
+    pub towers: Vec<SavedTower>,
+    pub turrets: Vec<SavedTurret>,
+    pub shields: Vec<SavedShield>,
+    pub capture_points: Vec<SavedCapturePoint>,
+    pub wave_manager: SavedWaveManager,
+    pub triggers: SavedTriggers,
+    pub groups: Vec<SavedGroup>,
+    pub next_group_id: u32,
+    pub economy: Economy,
+}
+
+impl SaveFile {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read save {}: {}", path, e))?;
+        let file: SaveFile = ron::from_str(&text)
+            .map_err(|e| format!("Failed to parse save {}: {}", path, e))?;
+
+        if file.version != SAVE_FORMAT_VERSION {
+            return Err(format!(
+                "Save {} has format version {}, expected {}",
+                path, file.version, SAVE_FORMAT_VERSION
+            ));
+        }
+        Ok(file)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| format!("Failed to serialize save: {}", e))?;
+        std::fs::write(path, text).map_err(|e| format!("Failed to write save {}: {}", path, e))
+    }
+}
+
+/// A squad and its members
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedSquad {
+    pub id: u32,
+    pub team: Team,
+    pub archetype: String,
+    pub formation_type: FormationType,
+    pub previous_formation: FormationType,
+    pub formation_blend: f32,
+    pub center_position: Vec3,
+    pub facing_direction: Vec3,
+    pub target_facing_direction: Vec3,
+    pub target_position: Vec3,
+    pub path: Vec<Vec3>,
+    pub average_health: f32,
+    pub morale: f32,
+    pub rout_timer: Option<f32>,
+    pub casualties: u32,
+    pub commander_lost: bool,
+    pub suppression: f32,
+    pub kills: u32,
+    /// Tactical wave the squad attacked in (scenario wave enemies)
+    pub wave_number: Option<u32>,
+    pub members: Vec<SavedUnit>,
+}
+
+impl SavedSquad {
+    fn from_squad(squad: &Squad, members: Vec<SavedUnit>, wave_number: Option<u32>) -> Self {
+        Self {
+            id: squad.id,
+            team: squad.team,
+            archetype: squad.archetype.clone(),
+            formation_type: squad.formation_type,
+            previous_formation: squad.previous_formation,
+            formation_blend: squad.formation_blend,
+            center_position: squad.center_position,
+            facing_direction: squad.facing_direction,
+            target_facing_direction: squad.target_facing_direction,
+            target_position: squad.target_position,
+            path: squad.path.iter().copied().collect(),
+            average_health: squad.average_health,
+            morale: squad.morale,
+            rout_timer: squad.rout_timer,
+            casualties: squad.casualties,
+            commander_lost: squad.commander_lost,
+            suppression: squad.suppression,
+            kills: squad.kills,
+            wave_number,
+            members,
+        }
+    }
+
+    /// Write the saved squad state over a freshly created squad (members and commander excluded)
+    fn restore_into(&self, squad: &mut Squad) {
+        squad.archetype = self.archetype.clone();
+        squad.formation_type = self.formation_type;
+        squad.previous_formation = self.previous_formation;
+        squad.formation_blend = self.formation_blend;
+        squad.center_position = self.center_position;
+        squad.facing_direction = self.facing_direction;
+        squad.target_facing_direction = self.target_facing_direction;
+        squad.target_position = self.target_position;
+        squad.path = self.path.iter().copied().collect();
+        squad.average_health = self.average_health;
+        squad.morale = self.morale;
+        squad.rout_timer = self.rout_timer;
+        squad.casualties = self.casualties;
+        squad.commander_lost = self.commander_lost;
+        squad.suppression = self.suppression;
+        squad.kills = self.kills;
+    }
+}
+
+/// One infantry unit of a squad
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedUnit {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+    pub formation_position: (usize, usize),
+    pub is_commander: bool,
+    pub local_offset: Vec3,
+    pub target_world_position: Vec3,
+    pub march_speed: f32,
+    pub march_offset: f32,
+    pub spawn_position: Vec3,
+    pub target_position: Vec3,
+    pub returning_to_spawn: bool,
+    pub health: f32,
+    pub max_health: f32,
+    pub movement_mode: MovementMode,
+}
+
+/// An uplink tower or the command bunker still standing
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedTower {
+    pub team: Team,
+    pub position: Vec3,
+    pub health: f32,
+    pub max_health: f32,
+    pub is_bunker: bool,
+    pub is_primary: bool,
+}
+
+/// Who holds a capture point and how far a capture has got
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedCapturePoint {
+    pub name: String,
+    pub owner: Option<Team>,
+    pub holder: Option<Team>,
+    pub progress: f32,
+    pub bonus_timer: f32,
+    pub reinforcement_squads: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedTurret {
+    pub turret_type: TurretType,
+    pub team: Team,
+    pub position: Vec3,
+    pub health: f32,
+    pub max_health: f32,
+}
+
+/// A shield, up or waiting to respawn
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SavedShield {
+    Active {
+        team: Team,
+        center: Vec3,
+        radius: f32,
+        hp: f32,
+        /// Seconds since the last hit (regeneration waits for ShieldConfig::regen_delay)
+        since_last_hit: f32,
+    },
+    Destroyed {
+        team: Team,
+        position: Vec3,
+        radius: f32,
+        respawn_timer: f32,
+    },
+}
+
+/// WaveManager progress; timers are stored as elapsed seconds and turrets by index
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedWaveManager {
+    pub strategic_wave: u32,
+    pub total_strategic_waves: u32,
+    pub strategic_cooldown_elapsed: f32,
+    pub tactical_wave: u32,
+    pub total_tactical_waves: u32,
+    pub next_wave_elapsed: f32,
+    pub spawning_active: bool,
+    pub enemies_remaining: u32,
+    pub squads_spawned: u32,
+    pub wave_squads: u32,
+    pub spawn_elapsed: f32,
+    pub wave_state: WaveState,
+    pub battle_time: f32,
+    pub turrets_remaining: u32,
+    pub place_mg_turret: bool,
+    /// Indices into SaveFile::turrets, in placement order
+    pub placed_turrets: Vec<usize>,
+    pub reinforcements_spawned: bool,
+}
+
+impl SavedWaveManager {
+    fn from_wave_manager(wave_manager: &WaveManager, turret_entities: &[Entity]) -> Self {
+        Self {
+            strategic_wave: wave_manager.strategic_wave,
+            total_strategic_waves: wave_manager.total_strategic_waves,
+            strategic_cooldown_elapsed: wave_manager.strategic_cooldown_timer.elapsed_secs(),
+            tactical_wave: wave_manager.tactical_wave,
+            total_tactical_waves: wave_manager.total_tactical_waves,
+            next_wave_elapsed: wave_manager.next_wave_timer.elapsed_secs(),
+            spawning_active: wave_manager.spawning_active,
+            enemies_remaining: wave_manager.enemies_remaining,
+            squads_spawned: wave_manager.squads_spawned,
+            wave_squads: wave_manager.wave_squads,
+            spawn_elapsed: wave_manager.spawn_timer.elapsed_secs(),
+            wave_state: wave_manager.wave_state,
+            battle_time: wave_manager.battle_time,
+            turrets_remaining: wave_manager.turrets_remaining,
+            place_mg_turret: wave_manager.place_mg_turret,
+            placed_turrets: wave_manager.placed_turrets.iter()
+                .filter_map(|entity| turret_entities.iter().position(|turret| turret == entity))
+                .collect(),
+            reinforcements_spawned: wave_manager.reinforcements_spawned,
+        }
+    }
+
+    /// Write the saved progress over the scenario's fresh WaveManager (which keeps its timer durations)
+    fn restore_into(&self, wave_manager: &mut WaveManager, turret_entities: &[Entity]) {
+        wave_manager.strategic_wave = self.strategic_wave;
+        wave_manager.total_strategic_waves = self.total_strategic_waves;
+        wave_manager.tactical_wave = self.tactical_wave;
+        wave_manager.total_tactical_waves = self.total_tactical_waves;
+        wave_manager.spawning_active = self.spawning_active;
+        wave_manager.enemies_remaining = self.enemies_remaining;
+        wave_manager.squads_spawned = self.squads_spawned;
+        wave_manager.wave_squads = self.wave_squads;
+        wave_manager.wave_state = self.wave_state;
+        wave_manager.battle_time = self.battle_time;
+        wave_manager.turrets_remaining = self.turrets_remaining;
+        wave_manager.place_mg_turret = self.place_mg_turret;
+        wave_manager.placed_turrets = self.placed_turrets.iter()
+            .filter_map(|&index| turret_entities.get(index).copied())
+            .collect();
+        wave_manager.reinforcements_spawned = self.reinforcements_spawned;
+        wave_manager.strategic_cooldown_timer.set_elapsed(Duration::from_secs_f32(self.strategic_cooldown_elapsed));
+        wave_manager.next_wave_timer.set_elapsed(Duration::from_secs_f32(self.next_wave_elapsed));
+        wave_manager.spawn_timer.set_elapsed(Duration::from_secs_f32(self.spawn_elapsed));
+    }
+}
+
+/// Scenario trigger progress: which triggers have fired, the message and objective on screen
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SavedTriggers {
+    pub fired: Vec<bool>,
+    pub message: Option<(String, f32)>,
+    pub objective: String,
+}
+
+impl SavedTriggers {
+    fn from_trigger_state(trigger_state: &TriggerState) -> Self {
+        Self {
+            fired: trigger_state.fired.clone(),
+            message: trigger_state.message.clone(),
+            objective: trigger_state.objective.clone(),
+        }
+    }
+
+    fn restore_into(&self, trigger_state: &mut TriggerState) {
+        trigger_state.fired = self.fired.clone();
+        trigger_state.message = self.message.clone();
+        trigger_state.objective = self.objective.clone();
+        trigger_state.outcome = None;
+    }
+}
+
+/// A squad group (see SquadGroup)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedGroup {
+    pub id: u32,
+    pub squad_ids: Vec<u32>,
+    pub squad_offsets: Vec<(u32, Vec3)>,
+    pub original_formation_facing: Vec3,
+    pub formation_facing: Vec3,
+}
+
+// ===== REQUESTS =====
+
+/// Save/load requested by the hotkeys, and a loaded save waiting for its map
+#[derive(Resource, Default)]
+pub struct SaveGameRequests {
+    pub save: Option<String>,
+    pub load: Option<String>,
+    pending: Option<PendingLoad>,
+}
+
+struct PendingLoad {
+    file: SaveFile,
+    /// The saved map has loaded; the save is applied on the next frame
+    map_ready: bool,
+}
+
+fn save_game_hotkey_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut requests: ResMut<SaveGameRequests>,
+) {
+    if keys.just_pressed(KeyCode::F8) {
+        requests.save = Some(QUICKSAVE_PATH.to_string());
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        requests.load = Some(QUICKSAVE_PATH.to_string());
+    }
+}
+
+// ===== SAVING =====
+
+/// Write the battle to disk when a save is requested
+fn write_save_system(
+    mut requests: ResMut<SaveGameRequests>,
+    config: Res<TerrainConfig>,
+    (squad_manager, wave_manager, scenario_state, selection_state, game_state, fixed_time):
+        (Res<SquadManager>, Res<WaveManager>, Res<ScenarioState>, Res<SelectionState>, Res<GameState>, Res<Time<Fixed>>),
+    (trigger_state, economy): (Res<TriggerState>, Res<Economy>),
+    droid_query: Query<(&Transform, &BattleDroid, &SquadMember, &Health, &MovementMode, &FormationOffset, Option<&WaveEnemy>)>,
+    tower_query: Query<(&Transform, &UplinkTower, &Health, Has<CommandBunker>, Option<&ObjectiveTarget>)>,
+    turret_query: Query<(Entity, &Transform, &TurretBase, &Health, &Children)>,
+    mg_query: Query<(), With<MgTurret>>,
+    shield_query: Query<&Shield>,
+    destroyed_shield_query: Query<&DestroyedShield>,
+    capture_query: Query<&CapturePoint>,
+) {
+    let Some(path) = requests.save.take() else { return };
+
+    if config.pending_heightmap.is_some() || requests.pending.is_some() {
+        warn!("Can't save while a map or save is loading");
+        return;
+    }
+
+    let mut squad_ids: Vec<u32> = squad_manager.squads.keys().copied().collect();
+    squad_ids.sort_unstable();
+    let squads: Vec<SavedSquad> = squad_ids.iter()
+        .filter_map(|id| squad_manager.get_squad(*id))
+        .map(|squad| {
+            let members: Vec<(SavedUnit, Option<u32>)> = squad.members.iter()
+                .filter_map(|&entity| droid_query.get(entity).ok())
+                .map(|(transform, droid, member, health, movement_mode, offset, wave_enemy)| (
+                    SavedUnit {
+                        translation: transform.translation,
+                        rotation: transform.rotation,
+                        scale: transform.scale,
+                        formation_position: member.formation_position,
+                        is_commander: member.is_commander,
+                        local_offset: offset.local_offset,
+                        target_world_position: offset.target_world_position,
+                        march_speed: droid.march_speed,
+                        march_offset: droid.march_offset,
+                        spawn_position: droid.spawn_position,
+                        target_position: droid.target_position,
+                        returning_to_spawn: droid.returning_to_spawn,
+                        health: health.current,
+                        max_health: health.max,
+                        movement_mode: *movement_mode,
+                    },
+                    wave_enemy.map(|enemy| enemy.wave_number),
+                ))
+                .collect();
+            let wave_number = members.iter().find_map(|(_, wave_number)| *wave_number);
+            SavedSquad::from_squad(squad, members.into_iter().map(|(unit, _)| unit).collect(), wave_number)
+        })
+        .collect();
+
+    // Towers already dying are left out: the save treats them as destroyed
+    let towers: Vec<SavedTower> = tower_query.iter()
+        .filter(|(_, _, health, _, _)| !health.is_dead())
+        .map(|(transform, tower, health, is_bunker, objective)| SavedTower {
+            team: tower.team,
+            position: transform.translation,
+            health: health.current,
+            max_health: health.max,
+            is_bunker,
+            is_primary: objective.map_or(true, |objective| objective.is_primary),
+        })
+        .collect();
+
+    let mut turret_entities = Vec::new();
+    let mut turrets = Vec::new();
+    for (entity, transform, turret, health, children) in turret_query.iter() {
+        if health.is_dead() {
+            continue;
+        }
+        let is_mg = children.iter().any(|child| mg_query.contains(child));
+        turret_entities.push(entity);
+        turrets.push(SavedTurret {
+            turret_type: if is_mg { TurretType::MachineGun } else { TurretType::Heavy },
+            team: turret.team,
+            position: transform.translation,
+            health: health.current,
+            max_health: health.max,
+        });
+    }
+
+    let now = fixed_time.elapsed_secs();
+    let shields: Vec<SavedShield> = shield_query.iter()
+        .map(|shield| SavedShield::Active {
+            team: shield.team,
+            center: shield.center,
+            radius: shield.radius,
+            hp: shield.current_hp,
+            since_last_hit: now - shield.last_hit_time,
+        })
+        .chain(destroyed_shield_query.iter().map(|destroyed| SavedShield::Destroyed {
+            team: destroyed.team,
+            position: destroyed.position,
+            radius: destroyed.radius,
+            respawn_timer: destroyed.respawn_timer,
+        }))
+        .collect();
+
+    let mut groups: Vec<SavedGroup> = selection_state.groups.iter()
+        .map(|(&id, group)| SavedGroup {
+            id,
+            squad_ids: group.squad_ids.clone(),
+            squad_offsets: group.squad_ids.iter()
+                .filter_map(|squad_id| group.squad_offsets.get(squad_id).map(|&offset| (*squad_id, offset)))
+                .collect(),
+            original_formation_facing: group.original_formation_facing,
+            formation_facing: group.formation_facing,
+        })
+        .collect();
+    groups.sort_by_key(|group| group.id);
+
+    let file = SaveFile {
+        version: SAVE_FORMAT_VERSION,
+        map: config.current_map,
+        scenario: scenario_state.scenario_id.clone(),
+        game_state: game_state.clone(),
+        next_squad_id: squad_manager.next_squad_id,
+        squads,
+        towers,
+        turrets,
+        shields,
+        capture_points: capture_query.iter()
+            .map(|point| SavedCapturePoint {
+                name: point.name.clone(),
+                owner: point.owner,
+                holder: point.holder,
+                progress: point.progress,
+                bonus_timer: point.bonus_timer,
+                reinforcement_squads: point.reinforcement_squads.clone(),
+            })
+            .collect(),
+        wave_manager: SavedWaveManager::from_wave_manager(&wave_manager, &turret_entities),
+        triggers: SavedTriggers::from_trigger_state(&trigger_state),
+        groups,
+        next_group_id: selection_state.next_group_id,
+        economy: economy.clone(),
+    };
+
+    match file.save(&path) {
+        Ok(()) => info!("Battle saved to {} ({} squads, {} turrets, map {:?})",
+            path, file.squads.len(), file.turrets.len(), file.map),
+        Err(e) => error!("{}", e),
+    }
+}
+
+// ===== LOADING =====
+
+/// Read a requested save; switch to its map first if it isn't the current one
+fn read_save_system(
+    mut requests: ResMut<SaveGameRequests>,
+    mut config: ResMut<TerrainConfig>,
+    mut scenario_state: ResMut<ScenarioState>,
+    recorder: Option<Res<ReplayRecorder>>,
+) {
+    let Some(path) = requests.load.take() else { return };
+    if recorder.is_some() {
+        warn!("Not loading {}: saves can't be loaded while recording a replay", path);
+        return;
+    }
+
+    let file = match SaveFile::load(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    // The saved scenario starts again when its map loads
+    if file.scenario.is_some() {
+        scenario_state.requested = file.scenario.clone();
+    }
+
+    let map_ready = file.map == config.current_map && config.pending_heightmap.is_none();
+    if map_ready && file.scenario != scenario_state.scenario_id {
+        warn!("{} was saved in scenario {:?}, loading it into {:?}", path, file.scenario, scenario_state.scenario_id);
+    }
+    if !map_ready {
+        info!("Loading {}: switching to map {:?} first", path, file.map);
+        config.requested_map = Some(file.map);
+    }
+    requests.pending = Some(PendingLoad { file, map_ready });
+}
+
+/// Replace the battle with the loaded save once its map is in place
+fn apply_loaded_save_system(
+    mut commands: Commands,
+    mut requests: ResMut<SaveGameRequests>,
+    mut map_switch_events: EventReader<MapSwitchEvent>,
+    (mut meshes, mut materials, mut shield_materials):
+        (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>, ResMut<Assets<ShieldMaterial>>),
+    (mut squad_manager, mut archetypes, mut sim_rng, heightmap):
+        (ResMut<SquadManager>, ResMut<UnitArchetypes>, ResMut<SimRng>, Res<TerrainHeightmap>),
+    (mut wave_manager, mut trigger_state, mut selection_state, mut game_state, shield_config, fixed_time):
+        (ResMut<WaveManager>, ResMut<TriggerState>, ResMut<SelectionState>, ResMut<GameState>, Res<ShieldConfig>, Res<Time<Fixed>>),
+    mut artillery_state: Option<ResMut<ArtilleryState>>,
+    mut economy: ResMut<Economy>,
+    droid_query: Query<Entity, With<SquadMember>>,
+    turret_query: Query<Entity, With<TurretBase>>,
+    shield_query: Query<Entity, Or<(With<Shield>, With<DestroyedShield>)>>,
+    mut tower_query: Query<(Entity, &Transform, &UplinkTower, &mut Health, Has<CommandBunker>)>,
+    mut capture_query: Query<&mut CapturePoint>,
+) {
+    let Some(pending) = requests.pending.as_mut() else {
+        map_switch_events.clear();
+        return;
+    };
+
+    // The map's own setup (scenario garrison, default turrets) spawns this frame; replace it next frame
+    if map_switch_events.read().any(|event| event.new_map == pending.file.map) {
+        pending.map_ready = true;
+        return;
+    }
+    if !pending.map_ready {
+        return;
+    }
+    let Some(PendingLoad { file, .. }) = requests.pending.take() else { return };
+
+    // Clear the current battle
+    for entity in droid_query.iter().chain(turret_query.iter()).chain(shield_query.iter()) {
+        commands.entity(entity).despawn();
+    }
+    *squad_manager = SquadManager::new();
+    if let Some(artillery_state) = artillery_state.as_mut() {
+        artillery_state.pending_shells.clear();
+    }
+
+    // Squads, under their saved ids
+    let mut team_materials = HashMap::new();
+    for saved in &file.squads {
+        if saved.members.is_empty() {
+            let mut squad = Squad::new(saved.id, saved.team, saved.center_position, saved.facing_direction);
+            saved.restore_into(&mut squad);
+            squad_manager.squads.insert(saved.id, squad);
+            continue;
+        }
+
+        let unit_materials = team_materials
+            .entry(saved.team)
+            .or_insert_with(|| create_team_materials(&mut materials, saved.team));
+        squad_manager.next_squad_id = saved.id;
+        let squad_id = spawn_single_squad(
+            &mut commands,
+            &mut squad_manager,
+            &mut archetypes,
+            &saved.archetype,
+            &mut meshes,
+            unit_materials,
+            &mut materials,
+            saved.team,
+            saved.center_position,
+            saved.facing_direction,
+            &heightmap,
+            &mut *sim_rng,
+        );
+        restore_squad_members(&mut commands, &mut squad_manager, squad_id, saved);
+    }
+    squad_manager.next_squad_id = file.next_squad_id;
+
+    // Turrets
+    let turret_entities: Vec<Entity> = file.turrets.iter()
+        .map(|saved| {
+            let entity = spawn_turret_at(saved.turret_type, &mut commands, &mut meshes, &mut materials, saved.position, saved.team);
+            commands.entity(entity).insert(Health { current: saved.health, max: saved.max_health });
+            entity
+        })
+        .collect();
+
+    // Towers: standing ones take the saved health, the rest fell before the save
+    let mut unmatched: Vec<&SavedTower> = file.towers.iter().collect();
+    for (entity, transform, tower, mut health, is_bunker) in tower_query.iter_mut() {
+        let closest = unmatched.iter()
+            .enumerate()
+            .filter(|(_, saved)| saved.team == tower.team && saved.is_bunker == is_bunker)
+            .min_by(|a, b| {
+                a.1.position.distance_squared(transform.translation)
+                    .total_cmp(&b.1.position.distance_squared(transform.translation))
+            })
+            .map(|(index, _)| index);
+        match closest {
+            Some(index) => {
+                let saved = unmatched.swap_remove(index);
+                health.current = saved.health;
+                health.max = saved.max_health;
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+    for saved in unmatched {
+        let entity = if saved.is_bunker {
+            spawn_command_bunker(&mut commands, &mut meshes, &mut materials, saved.team, saved.position, saved.max_health)
+        } else {
+            crate::objective::spawn_uplink_tower(&mut commands, &mut meshes, &mut materials, saved.team, saved.position)
+        };
+        commands.entity(entity).insert((
+            Health { current: saved.health, max: saved.max_health },
+            ObjectiveTarget { team: saved.team, is_primary: saved.is_primary },
+        ));
+    }
+
+    // Shields
+    let now = fixed_time.elapsed_secs();
+    for saved in &file.shields {
+        match *saved {
+            SavedShield::Active { team, center, radius, hp, since_last_hit } => {
+                let entity = spawn_shield_with_hp(
+                    &mut commands,
+                    &mut meshes,
+                    &mut shield_materials,
+                    center,
+                    radius,
+                    team.shield_color(),
+                    team,
+                    &shield_config,
+                    hp,
+                );
+                commands.entity(entity).entry::<Shield>().and_modify(move |mut shield| {
+                    shield.last_hit_time = now - since_last_hit;
+                });
+            }
+            SavedShield::Destroyed { team, position, radius, respawn_timer } => {
+                commands.spawn(DestroyedShield {
+                    team,
+                    position,
+                    radius,
+                    team_color: team.shield_color(),
+                    respawn_timer,
+                });
+            }
+        }
+    }
+
+    // Capture points belong to the map (or scenario) like towers
+    for mut point in capture_query.iter_mut() {
+        let Some(saved) = file.capture_points.iter().find(|saved| saved.name == point.name) else {
+            continue;
+        };
+        point.owner = saved.owner;
+        point.holder = saved.holder;
+        point.progress = saved.progress;
+        point.bonus_timer = saved.bonus_timer;
+        point.reinforcement_squads = saved.reinforcement_squads.clone();
+        point.contested = false;
+    }
+
+    file.wave_manager.restore_into(&mut wave_manager, &turret_entities);
+    file.triggers.restore_into(&mut trigger_state);
+    *game_state = file.game_state.clone();
+    *economy = file.economy.clone();
+
+    // Groups come back; the selection refers to old entities and squads, so it starts empty
+    selection_state.selected_squads.clear();
+    selection_state.selected_turret = None;
+    selection_state.hovered_squad = None;
+    selection_state.groups.clear();
+    selection_state.squad_to_group.clear();
+    for saved in &file.groups {
+        for &squad_id in &saved.squad_ids {
+            selection_state.squad_to_group.insert(squad_id, saved.id);
+        }
+        selection_state.groups.insert(saved.id, SquadGroup {
+            id: saved.id,
+            squad_ids: saved.squad_ids.clone(),
+            squad_offsets: saved.squad_offsets.iter().copied().collect(),
+            original_formation_facing: saved.original_formation_facing,
+            formation_facing: saved.formation_facing,
+        });
+    }
+    selection_state.next_group_id = file.next_group_id;
+
+    info!("Battle loaded on map {:?}: {} squads, {} turrets, {} towers, {} shields",
+        file.map, file.squads.len(), file.turrets.len(), file.towers.len(), file.shields.len());
+}
+
+/// Write the saved members over the units spawn_single_squad just created: the saved
+/// commander takes the commander's body, the others fill the remaining units in order.
+/// Units the save doesn't need are removed.
+fn restore_squad_members(
+    commands: &mut Commands,
+    squad_manager: &mut SquadManager,
+    squad_id: u32,
+    saved: &SavedSquad,
+) {
+    let Some(squad) = squad_manager.get_squad_mut(squad_id) else { return };
+    saved.restore_into(squad);
+
+    let spawned_commander = squad.commander;
+    let mut troopers = squad.members.iter().copied().filter(|&entity| Some(entity) != spawned_commander);
+    let mut restored = Vec::with_capacity(saved.members.len());
+    let mut commander = None;
+
+    for unit in &saved.members {
+        let entity = if unit.is_commander && commander.is_none() {
+            spawned_commander.or_else(|| troopers.next())
+        } else {
+            troopers.next()
+        };
+        let Some(entity) = entity else {
+            warn!("Squad {} ({}) has fewer units than its save, dropping the rest", squad_id, saved.archetype);
+            break;
+        };
+        if unit.is_commander && commander.is_none() {
+            commander = Some(entity);
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert((
+            Transform {
+                translation: unit.translation,
+                rotation: unit.rotation,
+                scale: unit.scale,
+            },
+            BattleDroid {
+                march_speed: unit.march_speed,
+                spawn_position: unit.spawn_position,
+                target_position: unit.target_position,
+                march_offset: unit.march_offset,
+                returning_to_spawn: unit.returning_to_spawn,
+                team: saved.team,
+            },
+            SquadMember {
+                squad_id,
+                formation_position: unit.formation_position,
+                is_commander: Some(entity) == commander,
+            },
+            FormationOffset {
+                local_offset: unit.local_offset,
+                target_world_position: unit.target_world_position,
+            },
+            Health { current: unit.health, max: unit.max_health },
+            unit.movement_mode,
+            MovementTracker::new(unit.translation),
+        ));
+        if let Some(wave_number) = saved.wave_number {
+            entity_commands.insert(WaveEnemy { wave_number });
+        }
+        restored.push(entity);
+    }
+
+    let leftover: Vec<Entity> = squad.members.iter().copied().filter(|entity| !restored.contains(entity)).collect();
+    squad.members = restored;
+    squad.commander = commander;
+    for entity in leftover {
+        squad_manager.entity_to_squad.remove(&entity);
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,530 @@
+// Scenario definitions - data-driven missions
+//
+// Each .ron file in assets/scenarios describes one mission (the file stem is its ID): the map
+// it plays on, named spawn points, the objectives each side defends, the player's starting
+// garrison, the turret budget, capture points, the strategic assaults (each a list of tactical waves plus the
+// reinforcements that arrive once it is beaten), scripted triggers (run by trigger.rs) and
+// the victory/defeat rules. Files are read once at startup; a file that fails to parse or
+// refers to an unknown spawn point is logged and skipped. assets/scenarios/firebase_delta.ron
+// is also compiled in, so the "firebase_delta" scenario is always available.
+//
+// A scenario starts when its map loads: the one requested with --scenario <id> if it plays on
+// that map, otherwise the first auto_start scenario for the map (by ID).
+//
+// Example (a single assault on the Firebase Delta hilltop):
+//   (
+//       name: "Outpost",
+//       map: FirebaseDelta,
+//       auto_start: false,
+//       spawn_points: [
+//           (name: "north", position: (0.0, 0.0, -200.0), team: 'B'),
+//           (name: "south", position: (0.0, 0.0, 200.0), team: 'A'),
+//       ],
+//       objectives: [(name: "Outpost", kind: CommandBunker, position: (0.0, 0.0, 0.0), health: 1500.0)],
+//       garrison: [(position: (0.0, 0.0, 30.0), facing: (0.0, 0.0, -1.0))],
+//       turret_budget: 2,
+//       capture_points: [(name: "Ridge", position: (80.0, 0.0, -60.0), bonus: Reinforcements(archetype: "battle_droid"))],
+//       assaults: [(
+//           waves: [(groups: [(spawn: "north", squads: 3)]), (groups: [(spawn: "north", squads: 2, archetype: "walker")])],
+//           reinforcements: [(spawn: "south", squads: 1)],
+//       )],
+//       triggers: [(
+//           condition: ObjectiveHealthBelow(objective: "Outpost", percent: 50.0),
+//           actions: [Message(text: "The outpost is falling!"), Artillery(Scatter(center: (0.0, 0.0, -40.0)))],
+//       )],
+//       victory: [SurviveAllAssaults, Points(target: 300.0)],
+//       defeat: [ObjectiveLost, Breach(radius: 40.0, threshold: 30)],
+//   )
+//
+// Positions are XZ; heights come from the terrain.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::artillery::ArtilleryBarrage;
+use crate::capture::CaptureBonus;
+use crate::constants::{CAPTURE_INCOME_POINTS, CAPTURE_POINT_RADIUS, DEFAULT_SCENARIO, DEFAULT_UNIT_ARCHETYPE, SCENARIO_MESSAGE_SECONDS};
+use crate::terrain::MapPreset;
+use crate::types::Team;
+
+/// Teams are written as their letter ('A'..'D') so scenario files stay readable
+mod team_letter {
+    use super::Team;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(team: &Team, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(team.letter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Team, D::Error> {
+        let letter = char::deserialize(deserializer)?;
+        Team::from_letter(letter).ok_or_else(|| D::Error::custom(format!("unknown team '{}'", letter)))
+    }
+}
+
+/// An optional team, written as Some('A') or None
+mod optional_team_letter {
+    use super::Team;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(team: &Option<Team>, serializer: S) -> Result<S::Ok, S::Error> {
+        team.map(|team| team.letter()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Team>, D::Error> {
+        Option::<char>::deserialize(deserializer)?
+            .map(|letter| Team::from_letter(letter).ok_or_else(|| D::Error::custom(format!("unknown team '{}'", letter))))
+            .transpose()
+    }
+}
+
+/// Where waves gather or reinforcements arrive, referred to by name
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpawnPoint {
+    pub name: String,
+    pub position: Vec3,
+    #[serde(with = "team_letter")]
+    pub team: Team, // Marker color: the player's team is drawn blue, anyone else red
+}
+
+impl Default for SpawnPoint {
+    fn default() -> Self {
+        Self { name: String::new(), position: Vec3::ZERO, team: Team::B }
+    }
+}
+
+/// Structure an objective is built as
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    /// The hilltop command bunker
+    CommandBunker,
+    /// A regular uplink tower
+    UplinkTower,
+}
+
+/// A structure one side has to hold
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioObjective {
+    pub name: String,
+    pub kind: ObjectiveKind,
+    #[serde(with = "team_letter")]
+    pub team: Team,
+    pub position: Vec3,
+    pub health: f32,
+    pub primary: bool,              // Primary objectives decide ObjectiveLost / DestroyEnemyObjectives
+    pub shield_radius: Option<f32>, // Covered by a team shield of this radius
+}
+
+impl Default for ScenarioObjective {
+    fn default() -> Self {
+        Self {
+            name: "Command Bunker".to_string(),
+            kind: ObjectiveKind::CommandBunker,
+            team: Team::A,
+            position: Vec3::ZERO,
+            health: 2000.0,
+            primary: true,
+            shield_radius: None,
+        }
+    }
+}
+
+/// Ground squads take by standing on it (see capture.rs)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioCapturePoint {
+    pub name: String,
+    pub position: Vec3,
+    pub radius: f32,
+    #[serde(with = "optional_team_letter")]
+    pub owner: Option<Team>, // Held from the start, or neutral
+    pub bonus: CaptureBonus,
+}
+
+impl Default for ScenarioCapturePoint {
+    fn default() -> Self {
+        Self {
+            name: "Capture Point".to_string(),
+            position: Vec3::ZERO,
+            radius: CAPTURE_POINT_RADIUS,
+            owner: None,
+            bonus: CaptureBonus::Income(CAPTURE_INCOME_POINTS),
+        }
+    }
+}
+
+/// A player squad in place when the scenario starts
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SquadPlacement {
+    pub archetype: String,
+    pub position: Vec3,
+    pub facing: Vec3,
+}
+
+impl Default for SquadPlacement {
+    fn default() -> Self {
+        Self {
+            archetype: DEFAULT_UNIT_ARCHETYPE.to_string(),
+            position: Vec3::ZERO,
+            facing: Vec3::NEG_Z,
+        }
+    }
+}
+
+/// A number of squads of one archetype entering at a spawn point
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WaveGroup {
+    pub spawn: String,
+    pub squads: u32,
+    pub archetype: String,
+}
+
+impl Default for WaveGroup {
+    fn default() -> Self {
+        Self { spawn: String::new(), squads: 1, archetype: DEFAULT_UNIT_ARCHETYPE.to_string() }
+    }
+}
+
+/// Squads spawned one after another (one per spawn_interval), groups in order
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TacticalWave {
+    pub groups: Vec<WaveGroup>,
+}
+
+impl TacticalWave {
+    pub fn squad_count(&self) -> u32 {
+        self.groups.iter().map(|group| group.squads).sum()
+    }
+
+    /// Group the `index`-th squad of the wave belongs to
+    pub fn group_for_squad(&self, index: u32) -> Option<&WaveGroup> {
+        let mut first = 0;
+        for group in &self.groups {
+            if index < first + group.squads {
+                return Some(group);
+            }
+            first += group.squads;
+        }
+        None
+    }
+}
+
+/// A strategic wave: overlapping tactical waves, then the player's reinforcements
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Assault {
+    pub waves: Vec<TacticalWave>,
+    pub reinforcements: Vec<WaveGroup>,
+}
+
+/// Any one of these wins the scenario
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum VictoryRule {
+    /// Every assault has been beaten
+    SurviveAllAssaults,
+    /// Every primary enemy objective is destroyed
+    DestroyEnemyObjectives,
+    /// The battle has lasted this long since the first assault
+    HoldOut { seconds: f32 },
+    /// The player's team has this many victory points (capture points, secondary objectives)
+    Points { target: f32 },
+}
+
+/// Any one of these loses the scenario
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DefeatRule {
+    /// A primary player objective is destroyed
+    ObjectiveLost,
+    /// This many wave enemies stand within `radius` of a primary player objective
+    Breach { radius: f32, threshold: u32 },
+    /// Every player squad is wiped out after the first assault began
+    ArmyDestroyed,
+    /// The enemy team has this many victory points
+    EnemyPoints { target: f32 },
+}
+
+/// What a trigger waits for
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TriggerCondition {
+    /// At least `units` of `team`'s units stand within `radius` of `center`
+    AreaEntered {
+        #[serde(with = "team_letter")]
+        team: Team,
+        center: Vec3,
+        radius: f32,
+        #[serde(default = "one_unit")]
+        units: u32,
+    },
+    /// `team` has fewer than `count` squads left
+    SquadsBelow {
+        #[serde(with = "team_letter")]
+        team: Team,
+        count: u32,
+    },
+    /// The named objective is below `percent` health (or destroyed)
+    ObjectiveHealthBelow { objective: String, percent: f32 },
+    /// The battle has lasted this long since the first assault (0 fires on scenario start)
+    TimerElapsed { seconds: f32 },
+    /// A shield of `team` is down
+    ShieldDestroyed {
+        #[serde(with = "team_letter")]
+        team: Team,
+    },
+}
+
+fn one_unit() -> u32 {
+    1
+}
+
+fn message_seconds() -> f32 {
+    SCENARIO_MESSAGE_SECONDS
+}
+
+/// What a trigger does when its condition is met
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TriggerAction {
+    /// Squads enter at a spawn point; the enemy team's attack the defended objective
+    SpawnSquads {
+        #[serde(with = "team_letter")]
+        team: Team,
+        spawn: String,
+        squads: u32,
+        #[serde(default = "default_archetype")]
+        archetype: String,
+    },
+    /// Player squads arrive at a spawn point, carrying on the records of fallen squads
+    Reinforcements {
+        spawn: String,
+        squads: u32,
+        #[serde(default = "default_archetype")]
+        archetype: String,
+    },
+    /// Show a message for `seconds`
+    Message {
+        text: String,
+        #[serde(default = "message_seconds")]
+        seconds: f32,
+    },
+    /// Replace the objective line in the scenario UI
+    ChangeObjective { text: String },
+    /// Off-map artillery strike
+    Artillery(ArtilleryBarrage),
+    Victory { reason: String },
+    Defeat { reason: String },
+}
+
+fn default_archetype() -> String {
+    DEFAULT_UNIT_ARCHETYPE.to_string()
+}
+
+/// Actions fired when a condition is met: once, or every time it becomes true again if `repeat`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Trigger {
+    pub name: String,
+    pub condition: TriggerCondition,
+    pub actions: Vec<TriggerAction>,
+    pub repeat: bool,
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            condition: TriggerCondition::TimerElapsed { seconds: 0.0 },
+            actions: Vec::new(),
+            repeat: false,
+        }
+    }
+}
+
+/// One mission, as read from assets/scenarios/<id>.ron
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioDefinition {
+    pub name: String,
+    pub description: String,
+    pub objective: String, // Objective line shown in the scenario UI (ChangeObjective replaces it)
+    pub map: MapPreset,
+    pub auto_start: bool, // Starts whenever its map loads, without --scenario
+    #[serde(with = "team_letter")]
+    pub player_team: Team,
+    #[serde(with = "team_letter")]
+    pub enemy_team: Team,
+    pub spawn_points: Vec<SpawnPoint>,
+    pub objectives: Vec<ScenarioObjective>,
+    pub garrison: Vec<SquadPlacement>,
+    pub turret_budget: u32,
+    pub capture_points: Vec<ScenarioCapturePoint>,
+    pub spawn_interval: f32, // Seconds between squads of a tactical wave
+    pub wave_delay: f32,     // Seconds from a tactical wave finishing its spawns to the next one
+    pub assault_delay: f32,  // Seconds between a cleared assault and the next
+    pub assaults: Vec<Assault>,
+    pub triggers: Vec<Trigger>,
+    pub victory: Vec<VictoryRule>,
+    pub defeat: Vec<DefeatRule>,
+}
+
+impl Default for ScenarioDefinition {
+    /// An empty skirmish: no waves, nothing to defend
+    fn default() -> Self {
+        Self {
+            name: "Untitled Scenario".to_string(),
+            description: String::new(),
+            objective: String::new(),
+            map: MapPreset::FirebaseDelta,
+            auto_start: false,
+            player_team: Team::A,
+            enemy_team: Team::B,
+            spawn_points: Vec::new(),
+            objectives: Vec::new(),
+            garrison: Vec::new(),
+            turret_budget: 0,
+            capture_points: Vec::new(),
+            spawn_interval: 0.1,
+            wave_delay: 8.0,
+            assault_delay: 15.0,
+            assaults: Vec::new(),
+            triggers: Vec::new(),
+            victory: vec![VictoryRule::SurviveAllAssaults],
+            defeat: vec![DefeatRule::ObjectiveLost],
+        }
+    }
+}
+
+impl ScenarioDefinition {
+    /// Assault `number` (1-indexed)
+    pub fn assault(&self, number: u32) -> Option<&Assault> {
+        self.assaults.get((number as usize).checked_sub(1)?)
+    }
+
+    /// Tactical wave `tactical` (1-indexed) of assault `strategic`
+    pub fn tactical_wave(&self, strategic: u32, tactical: u32) -> Option<&TacticalWave> {
+        self.assault(strategic)?.waves.get((tactical as usize).checked_sub(1)?)
+    }
+
+    pub fn spawn_point(&self, name: &str) -> Option<&SpawnPoint> {
+        self.spawn_points.iter().find(|spawn| spawn.name == name)
+    }
+
+    /// Where the attackers head: the player's first primary objective, else the map center
+    pub fn defended_position(&self) -> Vec3 {
+        self.objectives.iter()
+            .find(|objective| objective.team == self.player_team && objective.primary)
+            .map_or(Vec3::ZERO, |objective| objective.position)
+    }
+
+    /// Primary objectives `team` starts with
+    pub fn primary_objective_count(&self, team: Team) -> usize {
+        self.objectives.iter().filter(|objective| objective.team == team && objective.primary).count()
+    }
+
+    /// Every wave, reinforcement group and trigger spawn must name a known spawn point
+    fn validate(&self) -> Result<(), String> {
+        let groups = self.assaults.iter().flat_map(|assault| {
+            assault.waves.iter().flat_map(|wave| wave.groups.iter()).chain(assault.reinforcements.iter())
+        });
+        let trigger_spawns = self.triggers.iter().flat_map(|trigger| &trigger.actions).filter_map(|action| match action {
+            TriggerAction::SpawnSquads { spawn, .. } | TriggerAction::Reinforcements { spawn, .. } => Some(spawn),
+            _ => None,
+        });
+        for spawn in groups.map(|group| &group.spawn).chain(trigger_spawns) {
+            if self.spawn_point(spawn).is_none() {
+                return Err(format!("unknown spawn point '{}'", spawn));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every known scenario, keyed by ID (sorted)
+#[derive(Resource)]
+pub struct ScenarioLibrary {
+    scenarios: BTreeMap<String, ScenarioDefinition>,
+}
+
+/// Firebase Delta compiled in, so the map keeps its mission without the assets directory
+const BUILT_IN_FIREBASE_DELTA: &str = include_str!("../../assets/scenarios/firebase_delta.ron");
+
+impl Default for ScenarioLibrary {
+    /// Only the built-in Firebase Delta (assets/scenarios/firebase_delta.ron)
+    fn default() -> Self {
+        let mut scenarios = BTreeMap::new();
+        match ron::from_str::<ScenarioDefinition>(BUILT_IN_FIREBASE_DELTA) {
+            Ok(definition) => {
+                scenarios.insert(DEFAULT_SCENARIO.to_string(), definition);
+            }
+            Err(e) => error!("Built-in scenario {} failed to parse: {}", DEFAULT_SCENARIO, e),
+        }
+        Self { scenarios }
+    }
+}
+
+impl ScenarioLibrary {
+    /// Read every .ron file in `dir` on top of the built-in Firebase Delta.
+    /// Unreadable or invalid files are logged and skipped.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Self {
+        let mut result = Self::default();
+        let dir = dir.as_ref();
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("No scenarios loaded from {}: {}", dir.display(), e);
+                return result;
+            }
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else {
+                continue;
+            };
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| ron::from_str::<ScenarioDefinition>(&text).map_err(|e| e.to_string()))
+                .and_then(|definition| definition.validate().map(|_| definition));
+            match parsed {
+                Ok(definition) => {
+                    info!("Scenario '{}': {} on {:?} ({} assaults)", id, definition.name, definition.map, definition.assaults.len());
+                    result.scenarios.insert(id, definition);
+                }
+                Err(e) => warn!("Skipping scenario {}: {}", path.display(), e),
+            }
+        }
+
+        result
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ScenarioDefinition> {
+        self.scenarios.get(id)
+    }
+
+    /// Known scenario IDs, sorted
+    pub fn ids(&self) -> Vec<&str> {
+        self.scenarios.keys().map(String::as_str).collect()
+    }
+
+    /// Scenario that starts when `map` loads: `requested` if it plays there, otherwise the
+    /// first auto_start scenario for the map
+    pub fn for_map(&self, map: MapPreset, requested: Option<&str>) -> Option<(&str, &ScenarioDefinition)> {
+        let requested = requested
+            .and_then(|id| self.scenarios.get_key_value(id))
+            .filter(|(_, definition)| definition.map == map);
+        requested
+            .or_else(|| self.scenarios.iter().find(|(_, definition)| definition.auto_start && definition.map == map))
+            .map(|(id, definition)| (id.as_str(), definition))
+    }
+}
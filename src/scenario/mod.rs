@@ -1,86 +1,50 @@
-// Firebase Delta scenario implementation
-// Wave-based defense scenario where players defend a hilltop command bunker
+// Scenario system: wave-based missions loaded from data files
+// Each scenario (see definition.rs) sets its map, objectives, capture points, garrison,
+// turret budget, assaults, triggers and victory/defeat rules; WaveManager drives whichever
+// one is running and trigger.rs fires its scripted events.
+// Firebase Delta - defend a hilltop command bunker - is the built-in default.
 
+mod definition;
 mod wave;
 mod ui;
 mod placement;
+mod trigger;
 
 use bevy::prelude::*;
 use bevy::pbr::NotShadowCaster;
-use crate::terrain::{MapPreset, MapSwitchEvent, TerrainHeightmap, handle_map_switch_units};
+use crate::terrain::{MapPreset, MapSwitchEvent, TerrainConfig, TerrainHeightmap, handle_map_switch_units};
 use crate::types::*;
-use crate::setup::{spawn_single_squad, create_team_materials, create_droid_mesh};
+use crate::game_command::PlayerInputSet;
+use crate::archetype::UnitArchetypes;
+use crate::capture::{spawn_capture_point, CapturePoint};
+use crate::constants::SCENARIO_DIR;
+use crate::setup::{spawn_single_squad, create_team_materials};
+use crate::shield::{spawn_shield, ShieldConfig, ShieldMaterial};
 use crate::procedural_meshes::create_uplink_tower_mesh;
 
 // Re-export submodule systems
 use wave::{
-    wave_state_machine_system, wave_spawner_system, reinforcement_spawner_system,
+    wave_input_system, wave_state_machine_system, wave_spawner_system, reinforcement_spawner_system,
     enemy_death_tracking_system, wave_enemy_move_order_system, victory_defeat_check_system,
 };
-use ui::{spawn_scenario_ui, update_wave_counter_ui, update_enemy_count_ui, update_preparation_ui};
+use ui::{spawn_scenario_ui, update_wave_counter_ui, update_enemy_count_ui, update_preparation_ui, update_trigger_ui};
 use placement::turret_placement_system;
-
-// ============================================================================
-// SCENARIO CONSTANTS (easily tunable)
-// ============================================================================
-
-/// Number of strategic waves (major assault phases)
-/// Set to 1 to use only tactical waves (continuous spawning)
-pub const STRATEGIC_WAVES: u32 = 2;
-
-/// Delay between strategic waves (after all enemies cleared)
-pub const STRATEGIC_WAVE_DELAY: f32 = 15.0;
-
-/// Total number of tactical waves per strategic wave
-pub const TACTICAL_WAVES: u32 = 3;
-
-/// Delay between tactical wave spawn starts (waves overlap, so this is shorter)
-pub const INTER_WAVE_DELAY: f32 = 8.0;
-
-/// Units spawned per second during wave spawning
-pub const SPAWN_RATE: f32 = 10.0;
-
-/// Tactical wave sizes (within each strategic wave)
-pub const WAVE_SIZES: [u32; 3] = [200, 200, 300];
-
-/// Initial garrison size (number of friendly units)
-pub const INITIAL_GARRISON_SIZE: u32 = 300; // 6 squads of 50
-
-/// Number of turrets player can place during preparation
-pub const TURRET_BUDGET: u32 = 5;
-
-/// Radius around command bunker for breach detection
-pub const BREACH_RADIUS: f32 = 50.0;
-
-/// Number of enemies required to trigger breach defeat
-pub const BREACH_THRESHOLD: u32 = 50;
-
-/// North spawn point (enemies come from here in waves 1-10)
-pub const NORTH_SPAWN: Vec3 = Vec3::new(0.0, 0.0, -200.0);
-
-/// East spawn point (enemies come from here in waves 6-10)
-pub const EAST_SPAWN: Vec3 = Vec3::new(200.0, 0.0, 0.0);
-
-/// South spawn point (player reinforcements arrive here between assaults)
-pub const SOUTH_SPAWN: Vec3 = Vec3::new(0.0, 0.0, 200.0);
-
-/// Number of reinforcement squads spawned after each strategic wave
-pub const REINFORCEMENT_SQUADS: u32 = 2;
+use trigger::scenario_trigger_system;
+
+pub use placement::{place_turret, undo_turret_placement};
+pub use wave::end_preparation;
+pub use trigger::{TriggerOutcome, TriggerState};
+pub use definition::{
+    Assault, DefeatRule, ObjectiveKind, ScenarioCapturePoint, ScenarioDefinition, ScenarioLibrary, ScenarioObjective,
+    SpawnPoint, SquadPlacement, TacticalWave, Trigger, TriggerAction, TriggerCondition, VictoryRule, WaveGroup,
+};
 
 // ============================================================================
 // SCENARIO TYPES
 // ============================================================================
 
-/// Type of scenario being played
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
-pub enum ScenarioType {
-    #[default]
-    None,
-    FirebaseDelta,
-}
-
 /// Current state of wave progression
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub enum WaveState {
     #[default]
     Idle,
@@ -90,7 +54,7 @@ pub enum WaveState {
     Combat,
     /// Cooldown between strategic waves (all tactical waves done, enemies cleared)
     StrategicCooldown,
-    /// All strategic waves completed (victory)
+    /// All strategic waves completed
     Complete,
 }
 
@@ -103,8 +67,26 @@ pub enum WaveState {
 pub struct ScenarioState {
     /// Whether a scenario is currently active
     pub active: bool,
-    /// Type of active scenario
-    pub scenario_type: ScenarioType,
+    /// ID of the active scenario (its file stem in assets/scenarios)
+    pub scenario_id: Option<String>,
+    /// The active scenario
+    pub definition: ScenarioDefinition,
+    /// Scenario to start when its map loads instead of the map's default (--scenario)
+    pub requested: Option<String>,
+    /// How the scenario ended, for the UI
+    pub outcome: Option<String>,
+}
+
+impl ScenarioState {
+    /// No scenario running yet; `requested` starts when its map loads
+    pub fn requesting(requested: Option<String>) -> Self {
+        Self { requested, ..default() }
+    }
+
+    /// Team the player commands: the scenario's player team, Team::A outside scenarios
+    pub fn player_team(&self) -> Team {
+        if self.scenario_id.is_some() { self.definition.player_team } else { Team::A }
+    }
 }
 
 /// Wave management resource
@@ -121,7 +103,7 @@ pub struct WaveManager {
     // === Tactical Wave Tracking (within current strategic wave) ===
     /// Current tactical wave number within strategic wave (1-indexed, 0 = not started)
     pub tactical_wave: u32,
-    /// Total tactical waves per strategic wave
+    /// Total tactical waves in the current strategic wave
     pub total_tactical_waves: u32,
     /// Timer until next tactical wave starts spawning
     pub next_wave_timer: Timer,
@@ -131,16 +113,18 @@ pub struct WaveManager {
     // === Enemy Tracking ===
     /// Number of enemies remaining across all tactical waves in current strategic wave
     pub enemies_remaining: u32,
-    /// Number of enemies spawned so far in current tactical wave
-    pub enemies_spawned: u32,
-    /// Target enemy count for current tactical wave
-    pub wave_target: u32,
+    /// Number of squads spawned so far in current tactical wave
+    pub squads_spawned: u32,
+    /// Squads in the current tactical wave
+    pub wave_squads: u32,
     /// Timer for progressive spawning
     pub spawn_timer: Timer,
 
     // === Overall State ===
     /// Current state of wave progression
     pub wave_state: WaveState,
+    /// Seconds of battle since strategic wave 1 began (HoldOut victories)
+    pub battle_time: f32,
 
     // === Turret Placement ===
     /// Turrets remaining to place during preparation
@@ -155,31 +139,33 @@ pub struct WaveManager {
     pub reinforcements_spawned: bool,
 }
 
-impl Default for WaveManager {
-    fn default() -> Self {
+impl WaveManager {
+    /// Fresh wave progression for a scenario
+    pub fn new(definition: &ScenarioDefinition) -> Self {
         Self {
             // Strategic
             strategic_wave: 0,
-            total_strategic_waves: STRATEGIC_WAVES,
-            strategic_cooldown_timer: Timer::from_seconds(STRATEGIC_WAVE_DELAY, TimerMode::Once),
+            total_strategic_waves: definition.assaults.len() as u32,
+            strategic_cooldown_timer: Timer::from_seconds(definition.assault_delay, TimerMode::Once),
 
             // Tactical
             tactical_wave: 0,
-            total_tactical_waves: TACTICAL_WAVES,
-            next_wave_timer: Timer::from_seconds(INTER_WAVE_DELAY, TimerMode::Once),
+            total_tactical_waves: definition.assault(1).map_or(0, |assault| assault.waves.len() as u32),
+            next_wave_timer: Timer::from_seconds(definition.wave_delay, TimerMode::Once),
             spawning_active: false,
 
             // Enemies
             enemies_remaining: 0,
-            enemies_spawned: 0,
-            wave_target: 0,
-            spawn_timer: Timer::from_seconds(1.0 / SPAWN_RATE, TimerMode::Repeating),
+            squads_spawned: 0,
+            wave_squads: 0,
+            spawn_timer: Timer::from_seconds(definition.spawn_interval, TimerMode::Repeating),
 
             // State
             wave_state: WaveState::Idle,
+            battle_time: 0.0,
 
             // Turrets
-            turrets_remaining: TURRET_BUDGET,
+            turrets_remaining: definition.turret_budget,
             place_mg_turret: true,
             placed_turrets: Vec::new(),
 
@@ -189,6 +175,12 @@ impl Default for WaveManager {
     }
 }
 
+impl Default for WaveManager {
+    fn default() -> Self {
+        Self::new(&ScenarioDefinition::default())
+    }
+}
+
 // ============================================================================
 // SCENARIO COMPONENTS
 // ============================================================================
@@ -233,6 +225,14 @@ pub struct ScenarioUI;
 #[derive(Component)]
 pub struct PreparationInstructionsUI;
 
+/// Marker for the current objective UI text
+#[derive(Component)]
+pub struct ObjectiveUI;
+
+/// Marker for trigger message UI text
+#[derive(Component)]
+pub struct ScenarioMessageUI;
+
 /// Marker for spawn point visual markers
 #[derive(Component)]
 pub struct SpawnPointMarker;
@@ -247,173 +247,282 @@ impl Plugin for ScenarioPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ScenarioState>()
             .init_resource::<WaveManager>()
+            .init_resource::<TriggerState>()
+            .insert_resource(ScenarioLibrary::load_dir(SCENARIO_DIR))
+            .add_systems(Startup, request_scenario_map_system)
             .add_systems(Update, (
                 // Must run after handle_map_switch_units clears default units/squads
                 scenario_initialization_system.after(handle_map_switch_units),
+                update_wave_counter_ui,
+                update_enemy_count_ui,
+                update_preparation_ui,
+                update_trigger_ui,
+            ).chain())
+            .add_systems(Update, (
                 turret_placement_system,
+                wave_input_system,
+            ).in_set(PlayerInputSet))
+            // Wave timers and spawns advance in simulation ticks so replays stay in sync
+            .add_systems(FixedUpdate, (
                 wave_state_machine_system,
                 wave_spawner_system,
                 reinforcement_spawner_system,
                 wave_enemy_move_order_system,
                 enemy_death_tracking_system,
-                update_wave_counter_ui,
-                update_enemy_count_ui,
-                update_preparation_ui,
+                scenario_trigger_system,
                 victory_defeat_check_system,
             ).chain());
     }
 }
 
+/// Whether loading `map` starts a new battle: Firebase Delta and debug maps always do, other
+/// maps when a scenario starts there. New battles replace the armies and reset SimRng and
+/// SimTick, so everything that keeps per-battle state (replays, AI) restarts on this.
+pub fn map_starts_new_battle(map: MapPreset, library: Option<&ScenarioLibrary>, state: Option<&ScenarioState>) -> bool {
+    map.starts_new_battle() || scenario_starts_on(map, library, state)
+}
+
+/// Whether loading `map` starts a scenario, which replaces the default armies like a new battle
+pub fn scenario_starts_on(map: MapPreset, library: Option<&ScenarioLibrary>, state: Option<&ScenarioState>) -> bool {
+    library.zip(state)
+        .is_some_and(|(library, state)| library.for_map(map, state.requested.as_deref()).is_some())
+}
+
 // ============================================================================
 // INITIALIZATION SYSTEM
 // ============================================================================
 
-/// System that initializes the scenario when FirebaseDelta map is loaded
+/// --scenario: switch to the requested scenario's map (or restart the current one)
+fn request_scenario_map_system(
+    library: Res<ScenarioLibrary>,
+    mut scenario_state: ResMut<ScenarioState>,
+    mut config: ResMut<TerrainConfig>,
+    mut map_switch_events: EventWriter<MapSwitchEvent>,
+) {
+    let Some(id) = scenario_state.requested.clone() else {
+        return;
+    };
+    let Some(definition) = library.get(&id) else {
+        warn!("Unknown scenario '{}' (known: {})", id, library.ids().join(", "));
+        scenario_state.requested = None;
+        return;
+    };
+
+    info!("Scenario '{}' requested, loading {:?}", id, definition.map);
+    if definition.map == config.current_map {
+        // Already on its map: start over as if the map had just loaded
+        map_switch_events.write(MapSwitchEvent { new_map: definition.map });
+    } else {
+        config.requested_map = Some(definition.map);
+    }
+}
+
+/// System that ends the running scenario on a map switch and starts the new map's scenario
 fn scenario_initialization_system(
     mut map_switch_events: EventReader<MapSwitchEvent>,
+    library: Res<ScenarioLibrary>,
     mut scenario_state: ResMut<ScenarioState>,
     mut wave_manager: ResMut<WaveManager>,
+    mut trigger_state: ResMut<TriggerState>,
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut squad_manager: ResMut<SquadManager>,
-    heightmap: Res<TerrainHeightmap>,
-    // Query to check if scenario is already initialized
-    bunker_query: Query<Entity, With<CommandBunker>>,
-    // Query for cleanup
+    (mut meshes, mut materials, mut shield_materials, shield_config):
+        (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>, ResMut<Assets<ShieldMaterial>>, Res<ShieldConfig>),
+    (mut squad_manager, mut archetypes, mut sim_rng, heightmap):
+        (ResMut<SquadManager>, ResMut<UnitArchetypes>, ResMut<SimRng>, Res<TerrainHeightmap>),
+    // Queries for cleanup
     scenario_entities: Query<Entity, With<ScenarioUnit>>,
     scenario_ui: Query<Entity, With<ScenarioUI>>,
     // Query to hide/show default UI during scenario
     mut game_info_ui: Query<&mut Visibility, With<GameInfoUI>>,
 ) {
     for event in map_switch_events.read() {
-        if event.new_map == MapPreset::FirebaseDelta {
-            // Check if already initialized
-            if !bunker_query.is_empty() {
-                info!("Firebase Delta scenario already initialized");
-                continue;
-            }
+        if scenario_state.active {
+            info!("Leaving scenario '{}', cleaning up...", scenario_state.definition.name);
+            scenario_state.active = false;
+            scenario_state.scenario_id = None;
+            scenario_state.outcome = None;
+            *wave_manager = WaveManager::default();
+            *trigger_state = TriggerState::default();
 
-            info!("Initializing Firebase Delta scenario...");
+            // Despawn all scenario entities and UI (the map switch may already have taken some)
+            for entity in scenario_entities.iter().chain(scenario_ui.iter()) {
+                commands.entity(entity).try_despawn();
+            }
 
-            // Set scenario state
-            scenario_state.active = true;
-            scenario_state.scenario_type = ScenarioType::FirebaseDelta;
+            // Show default UI again
+            for mut visibility in game_info_ui.iter_mut() {
+                *visibility = Visibility::Visible;
+            }
+        }
 
-            // Reset wave manager
-            *wave_manager = WaveManager::default();
+        let Some((id, definition)) = library.for_map(event.new_map, scenario_state.requested.as_deref()) else {
+            continue;
+        };
+
+        info!("Initializing scenario '{}' ({})...", id, definition.name);
+        scenario_state.active = true;
+        scenario_state.scenario_id = Some(id.to_string());
+        scenario_state.definition = definition.clone();
+        scenario_state.outcome = None;
+
+        // Fresh waves; the player places turrets before the first assault
+        *wave_manager = WaveManager::new(definition);
+        wave_manager.wave_state = WaveState::Preparation;
+        *trigger_state = TriggerState::new(definition);
+
+        spawn_scenario_objectives(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &mut shield_materials,
+            &shield_config,
+            &heightmap,
+            definition,
+        );
+
+        for placement in &definition.capture_points {
+            let point = CapturePoint::new(placement.name.clone(), placement.radius, placement.bonus.clone(), placement.owner);
+            let entity = spawn_capture_point(&mut commands, &heightmap, point, placement.position);
+            commands.entity(entity).insert(ScenarioUnit);
+        }
 
-            // Get terrain height at center for command bunker
-            let bunker_y = heightmap.sample_height(0.0, 0.0);
-            let bunker_pos = Vec3::new(0.0, bunker_y, 0.0);
-
-            // Spawn command bunker (reusing UplinkTower mesh)
-            let tower_mesh = create_uplink_tower_mesh(&mut meshes);
-            let tower_material = materials.add(StandardMaterial {
-                base_color: Color::srgb(0.3, 0.5, 0.8), // Blue for Team A
-                metallic: 0.3,
-                perceptual_roughness: 0.7,
-                ..default()
-            });
-
-            commands.spawn((
-                Mesh3d(tower_mesh),
-                MeshMaterial3d(tower_material),
-                Transform::from_translation(bunker_pos),
-                UplinkTower {
-                    team: Team::A,
-                    destruction_radius: 80.0, // Same as normal tower
-                },
-                Health::new(2000.0), // More health than normal tower
-                CommandBunker,
-                ScenarioUnit,
-                Name::new("CommandBunker"),
-            ));
-            info!("Spawned command bunker at {:?}", bunker_pos);
-
-            // Set wave state to Preparation so player can place turrets
-            wave_manager.wave_state = WaveState::Preparation;
-            wave_manager.turrets_remaining = TURRET_BUDGET;
-
-            // Spawn initial garrison (6 squads around the bunker)
-            let droid_mesh = create_droid_mesh(&mut meshes);
-            let unit_materials = create_team_materials(&mut materials, Team::A);
-            let num_garrison_squads = (INITIAL_GARRISON_SIZE / 50) as usize; // 6 squads
-
-            for i in 0..num_garrison_squads {
-                // Position squads in a circle around the bunker
-                let angle = (i as f32 / num_garrison_squads as f32) * std::f32::consts::TAU;
-                let radius = 40.0;
-                let squad_x = radius * angle.cos();
-                let squad_z = radius * angle.sin();
-                let squad_y = heightmap.sample_height(squad_x, squad_z);
-                let squad_pos = Vec3::new(squad_x, squad_y, squad_z);
-
-                // Face outward from bunker
-                let facing = Vec3::new(squad_x, 0.0, squad_z).normalize();
-
-                spawn_single_squad(
-                    &mut commands,
-                    &mut squad_manager,
-                    &droid_mesh,
-                    &unit_materials,
-                    &mut materials,
-                    Team::A,
-                    squad_pos,
-                    facing,
-                    &heightmap,
-                );
-            }
-            info!("Spawned {} garrison squads", num_garrison_squads);
+        // Starting garrison
+        let unit_materials = create_team_materials(&mut materials, definition.player_team);
+        for placement in &definition.garrison {
+            let squad_y = heightmap.sample_height(placement.position.x, placement.position.z);
+            let squad_pos = Vec3::new(placement.position.x, squad_y, placement.position.z);
+
+            spawn_single_squad(
+                &mut commands,
+                &mut squad_manager,
+                &mut archetypes,
+                &placement.archetype,
+                &mut meshes,
+                &unit_materials,
+                &mut materials,
+                definition.player_team,
+                squad_pos,
+                placement.facing.normalize_or(Vec3::NEG_Z),
+                &heightmap,
+                &mut *sim_rng,
+            );
+        }
+        info!("Spawned {} garrison squads", definition.garrison.len());
 
-            // Spawn spawn point markers
-            spawn_spawn_point_markers(&mut commands, &mut meshes, &mut materials, &heightmap);
+        // Spawn spawn point markers
+        spawn_spawn_point_markers(&mut commands, &mut meshes, &mut materials, &heightmap, definition);
 
-            // Spawn scenario UI
-            spawn_scenario_ui(&mut commands);
+        // Spawn scenario UI
+        spawn_scenario_ui(&mut commands, definition);
 
-            // Hide default UI during scenario
-            for mut visibility in game_info_ui.iter_mut() {
-                *visibility = Visibility::Hidden;
-            }
+        // Hide default UI during scenario
+        for mut visibility in game_info_ui.iter_mut() {
+            *visibility = Visibility::Hidden;
+        }
 
-            info!("Firebase Delta scenario initialized - place {} turrets (T to toggle type, click to place, SPACE to start)", TURRET_BUDGET);
+        info!("{} initialized - place {} turrets (T to toggle type, click to place, SPACE to start)",
+            definition.name, definition.turret_budget);
+    }
+}
 
-        } else if scenario_state.active {
-            // Switching away from Firebase Delta - cleanup
-            info!("Leaving Firebase Delta scenario, cleaning up...");
-            scenario_state.active = false;
-            scenario_state.scenario_type = ScenarioType::None;
-            *wave_manager = WaveManager::default();
+/// Build the scenario's objectives, with their shields
+fn spawn_scenario_objectives(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    shield_materials: &mut ResMut<Assets<ShieldMaterial>>,
+    shield_config: &ShieldConfig,
+    heightmap: &TerrainHeightmap,
+    definition: &ScenarioDefinition,
+) {
+    for objective in &definition.objectives {
+        let y = heightmap.sample_height(objective.position.x, objective.position.z);
+        let position = Vec3::new(objective.position.x, y, objective.position.z);
 
-            // Despawn all scenario entities
-            for entity in scenario_entities.iter() {
-                commands.entity(entity).despawn();
+        let entity = match objective.kind {
+            ObjectiveKind::CommandBunker => {
+                spawn_command_bunker(commands, meshes, materials, objective.team, position, objective.health)
             }
-
-            // Despawn scenario UI
-            for entity in scenario_ui.iter() {
-                commands.entity(entity).despawn();
+            ObjectiveKind::UplinkTower => {
+                let entity = crate::objective::spawn_uplink_tower(commands, meshes, materials, objective.team, position);
+                commands.entity(entity).insert((Health::new(objective.health), ScenarioUnit));
+                entity
             }
+        };
+        commands.entity(entity).insert((
+            ObjectiveTarget {
+                team: objective.team,
+                is_primary: objective.primary,
+            },
+            Name::new(objective.name.clone()),
+        ));
 
-            // Show default UI again
-            for mut visibility in game_info_ui.iter_mut() {
-                *visibility = Visibility::Visible;
-            }
+        if let Some(radius) = objective.shield_radius {
+            let shield = spawn_shield(
+                commands,
+                meshes,
+                shield_materials,
+                position,
+                radius,
+                objective.team.shield_color(),
+                objective.team,
+                shield_config,
+            );
+            commands.entity(shield).insert(ScenarioUnit);
         }
+        info!("Spawned {} ({:?}, team {:?}) at {:?}", objective.name, objective.kind, objective.team, position);
     }
 }
 
+/// Spawn a command bunker (reusing the UplinkTower mesh, tougher than a normal tower)
+/// Returns the bunker entity
+pub fn spawn_command_bunker(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    team: Team,
+    position: Vec3,
+    max_health: f32,
+) -> Entity {
+    let tower_mesh = create_uplink_tower_mesh(meshes);
+    let tower_material = materials.add(StandardMaterial {
+        base_color: team.palette().tower, // Matte, unlike the glowing uplink towers
+        metallic: 0.3,
+        perceptual_roughness: 0.7,
+        ..default()
+    });
+
+    commands.spawn((
+        Mesh3d(tower_mesh),
+        MeshMaterial3d(tower_material),
+        Transform::from_translation(position),
+        UplinkTower {
+            team,
+            destruction_radius: 80.0, // Same as normal tower
+        },
+        ObjectiveTarget {
+            team,
+            is_primary: true,
+        },
+        Health::new(max_health),
+        ArmorClass::Structure,
+        CommandBunker,
+        ScenarioUnit,
+        Name::new("CommandBunker"),
+    )).id()
+}
+
 // ============================================================================
 // SPAWN POINT MARKERS
 // ============================================================================
 
-/// Spawn visual markers at spawn points
+/// Spawn visual markers at the scenario's spawn points
 fn spawn_spawn_point_markers(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     heightmap: &TerrainHeightmap,
+    definition: &ScenarioDefinition,
 ) {
     // Create a simple pole/beacon mesh for spawn points
     let pole_mesh = meshes.add(Cylinder::new(0.5, 15.0));
@@ -432,7 +541,7 @@ fn spawn_spawn_point_markers(
         ..default()
     });
 
-    // Blue material for friendly reinforcement spawn point
+    // Blue material for friendly reinforcement spawn points
     let friendly_pole_material = materials.add(StandardMaterial {
         base_color: Color::srgb(0.2, 0.4, 0.8),
         emissive: bevy::color::LinearRgba::new(0.3, 0.5, 1.0, 1.0),
@@ -445,66 +554,40 @@ fn spawn_spawn_point_markers(
         ..default()
     });
 
-    // Enemy spawn points (red)
-    let enemy_spawn_points = [
-        (NORTH_SPAWN, "North Spawn"),
-        (EAST_SPAWN, "East Spawn"),
-    ];
-
-    for (spawn_pos, name) in enemy_spawn_points {
+    for spawn_point in &definition.spawn_points {
+        let spawn_pos = spawn_point.position;
         let y = heightmap.sample_height(spawn_pos.x, spawn_pos.z);
         let marker_pos = Vec3::new(spawn_pos.x, y + 7.5, spawn_pos.z); // Pole center
         let beacon_pos = Vec3::new(spawn_pos.x, y + 16.0, spawn_pos.z); // Beacon on top
 
+        let (pole_material, beacon_material) = if spawn_point.team == definition.player_team {
+            (&friendly_pole_material, &friendly_beacon_material)
+        } else {
+            (&enemy_pole_material, &enemy_beacon_material)
+        };
+
         // Spawn pole
         commands.spawn((
             Mesh3d(pole_mesh.clone()),
-            MeshMaterial3d(enemy_pole_material.clone()),
+            MeshMaterial3d(pole_material.clone()),
             Transform::from_translation(marker_pos),
             SpawnPointMarker,
             ScenarioUnit,
             NotShadowCaster,
-            Name::new(format!("{} Pole", name)),
+            Name::new(format!("{} Spawn Pole", spawn_point.name)),
         ));
 
         // Spawn glowing beacon on top
         commands.spawn((
             Mesh3d(beacon_mesh.clone()),
-            MeshMaterial3d(enemy_beacon_material.clone()),
-            Transform::from_translation(beacon_pos),
-            SpawnPointMarker,
-            ScenarioUnit,
-            NotShadowCaster,
-            Name::new(format!("{} Beacon", name)),
-        ));
-    }
-
-    // Friendly reinforcement spawn point (blue)
-    {
-        let y = heightmap.sample_height(SOUTH_SPAWN.x, SOUTH_SPAWN.z);
-        let marker_pos = Vec3::new(SOUTH_SPAWN.x, y + 7.5, SOUTH_SPAWN.z);
-        let beacon_pos = Vec3::new(SOUTH_SPAWN.x, y + 16.0, SOUTH_SPAWN.z);
-
-        commands.spawn((
-            Mesh3d(pole_mesh.clone()),
-            MeshMaterial3d(friendly_pole_material),
-            Transform::from_translation(marker_pos),
-            SpawnPointMarker,
-            ScenarioUnit,
-            NotShadowCaster,
-            Name::new("South Spawn Pole"),
-        ));
-
-        commands.spawn((
-            Mesh3d(beacon_mesh.clone()),
-            MeshMaterial3d(friendly_beacon_material),
+            MeshMaterial3d(beacon_material.clone()),
             Transform::from_translation(beacon_pos),
             SpawnPointMarker,
             ScenarioUnit,
             NotShadowCaster,
-            Name::new("South Spawn Beacon"),
+            Name::new(format!("{} Spawn Beacon", spawn_point.name)),
         ));
     }
 
-    info!("Spawned spawn point markers at North, East (enemy) and South (reinforcements)");
+    info!("Spawned {} spawn point markers", definition.spawn_points.len());
 }
@@ -3,9 +3,10 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
+use crate::game_command::GameCommand;
 use crate::terrain::TerrainHeightmap;
 use crate::types::*;
-use crate::turrets::{spawn_mg_turret_at, spawn_heavy_turret_at};
+use crate::turrets::{spawn_turret_at, TurretType};
 use crate::selection::screen_to_ground_with_heightmap;
 
 use super::{ScenarioState, WaveManager, WaveState};
@@ -13,7 +14,7 @@ use super::{ScenarioState, WaveManager, WaveState};
 /// Turret placement system - handles mouse clicks during Preparation phase
 /// LMB: Place turret (only if not clicking on a unit), RMB: Undo last placement (only if no squads selected)
 pub fn turret_placement_system(
-    mut wave_manager: ResMut<WaveManager>,
+    wave_manager: Res<WaveManager>,
     scenario_state: Res<ScenarioState>,
     selection_state: Res<crate::selection::SelectionState>,
     mouse_button: Res<ButtonInput<MouseButton>>,
@@ -21,24 +22,24 @@ pub fn turret_placement_system(
     camera_query: Query<(&Camera, &GlobalTransform)>,
     heightmap: Res<TerrainHeightmap>,
     unit_query: Query<(&Transform, &SquadMember), With<BattleDroid>>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut game_commands: EventWriter<GameCommand>,
 ) {
     // Only active during preparation phase
     if !scenario_state.active || wave_manager.wave_state != WaveState::Preparation {
         return;
     }
 
+    // Clicks on the minimap are not placement clicks
+    let Ok(window) = window_query.single() else { return };
+    if crate::minimap::cursor_over_minimap(window) {
+        return;
+    }
+
     // RMB: Undo last turret placement - but only if no squads are selected (let movement handle it)
     if mouse_button.just_pressed(MouseButton::Right) {
         // Only undo if no squads are selected - otherwise let movement system handle RMB
-        if selection_state.selected_squads.is_empty() {
-            if let Some(turret_entity) = wave_manager.placed_turrets.pop() {
-                commands.entity(turret_entity).despawn();
-                wave_manager.turrets_remaining += 1;
-                info!("Undid turret placement ({} remaining)", wave_manager.turrets_remaining);
-            }
+        if selection_state.selected_squads.is_empty() && !wave_manager.placed_turrets.is_empty() {
+            game_commands.write(GameCommand::UndoTurretPlacement);
         }
         return;
     }
@@ -54,7 +55,6 @@ pub fn turret_placement_system(
     }
 
     // Get cursor position
-    let Ok(window) = window_query.single() else { return };
     let Ok((camera, camera_transform)) = camera_query.single() else { return };
     let Some(cursor_pos) = window.cursor_position() else { return };
 
@@ -70,21 +70,44 @@ pub fn turret_placement_system(
         return;
     }
 
-    // Spawn the selected turret type at click position and track it
-    let turret_entity = if wave_manager.place_mg_turret {
-        let entity = spawn_mg_turret_at(&mut commands, &mut meshes, &mut materials, world_pos);
-        info!("Placed MG turret at {:?} ({} remaining)", world_pos, wave_manager.turrets_remaining - 1);
-        entity
-    } else {
-        let entity = spawn_heavy_turret_at(&mut commands, &mut meshes, &mut materials, world_pos);
-        info!("Placed Heavy turret at {:?} ({} remaining)", world_pos, wave_manager.turrets_remaining - 1);
-        entity
-    };
+    // Place the selected turret type at click position
+    let turret_type = if wave_manager.place_mg_turret { TurretType::MachineGun } else { TurretType::Heavy };
+    game_commands.write(GameCommand::PlaceTurret { position: world_pos, turret_type });
+}
+
+/// Spawn a turret during the preparation phase and charge it against the budget
+pub fn place_turret(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    wave_manager: &mut WaveManager,
+    turret_type: TurretType,
+    world_pos: Vec3,
+) {
+    if wave_manager.wave_state != WaveState::Preparation || wave_manager.turrets_remaining == 0 {
+        return;
+    }
+
+    let turret_entity = spawn_turret_at(turret_type, commands, meshes, materials, world_pos, Team::A);
+    info!("Placed {:?} turret at {:?} ({} remaining)", turret_type, world_pos, wave_manager.turrets_remaining - 1);
 
     wave_manager.placed_turrets.push(turret_entity);
     wave_manager.turrets_remaining -= 1;
 }
 
+/// Remove the most recently placed turret and refund it
+pub fn undo_turret_placement(commands: &mut Commands, wave_manager: &mut WaveManager) {
+    if wave_manager.wave_state != WaveState::Preparation {
+        return;
+    }
+    let Some(turret_entity) = wave_manager.placed_turrets.pop() else {
+        return;
+    };
+    commands.entity(turret_entity).despawn();
+    wave_manager.turrets_remaining += 1;
+    info!("Undid turret placement ({} remaining)", wave_manager.turrets_remaining);
+}
+
 /// Calculate squad centers for all friendly (Team::A) squads
 /// Only calculates centers for squads with members present
 fn calculate_squad_centers_for_team(
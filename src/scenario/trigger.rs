@@ -0,0 +1,198 @@
+// Scenario triggers: scripted events from the scenario file
+// Every simulation tick each trigger's condition is checked; when it is met the trigger's
+// actions run once (repeating triggers run again each time the condition becomes true anew).
+
+use bevy::prelude::*;
+use crate::artillery::{queue_artillery_barrage, ArtilleryState};
+use crate::archetype::UnitArchetypes;
+use crate::shield::DestroyedShield;
+use crate::terrain::TerrainHeightmap;
+use crate::types::*;
+
+use super::wave::{inherit_fallen_records, send_wave_squad, spawn_squad_line};
+use super::{ScenarioDefinition, ScenarioState, TriggerAction, TriggerCondition, WaveManager, WaveState};
+
+/// How a trigger ended the scenario
+#[derive(Clone, Debug)]
+pub enum TriggerOutcome {
+    Victory(String),
+    Defeat(String),
+}
+
+/// Runtime state of the active scenario's triggers
+#[derive(Resource, Default)]
+pub struct TriggerState {
+    /// Per trigger in ScenarioDefinition::triggers: fired and not yet re-armed
+    pub fired: Vec<bool>,
+    /// Message on screen and the seconds it has left
+    pub message: Option<(String, f32)>,
+    /// Objective line shown in the scenario UI
+    pub objective: String,
+    /// Win/lose from a Victory or Defeat action, applied by victory_defeat_check_system
+    pub outcome: Option<TriggerOutcome>,
+}
+
+impl TriggerState {
+    /// Nothing fired yet, showing the scenario's starting objective
+    pub fn new(definition: &ScenarioDefinition) -> Self {
+        Self {
+            fired: vec![false; definition.triggers.len()],
+            objective: definition.objective.clone(),
+            ..default()
+        }
+    }
+}
+
+/// Check trigger conditions and run the actions of those that fire
+pub fn scenario_trigger_system(
+    scenario_state: Res<ScenarioState>,
+    game_state: Res<GameState>,
+    mut wave_manager: ResMut<WaveManager>,
+    mut trigger_state: ResMut<TriggerState>,
+    time: Res<Time>,
+    mut commands: Commands,
+    (mut meshes, mut materials): (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>),
+    (mut squad_manager, mut archetypes, mut sim_rng, heightmap):
+        (ResMut<SquadManager>, ResMut<UnitArchetypes>, ResMut<SimRng>, Res<TerrainHeightmap>),
+    mut artillery_state: Option<ResMut<ArtilleryState>>,
+    unit_query: Query<(&Transform, &BattleDroid)>,
+    objective_query: Query<(&Name, &Health), With<ObjectiveTarget>>,
+    destroyed_shield_query: Query<&DestroyedShield>,
+) {
+    if !scenario_state.active || game_state.game_ended || wave_manager.wave_state == WaveState::Idle {
+        return;
+    }
+
+    // Messages fade on their own
+    if let Some((_, seconds_left)) = trigger_state.message.as_mut() {
+        *seconds_left -= time.delta_secs();
+        if *seconds_left <= 0.0 {
+            trigger_state.message = None;
+        }
+    }
+
+    let definition = &scenario_state.definition;
+    if trigger_state.fired.len() != definition.triggers.len() {
+        trigger_state.fired.resize(definition.triggers.len(), false);
+    }
+
+    let condition_met = |condition: &TriggerCondition| match condition {
+        TriggerCondition::AreaEntered { team, center, radius, units } => {
+            let inside = unit_query.iter()
+                .filter(|(transform, droid)| {
+                    droid.team == *team && transform.translation.xz().distance(center.xz()) <= *radius
+                })
+                .count() as u32;
+            inside >= *units
+        }
+        TriggerCondition::SquadsBelow { team, count } => {
+            let squads = squad_manager.squads.values()
+                .filter(|squad| squad.team == *team && !squad.members.is_empty())
+                .count() as u32;
+            squads < *count
+        }
+        TriggerCondition::ObjectiveHealthBelow { objective, percent } => {
+            // A destroyed (despawned) objective is as low as it gets
+            objective_query.iter()
+                .find(|(name, _)| name.as_str() == objective)
+                .map_or(true, |(_, health)| health.current / health.max * 100.0 < *percent)
+        }
+        TriggerCondition::TimerElapsed { seconds } => wave_manager.battle_time >= *seconds,
+        TriggerCondition::ShieldDestroyed { team } => {
+            destroyed_shield_query.iter().any(|shield| shield.team == *team)
+        }
+    };
+
+    // Decide everything first so actions this tick can't affect other triggers' conditions
+    let mut firing = Vec::new();
+    for (index, trigger) in definition.triggers.iter().enumerate() {
+        let met = condition_met(&trigger.condition);
+        if met && !trigger_state.fired[index] {
+            trigger_state.fired[index] = true;
+            firing.push(index);
+        } else if !met && trigger.repeat {
+            // Re-arm for the next time the condition is met
+            trigger_state.fired[index] = false;
+        }
+    }
+
+    let defended_pos = definition.defended_position();
+    let defended_pos = Vec3::new(defended_pos.x, heightmap.sample_height(defended_pos.x, defended_pos.z), defended_pos.z);
+
+    for index in firing {
+        let trigger = &definition.triggers[index];
+        info!("Trigger '{}' fired", trigger.name);
+
+        for action in &trigger.actions {
+            match action {
+                TriggerAction::SpawnSquads { team, spawn, squads, archetype } => {
+                    let Some(spawn_point) = definition.spawn_point(spawn) else {
+                        continue;
+                    };
+                    let squad_ids = spawn_squad_line(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        &mut squad_manager,
+                        &mut archetypes,
+                        &mut sim_rng,
+                        &heightmap,
+                        *team,
+                        archetype,
+                        *squads,
+                        spawn_point.position,
+                        defended_pos,
+                    );
+                    // The enemy's squads join the current wave and march on the objective
+                    if *team == definition.enemy_team {
+                        for squad_id in squad_ids {
+                            send_wave_squad(&mut commands, &mut wave_manager, &mut squad_manager, squad_id, defended_pos);
+                        }
+                    }
+                    info!("{} squads of team {:?} arrived at {}", squads, team, spawn);
+                }
+                TriggerAction::Reinforcements { spawn, squads, archetype } => {
+                    let Some(spawn_point) = definition.spawn_point(spawn) else {
+                        continue;
+                    };
+                    let squad_ids = spawn_squad_line(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        &mut squad_manager,
+                        &mut archetypes,
+                        &mut sim_rng,
+                        &heightmap,
+                        definition.player_team,
+                        archetype,
+                        *squads,
+                        spawn_point.position,
+                        defended_pos,
+                    );
+                    inherit_fallen_records(&mut squad_manager, definition.player_team, &squad_ids);
+                    info!("Reinforcements arrived! {} squads spawned", squad_ids.len());
+                }
+                TriggerAction::Message { text, seconds } => {
+                    info!("Scenario message: {}", text);
+                    trigger_state.message = Some((text.clone(), *seconds));
+                }
+                TriggerAction::ChangeObjective { text } => {
+                    info!("New objective: {}", text);
+                    trigger_state.objective = text.clone();
+                }
+                TriggerAction::Artillery(barrage) => {
+                    // The artillery plugin may be absent (headless runs)
+                    if let Some(artillery_state) = artillery_state.as_mut() {
+                        queue_artillery_barrage(artillery_state, *barrage, Some(&*heightmap), &mut *sim_rng);
+                    }
+                }
+                TriggerAction::Victory { reason } => {
+                    trigger_state.outcome.get_or_insert_with(|| TriggerOutcome::Victory(reason.clone()));
+                }
+                TriggerAction::Defeat { reason } => {
+                    trigger_state.outcome.get_or_insert_with(|| TriggerOutcome::Defeat(reason.clone()));
+                }
+            }
+        }
+    }
+}
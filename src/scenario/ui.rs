@@ -3,16 +3,15 @@
 use bevy::prelude::*;
 
 use super::{
-    ScenarioState, WaveManager, WaveState,
-    WaveCounterUI, EnemyCountUI, PreparationInstructionsUI, ScenarioUI,
-    TURRET_BUDGET, INTER_WAVE_DELAY, STRATEGIC_WAVE_DELAY,
+    ScenarioDefinition, ScenarioState, TriggerState, WaveManager, WaveState,
+    WaveCounterUI, EnemyCountUI, PreparationInstructionsUI, ObjectiveUI, ScenarioMessageUI, ScenarioUI,
 };
 
 /// Spawn scenario UI elements
-pub fn spawn_scenario_ui(commands: &mut Commands) {
+pub fn spawn_scenario_ui(commands: &mut Commands, definition: &ScenarioDefinition) {
     // Wave counter UI
     commands.spawn((
-        Text::new("Assault: 0/0 | Wave: 0/0"),
+        Text::new(format!("{} | Assault: 0/{} | Wave: 0/0", definition.name, definition.assaults.len())),
         TextFont {
             font_size: 24.0,
             ..default()
@@ -49,7 +48,7 @@ pub fn spawn_scenario_ui(commands: &mut Commands) {
     // Preparation phase instructions
     commands.spawn((
         Text::new(format!("PREPARATION - Turrets: {}/{} | T: toggle type | Click: place | SPACE: start",
-            TURRET_BUDGET, TURRET_BUDGET)),
+            definition.turret_budget, definition.turret_budget)),
         TextFont {
             font_size: 18.0,
             ..default()
@@ -64,6 +63,50 @@ pub fn spawn_scenario_ui(commands: &mut Commands) {
         PreparationInstructionsUI,
         ScenarioUI,
     ));
+
+    // Current objective (trigger actions can change it)
+    commands.spawn((
+        Text::new(objective_text(&definition.objective)),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(130.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        ObjectiveUI,
+        ScenarioUI,
+    ));
+
+    // Trigger messages
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 22.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.9, 0.3)), // Yellow
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(155.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        ScenarioMessageUI,
+        ScenarioUI,
+    ));
+}
+
+fn objective_text(objective: &str) -> String {
+    if objective.is_empty() {
+        String::new()
+    } else {
+        format!("Objective: {}", objective)
+    }
 }
 
 /// Update wave counter UI
@@ -78,7 +121,8 @@ pub fn update_wave_counter_ui(
 
     for mut text in query.iter_mut() {
         // Show strategic wave and tactical wave progress
-        *text = Text::new(format!("Assault: {}/{} | Wave: {}/{}",
+        *text = Text::new(format!("{} | Assault: {}/{} | Wave: {}/{}",
+            scenario_state.definition.name,
             wave_manager.strategic_wave, wave_manager.total_strategic_waves,
             wave_manager.tactical_wave, wave_manager.total_tactical_waves));
     }
@@ -110,12 +154,23 @@ pub fn update_preparation_ui(
     }
 
     for (mut text, mut color) in query.iter_mut() {
+        // The scenario is over: show how it ended
+        if let Some(outcome) = &scenario_state.outcome {
+            *text = Text::new(outcome.clone());
+            *color = if outcome.starts_with("VICTORY") {
+                TextColor(Color::srgb(0.3, 1.0, 0.3)) // Green
+            } else {
+                TextColor(Color::srgb(1.0, 0.3, 0.3)) // Red
+            };
+            continue;
+        }
+
         match wave_manager.wave_state {
             WaveState::Preparation => {
                 let turret_type = if wave_manager.place_mg_turret { "MG" } else { "Heavy" };
                 *text = Text::new(format!(
                     "PREPARATION - Turrets: {}/{} | Type: {} | T: toggle | LMB: place | RMB: undo | SPACE: start",
-                    wave_manager.turrets_remaining, TURRET_BUDGET, turret_type
+                    wave_manager.turrets_remaining, scenario_state.definition.turret_budget, turret_type
                 ));
                 *color = TextColor(Color::srgb(0.3, 1.0, 0.3)); // Green
             }
@@ -124,8 +179,8 @@ pub fn update_preparation_ui(
                     format!("COMBAT - Assault {} Wave {} spawning...",
                         wave_manager.strategic_wave, wave_manager.tactical_wave)
                 } else if wave_manager.tactical_wave < wave_manager.total_tactical_waves {
-                    let remaining = INTER_WAVE_DELAY - wave_manager.next_wave_timer.elapsed_secs();
-                    format!("COMBAT - Next wave in {:.0}s", remaining.max(0.0))
+                    let remaining = wave_manager.next_wave_timer.remaining_secs();
+                    format!("COMBAT - Next wave in {:.0}s", remaining)
                 } else {
                     format!("COMBAT - Final wave of Assault {}!", wave_manager.strategic_wave)
                 };
@@ -133,16 +188,16 @@ pub fn update_preparation_ui(
                 *color = TextColor(Color::srgb(1.0, 0.5, 0.3)); // Orange
             }
             WaveState::StrategicCooldown => {
-                let remaining = STRATEGIC_WAVE_DELAY - wave_manager.strategic_cooldown_timer.elapsed_secs();
+                let remaining = wave_manager.strategic_cooldown_timer.remaining_secs();
                 *text = Text::new(format!(
                     "ASSAULT {} CLEARED! Next assault in {:.0}s",
-                    wave_manager.strategic_wave, remaining.max(0.0)
+                    wave_manager.strategic_wave, remaining
                 ));
                 *color = TextColor(Color::srgb(0.3, 0.8, 1.0)); // Cyan
             }
             WaveState::Complete => {
-                *text = Text::new("VICTORY! All assaults repelled!");
-                *color = TextColor(Color::srgb(0.3, 1.0, 0.3)); // Green
+                *text = Text::new("All assaults repelled! Complete the remaining objectives");
+                *color = TextColor(Color::srgb(0.3, 0.8, 1.0)); // Cyan
             }
             WaveState::Idle => {
                 *text = Text::new("");
@@ -150,3 +205,23 @@ pub fn update_preparation_ui(
         }
     }
 }
+
+/// Update the objective line and trigger message
+pub fn update_trigger_ui(
+    trigger_state: Res<TriggerState>,
+    scenario_state: Res<ScenarioState>,
+    mut objective_query: Query<&mut Text, (With<ObjectiveUI>, Without<ScenarioMessageUI>)>,
+    mut message_query: Query<&mut Text, With<ScenarioMessageUI>>,
+) {
+    if !scenario_state.active || !trigger_state.is_changed() {
+        return;
+    }
+
+    for mut text in objective_query.iter_mut() {
+        *text = Text::new(objective_text(&trigger_state.objective));
+    }
+    for mut text in message_query.iter_mut() {
+        let message = trigger_state.message.as_ref().map_or("", |(message, _)| message.as_str());
+        *text = Text::new(message);
+    }
+}
@@ -1,14 +1,15 @@
 // Wave spawning and state machine systems
 
 use bevy::prelude::*;
+use crate::game_command::GameCommand;
 use crate::terrain::TerrainHeightmap;
 use crate::types::*;
-use crate::setup::{spawn_single_squad, create_team_materials, create_droid_mesh};
+use crate::archetype::UnitArchetypes;
+use crate::setup::{spawn_single_squad, create_team_materials};
 
 use super::{
-    ScenarioState, WaveManager, WaveState, WaveEnemy, NeedsMoveOrder, CommandBunker,
-    WAVE_SIZES, INTER_WAVE_DELAY, STRATEGIC_WAVE_DELAY, NORTH_SPAWN, EAST_SPAWN, SOUTH_SPAWN,
-    REINFORCEMENT_SQUADS,
+    ScenarioDefinition, ScenarioState, TriggerOutcome, TriggerState, WaveManager, WaveState, WaveEnemy,
+    NeedsMoveOrder, VictoryRule, DefeatRule,
 };
 
 /// Wave state machine - handles transitions between wave states
@@ -16,29 +17,22 @@ use super::{
 pub fn wave_state_machine_system(
     mut wave_manager: ResMut<WaveManager>,
     scenario_state: Res<ScenarioState>,
-    keys: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
 ) {
     if !scenario_state.active {
         return;
     }
 
+    if matches!(wave_manager.wave_state, WaveState::Combat | WaveState::StrategicCooldown) {
+        wave_manager.battle_time += time.delta_secs();
+    }
+
     match wave_manager.wave_state {
         WaveState::Idle => {
-            // Idle state - shouldn't happen in FirebaseDelta, goes straight to Preparation
+            // Idle state - shouldn't happen in a scenario, goes straight to Preparation
         }
         WaveState::Preparation => {
-            // Player is placing turrets. SPACE key starts strategic wave 1.
-            if keys.just_pressed(KeyCode::Space) {
-                start_strategic_wave(&mut wave_manager, 1);
-                info!("Preparation complete! Strategic Wave 1 starting!");
-            }
-            // T key toggles turret type
-            if keys.just_pressed(KeyCode::KeyT) {
-                wave_manager.place_mg_turret = !wave_manager.place_mg_turret;
-                let turret_type = if wave_manager.place_mg_turret { "MG" } else { "Heavy" };
-                info!("Turret type: {}", turret_type);
-            }
+            // Player is placing turrets - wave_input_system ends preparation
         }
         WaveState::Combat => {
             // Check if all tactical waves in this strategic wave are done
@@ -48,16 +42,15 @@ pub fn wave_state_machine_system(
             if all_tactical_waves_spawned && wave_manager.enemies_remaining == 0 {
                 // All tactical waves cleared - check if more strategic waves remain
                 if wave_manager.strategic_wave >= wave_manager.total_strategic_waves {
-                    // Victory!
                     wave_manager.wave_state = WaveState::Complete;
-                    info!("All strategic waves completed! Victory!");
+                    info!("All strategic waves completed!");
                 } else {
                     // Start cooldown before next strategic wave
                     wave_manager.wave_state = WaveState::StrategicCooldown;
                     wave_manager.strategic_cooldown_timer.reset();
                     wave_manager.reinforcements_spawned = false; // Allow reinforcements to spawn
-                    info!("Strategic Wave {} cleared! Next assault in {:.0}s - Reinforcements incoming!",
-                        wave_manager.strategic_wave, STRATEGIC_WAVE_DELAY);
+                    info!("Strategic Wave {} cleared! Next assault in {:.0}s",
+                        wave_manager.strategic_wave, wave_manager.strategic_cooldown_timer.duration().as_secs_f32());
                 }
                 return;
             }
@@ -69,17 +62,13 @@ pub fn wave_state_machine_system(
                 wave_manager.next_wave_timer.tick(time.delta());
 
                 if wave_manager.next_wave_timer.just_finished() {
-                    // Start next tactical wave (enemies ADD to remaining, waves overlap)
-                    wave_manager.tactical_wave += 1;
-                    wave_manager.enemies_spawned = 0;
-                    let wave_idx = (wave_manager.tactical_wave - 1) as usize;
-                    wave_manager.wave_target = WAVE_SIZES.get(wave_idx).copied().unwrap_or(200);
-                    wave_manager.enemies_remaining += wave_manager.wave_target;
-                    wave_manager.spawning_active = true;
+                    // Start next tactical wave (its enemies ADD to remaining, waves overlap)
+                    let next_tactical = wave_manager.tactical_wave + 1;
+                    start_tactical_wave(&mut wave_manager, &scenario_state.definition, next_tactical);
                     wave_manager.next_wave_timer.reset();
-                    info!("Strategic {}, Tactical {} starting! Target: {} enemies, total remaining: {}",
+                    info!("Strategic {}, Tactical {} starting! Target: {} squads, enemies remaining: {}",
                         wave_manager.strategic_wave, wave_manager.tactical_wave,
-                        wave_manager.wave_target, wave_manager.enemies_remaining);
+                        wave_manager.wave_squads, wave_manager.enemies_remaining);
                 }
             }
         }
@@ -89,25 +78,63 @@ pub fn wave_state_machine_system(
 
             if wave_manager.strategic_cooldown_timer.just_finished() {
                 let next_strategic = wave_manager.strategic_wave + 1;
-                start_strategic_wave(&mut wave_manager, next_strategic);
+                start_strategic_wave(&mut wave_manager, &scenario_state.definition, next_strategic);
                 info!("Strategic Wave {} starting!", wave_manager.strategic_wave);
             }
         }
         WaveState::Complete => {
-            // Victory state - handled by victory_defeat_check_system
+            // Every assault beaten - victory_defeat_check_system decides what that means
         }
     }
 }
 
+/// Preparation phase hotkeys: SPACE starts strategic wave 1, T toggles turret type
+pub fn wave_input_system(
+    mut wave_manager: ResMut<WaveManager>,
+    scenario_state: Res<ScenarioState>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut game_commands: EventWriter<GameCommand>,
+) {
+    if !scenario_state.active || wave_manager.wave_state != WaveState::Preparation {
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Space) {
+        game_commands.write(GameCommand::StartWaves);
+    }
+    // Turret type only affects what the next placement command asks for
+    if keys.just_pressed(KeyCode::KeyT) {
+        wave_manager.place_mg_turret = !wave_manager.place_mg_turret;
+        let turret_type = if wave_manager.place_mg_turret { "MG" } else { "Heavy" };
+        info!("Turret type: {}", turret_type);
+    }
+}
+
+/// End the preparation phase and launch strategic wave 1
+pub fn end_preparation(wave_manager: &mut WaveManager, definition: &ScenarioDefinition) {
+    if wave_manager.wave_state != WaveState::Preparation {
+        return;
+    }
+    start_strategic_wave(wave_manager, definition, 1);
+    info!("Preparation complete! Strategic Wave 1 starting!");
+}
+
 /// Helper to start a new strategic wave
-pub fn start_strategic_wave(wave_manager: &mut WaveManager, strategic_wave_num: u32) {
+pub fn start_strategic_wave(wave_manager: &mut WaveManager, definition: &ScenarioDefinition, strategic_wave_num: u32) {
     wave_manager.strategic_wave = strategic_wave_num;
-    wave_manager.tactical_wave = 1;
+    wave_manager.total_tactical_waves = definition.assault(strategic_wave_num)
+        .map_or(0, |assault| assault.waves.len() as u32);
     wave_manager.wave_state = WaveState::Combat;
-    wave_manager.spawning_active = true;
-    wave_manager.enemies_spawned = 0;
-    wave_manager.wave_target = WAVE_SIZES[0];
-    wave_manager.enemies_remaining = wave_manager.wave_target;
+    start_tactical_wave(wave_manager, definition, 1);
+}
+
+/// Begin spawning tactical wave `tactical_wave_num` of the current strategic wave
+fn start_tactical_wave(wave_manager: &mut WaveManager, definition: &ScenarioDefinition, tactical_wave_num: u32) {
+    let wave = definition.tactical_wave(wave_manager.strategic_wave, tactical_wave_num);
+    wave_manager.tactical_wave = tactical_wave_num.min(wave_manager.total_tactical_waves);
+    wave_manager.squads_spawned = 0;
+    wave_manager.wave_squads = wave.map_or(0, |wave| wave.squad_count());
+    wave_manager.spawning_active = wave_manager.wave_squads > 0;
 }
 
 /// Progressive spawning of enemies during wave
@@ -119,8 +146,9 @@ pub fn wave_spawner_system(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut squad_manager: ResMut<SquadManager>,
+    mut archetypes: ResMut<UnitArchetypes>,
+    mut sim_rng: ResMut<SimRng>,
     heightmap: Res<TerrainHeightmap>,
-    bunker_query: Query<&Transform, With<CommandBunker>>,
 ) {
     if !scenario_state.active || wave_manager.wave_state != WaveState::Combat {
         return;
@@ -131,42 +159,31 @@ pub fn wave_spawner_system(
         return;
     }
 
-    // Get bunker position for enemy targeting
-    let bunker_pos = bunker_query.single().map(|t| t.translation).unwrap_or(Vec3::ZERO);
+    let definition = &scenario_state.definition;
+
+    // Enemies march on the objective the player defends
+    let target_pos = definition.defended_position();
+    let target_pos = Vec3::new(target_pos.x, heightmap.sample_height(target_pos.x, target_pos.z), target_pos.z);
 
     // Tick spawn timer
     wave_manager.spawn_timer.tick(time.delta());
 
-    // Spawn enemies when timer fires (spawn 1 squad = 50 units per tick)
-    if wave_manager.spawn_timer.finished() && wave_manager.enemies_spawned < wave_manager.wave_target {
+    // Spawn one squad each time the timer fires
+    if wave_manager.spawn_timer.finished() && wave_manager.squads_spawned < wave_manager.wave_squads {
         wave_manager.spawn_timer.reset();
 
-        // Spawn one squad at a time (50 units)
-        let squad_size = 50u32;
-        let remaining = wave_manager.wave_target - wave_manager.enemies_spawned;
-        if remaining == 0 {
+        // The wave's groups spawn in order: find the group this squad belongs to
+        let squad_num = wave_manager.squads_spawned;
+        let Some(group) = definition
+            .tactical_wave(wave_manager.strategic_wave, wave_manager.tactical_wave)
+            .and_then(|wave| wave.group_for_squad(squad_num))
+        else {
+            wave_manager.spawning_active = false;
             return;
-        }
-
-        // Determine spawn location based on wave number and progress
-        let squad_num = wave_manager.enemies_spawned / squad_size;
-        let is_final_strategic_wave = wave_manager.strategic_wave == wave_manager.total_strategic_waves;
-        let is_final_tactical_wave = wave_manager.tactical_wave == wave_manager.total_tactical_waves;
-
-        let base_spawn_pos = if is_final_strategic_wave && is_final_tactical_wave {
-            // Final tactical wave of final strategic wave: first 2 squads from east (flanking), rest from north
-            if squad_num < 2 {
-                EAST_SPAWN
-            } else {
-                NORTH_SPAWN
-            }
-        } else {
-            // Other waves: north spawn only
-            NORTH_SPAWN
         };
+        let base_spawn_pos = definition.spawn_point(&group.spawn).map_or(Vec3::ZERO, |spawn| spawn.position);
 
         // Add some spread to spawn position so squads don't stack
-        let squad_num = wave_manager.enemies_spawned / squad_size;
         let spread_offset = Vec3::new(
             ((squad_num % 5) as f32 - 2.0) * 15.0, // -30 to +30 spread on X
             0.0,
@@ -177,62 +194,46 @@ pub fn wave_spawner_system(
         let spawn_y = heightmap.sample_height(spawn_x, spawn_z);
         let spawn_pos = Vec3::new(spawn_x, spawn_y, spawn_z);
 
-        // Face toward bunker
-        let facing = (bunker_pos - spawn_pos).normalize();
+        // Face toward the target
+        let facing = (target_pos - spawn_pos).normalize_or(Vec3::Z);
 
-        // Create materials for enemy team (Team B)
-        let unit_materials = create_team_materials(&mut materials, Team::B);
-        let droid_mesh = create_droid_mesh(&mut meshes);
+        // Create materials for the enemy team
+        let unit_materials = create_team_materials(&mut materials, definition.enemy_team);
 
         // Spawn the squad
         let squad_id = spawn_single_squad(
             &mut commands,
             &mut squad_manager,
-            &droid_mesh,
+            &mut archetypes,
+            &group.archetype,
+            &mut meshes,
             &unit_materials,
             &mut materials,
-            Team::B,
+            definition.enemy_team,
             spawn_pos,
             facing,
             &heightmap,
+            &mut *sim_rng,
         );
 
-        // Set squad target to bunker position (movement happens when target != center)
-        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
-            squad.target_position = bunker_pos;
-            // Also set facing direction toward bunker
-            squad.target_facing_direction = facing;
-        }
-
-        // Add WaveEnemy and NeedsMoveOrder markers to all units in this squad
-        // NeedsMoveOrder will be processed next frame by wave_enemy_move_order_system
-        if let Some(squad) = squad_manager.get_squad(squad_id) {
-            for &unit_entity in &squad.members {
-                commands.entity(unit_entity).insert((
-                    WaveEnemy {
-                        wave_number: wave_manager.tactical_wave,
-                    },
-                    NeedsMoveOrder,
-                ));
-            }
-        }
+        send_wave_squad(&mut commands, &mut wave_manager, &mut squad_manager, squad_id, target_pos);
 
-        wave_manager.enemies_spawned += squad_size.min(remaining);
+        wave_manager.squads_spawned += 1;
 
         // Log every 4th squad to reduce spam
-        if (wave_manager.enemies_spawned / squad_size) % 4 == 0 {
-            info!("S{}/T{}: Spawned squad ({}/{} enemies)",
+        if wave_manager.squads_spawned % 4 == 0 {
+            info!("S{}/T{}: Spawned squad ({}/{} squads)",
                 wave_manager.strategic_wave, wave_manager.tactical_wave,
-                wave_manager.enemies_spawned, wave_manager.wave_target);
+                wave_manager.squads_spawned, wave_manager.wave_squads);
         }
 
         // When done spawning this tactical wave, deactivate spawning and start next wave timer
-        if wave_manager.enemies_spawned >= wave_manager.wave_target {
+        if wave_manager.squads_spawned >= wave_manager.wave_squads {
             wave_manager.spawning_active = false;
             wave_manager.next_wave_timer.reset();
             if wave_manager.tactical_wave < wave_manager.total_tactical_waves {
                 info!("S{}/T{} fully spawned, next tactical wave in {:.0}s",
-                    wave_manager.strategic_wave, wave_manager.tactical_wave, INTER_WAVE_DELAY);
+                    wave_manager.strategic_wave, wave_manager.tactical_wave, definition.wave_delay);
             } else {
                 info!("S{}/T{} fully spawned (final tactical wave of this assault!)",
                     wave_manager.strategic_wave, wave_manager.tactical_wave);
@@ -241,6 +242,102 @@ pub fn wave_spawner_system(
     }
 }
 
+/// Order a freshly spawned enemy squad to attack `target_pos` as part of the current wave
+pub(super) fn send_wave_squad(
+    commands: &mut Commands,
+    wave_manager: &mut WaveManager,
+    squad_manager: &mut SquadManager,
+    squad_id: u32,
+    target_pos: Vec3,
+) {
+    let Some(squad) = squad_manager.get_squad_mut(squad_id) else {
+        return;
+    };
+
+    // Set squad target to the objective (movement happens when target != center)
+    squad.target_position = target_pos;
+    // Also set facing direction toward the objective
+    squad.target_facing_direction = (target_pos - squad.center_position).normalize_or(Vec3::Z);
+
+    // Add WaveEnemy and NeedsMoveOrder markers to all units in this squad
+    // NeedsMoveOrder will be processed next frame by wave_enemy_move_order_system
+    for &unit_entity in &squad.members {
+        commands.entity(unit_entity).insert((
+            WaveEnemy {
+                wave_number: wave_manager.tactical_wave,
+            },
+            NeedsMoveOrder,
+        ));
+    }
+    wave_manager.enemies_remaining += squad.members.len() as u32;
+}
+
+/// Spawn `count` squads of `team` side by side at `base_spawn_pos`, facing `toward`
+/// Returns the new squad IDs
+pub(super) fn spawn_squad_line(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    squad_manager: &mut SquadManager,
+    archetypes: &mut UnitArchetypes,
+    sim_rng: &mut SimRng,
+    heightmap: &TerrainHeightmap,
+    team: Team,
+    archetype: &str,
+    count: u32,
+    base_spawn_pos: Vec3,
+    toward: Vec3,
+) -> Vec<u32> {
+    let unit_materials = create_team_materials(materials, team);
+
+    let facing = Vec3::new(toward.x - base_spawn_pos.x, 0.0, toward.z - base_spawn_pos.z)
+        .normalize_or(Vec3::NEG_Z);
+    let across = Vec3::new(-facing.z, 0.0, facing.x);
+
+    (0..count).map(|i| {
+        // Spread squads slightly apart, side by side
+        let offset = across * (i as f32 - (count - 1) as f32 / 2.0) * 20.0;
+        let spawn_x = base_spawn_pos.x + offset.x;
+        let spawn_z = base_spawn_pos.z + offset.z;
+        let spawn_pos = Vec3::new(spawn_x, heightmap.sample_height(spawn_x, spawn_z), spawn_z);
+
+        spawn_single_squad(
+            commands,
+            squad_manager,
+            archetypes,
+            archetype,
+            meshes,
+            &unit_materials,
+            materials,
+            team,
+            spawn_pos,
+            facing,
+            heightmap,
+            sim_rng,
+        )
+    }).collect()
+}
+
+/// Surviving squads keep their veterancy; the experience of wiped-out squads of `team`
+/// passes to the new `squad_ids`, best first
+pub(super) fn inherit_fallen_records(squad_manager: &mut SquadManager, team: Team, squad_ids: &[u32]) {
+    let mut fallen_veterans: Vec<(u32, u32)> = squad_manager.squads.values()
+        .filter(|squad| squad.team == team && squad.members.is_empty() && squad.kills > 0)
+        .map(|squad| (squad.id, squad.kills))
+        .collect();
+    fallen_veterans.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    for (&squad_id, (fallen_id, kills)) in squad_ids.iter().zip(fallen_veterans) {
+        if let Some(fallen) = squad_manager.get_squad_mut(fallen_id) {
+            fallen.kills = 0;
+        }
+        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
+            squad.kills = kills;
+            info!("Squad {} carries on the record of fallen squad {} ({})", squad_id, fallen_id, squad.rank_name());
+        }
+    }
+}
+
 /// Spawn player reinforcements during strategic cooldown
 pub fn reinforcement_spawner_system(
     mut wave_manager: ResMut<WaveManager>,
@@ -249,6 +346,8 @@ pub fn reinforcement_spawner_system(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut squad_manager: ResMut<SquadManager>,
+    mut archetypes: ResMut<UnitArchetypes>,
+    mut sim_rng: ResMut<SimRng>,
     heightmap: Res<TerrainHeightmap>,
 ) {
     // Only spawn during strategic cooldown and if not already spawned
@@ -262,37 +361,37 @@ pub fn reinforcement_spawner_system(
     // Mark as spawned so we don't spawn again this cooldown
     wave_manager.reinforcements_spawned = true;
 
-    // Create materials and mesh for friendly units
-    let droid_mesh = create_droid_mesh(&mut meshes);
-    let unit_materials = create_team_materials(&mut materials, Team::A);
-
-    // Get spawn position height
-    let spawn_y = heightmap.sample_height(SOUTH_SPAWN.x, SOUTH_SPAWN.z);
-    let base_spawn_pos = Vec3::new(SOUTH_SPAWN.x, spawn_y, SOUTH_SPAWN.z);
-
-    // Facing direction (toward the bunker/north)
-    let facing = Vec3::new(0.0, 0.0, -1.0);
-
-    for i in 0..REINFORCEMENT_SQUADS {
-        // Spread squads slightly apart
-        let offset = Vec3::new((i as f32 - 0.5) * 20.0, 0.0, 0.0);
-        let spawn_pos = base_spawn_pos + offset;
+    let definition = &scenario_state.definition;
+    let Some(assault) = definition.assault(wave_manager.strategic_wave) else {
+        return;
+    };
+    let defended_pos = definition.defended_position();
+    let mut spawned = Vec::new();
 
-        spawn_single_squad(
+    for group in &assault.reinforcements {
+        let Some(spawn_point) = definition.spawn_point(&group.spawn) else {
+            continue;
+        };
+        spawned.extend(spawn_squad_line(
             &mut commands,
-            &mut squad_manager,
-            &droid_mesh,
-            &unit_materials,
+            &mut meshes,
             &mut materials,
-            Team::A,
-            spawn_pos,
-            facing,
+            &mut squad_manager,
+            &mut archetypes,
+            &mut sim_rng,
             &heightmap,
-        );
+            definition.player_team,
+            &group.archetype,
+            group.squads,
+            spawn_point.position,
+            defended_pos,
+        ));
     }
+    inherit_fallen_records(&mut squad_manager, definition.player_team, &spawned);
 
-    info!("Reinforcements arrived! {} squads spawned at south spawn point",
-        REINFORCEMENT_SQUADS);
+    if !spawned.is_empty() {
+        info!("Reinforcements arrived! {} squads spawned", spawned.len());
+    }
 }
 
 /// Track enemy deaths and update wave manager
@@ -336,7 +435,6 @@ pub fn wave_enemy_move_order_system(
     scenario_state: Res<ScenarioState>,
     squad_manager: Res<SquadManager>,
     heightmap: Res<TerrainHeightmap>,
-    bunker_query: Query<&Transform, With<CommandBunker>>,
     mut needs_order_query: Query<
         (Entity, &SquadMember, &mut BattleDroid, &mut FormationOffset),
         With<NeedsMoveOrder>,
@@ -346,14 +444,9 @@ pub fn wave_enemy_move_order_system(
         return;
     }
 
-    // Ensure bunker exists before processing
-    let Ok(_bunker_transform) = bunker_query.single() else {
-        return;
-    };
-
     // Process all units that need move orders
     for (entity, squad_member, mut droid, mut formation_offset) in needs_order_query.iter_mut() {
-        // Get the squad's target position (should be bunker)
+        // Get the squad's target position (the defended objective)
         if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
             // Calculate this unit's target position (squad target + formation offset)
             let target_xz = squad.target_position + formation_offset.local_offset;
@@ -370,55 +463,89 @@ pub fn wave_enemy_move_order_system(
     }
 }
 
-/// Check victory and defeat conditions
+/// Check the scenario's victory and defeat rules (a trigger's Victory/Defeat action wins over both)
 pub fn victory_defeat_check_system(
     wave_manager: Res<WaveManager>,
-    scenario_state: Res<ScenarioState>,
+    trigger_state: Res<TriggerState>,
+    mut scenario_state: ResMut<ScenarioState>,
     mut game_state: ResMut<GameState>,
-    bunker_query: Query<&Transform, With<CommandBunker>>,
+    squad_manager: Res<SquadManager>,
+    objective_query: Query<(&Transform, &ObjectiveTarget, &Health)>,
     enemy_query: Query<&Transform, With<WaveEnemy>>,
 ) {
-    use super::{ScenarioType, BREACH_RADIUS, BREACH_THRESHOLD};
-
-    if !scenario_state.active || game_state.game_ended {
+    if !scenario_state.active || game_state.game_ended || wave_manager.wave_state == WaveState::Idle {
         return;
     }
 
-    // Victory: all strategic waves complete
-    if wave_manager.wave_state == WaveState::Complete {
-        info!("VICTORY! All {} assaults repelled!", wave_manager.total_strategic_waves);
-        game_state.game_ended = true;
-        game_state.winner = Some(Team::A);
-        return;
-    }
-
-    // Defeat: bunker destroyed
-    if bunker_query.is_empty() && scenario_state.scenario_type == ScenarioType::FirebaseDelta {
-        // Only trigger if we've started (bunker should exist after initialization)
-        if wave_manager.strategic_wave > 0 {
-            info!("DEFEAT! Command bunker destroyed!");
-            game_state.game_ended = true;
-            game_state.winner = Some(Team::B);
-            return;
-        }
-    }
+    let definition = &scenario_state.definition;
+    let (player_team, enemy_team) = (definition.player_team, definition.enemy_team);
 
-    // Defeat: bunker breach (too many enemies near bunker)
-    if let Ok(bunker_transform) = bunker_query.single() {
-        let bunker_pos = bunker_transform.translation;
-        let mut enemies_in_radius = 0;
+    // Primary objectives still standing, per side
+    let standing = |team: Team| objective_query.iter()
+        .filter(move |(_, objective, health)| objective.team == team && objective.is_primary && !health.is_dead());
+    let player_objectives_standing = standing(player_team).count();
+    let enemy_objectives_standing = standing(enemy_team).count();
 
-        for enemy_transform in enemy_query.iter() {
-            let dist = (enemy_transform.translation - bunker_pos).length();
-            if dist <= BREACH_RADIUS {
-                enemies_in_radius += 1;
-            }
+    let victory = definition.victory.iter().find_map(|rule| match *rule {
+        VictoryRule::SurviveAllAssaults if wave_manager.wave_state == WaveState::Complete => {
+            Some(format!("All {} assaults repelled!", wave_manager.total_strategic_waves))
+        }
+        VictoryRule::DestroyEnemyObjectives
+            if definition.primary_objective_count(enemy_team) > 0 && enemy_objectives_standing == 0 =>
+        {
+            Some("Enemy objectives destroyed!".to_string())
+        }
+        VictoryRule::HoldOut { seconds } if wave_manager.battle_time >= seconds => {
+            Some(format!("Held out for {:.0}s!", seconds))
         }
+        VictoryRule::Points { target } if game_state.points[player_team.index()] >= target => {
+            Some(format!("{:.0} victory points scored!", target))
+        }
+        _ => None,
+    });
 
-        if enemies_in_radius >= BREACH_THRESHOLD {
-            info!("DEFEAT! Command bunker breached! ({} enemies inside perimeter)", enemies_in_radius);
-            game_state.game_ended = true;
-            game_state.winner = Some(Team::B);
+    let defeat = || definition.defeat.iter().find_map(|rule| match *rule {
+        DefeatRule::ObjectiveLost if player_objectives_standing < definition.primary_objective_count(player_team) => {
+            Some("Objective destroyed!".to_string())
         }
-    }
+        DefeatRule::Breach { radius, threshold } => {
+            // Too many enemies inside the perimeter of any primary objective
+            standing(player_team).find_map(|(transform, _, _)| {
+                let enemies_in_radius = enemy_query.iter()
+                    .filter(|enemy| enemy.translation.distance(transform.translation) <= radius)
+                    .count() as u32;
+                (enemies_in_radius >= threshold)
+                    .then(|| format!("Perimeter breached! ({} enemies inside)", enemies_in_radius))
+            })
+        }
+        DefeatRule::ArmyDestroyed
+            if wave_manager.strategic_wave > 0
+                && !squad_manager.squads.values().any(|squad| squad.team == player_team && !squad.members.is_empty()) =>
+        {
+            Some("All squads lost!".to_string())
+        }
+        DefeatRule::EnemyPoints { target } if game_state.points[enemy_team.index()] >= target => {
+            Some(format!("The enemy scored {:.0} victory points!", target))
+        }
+        _ => None,
+    });
+
+    let (winner, outcome) = if let Some(TriggerOutcome::Victory(reason)) = &trigger_state.outcome {
+        info!("VICTORY! {}", reason);
+        (player_team, format!("VICTORY! {}", reason))
+    } else if let Some(TriggerOutcome::Defeat(reason)) = &trigger_state.outcome {
+        info!("DEFEAT! {}", reason);
+        (enemy_team, format!("DEFEAT! {}", reason))
+    } else if let Some(reason) = victory {
+        info!("VICTORY! {}", reason);
+        (player_team, format!("VICTORY! {}", reason))
+    } else if let Some(reason) = defeat() {
+        info!("DEFEAT! {}", reason);
+        (enemy_team, format!("DEFEAT! {}", reason))
+    } else {
+        return;
+    };
+
+    game_state.end(Some(winner), outcome.clone());
+    scenario_state.outcome = Some(outcome);
 }
@@ -2,6 +2,7 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 use crate::types::*;
+use crate::game_command::GameCommand;
 
 use super::state::SelectionState;
 
@@ -19,13 +20,18 @@ pub struct SquadGroup {
 /// Check if the current selection is exactly one complete group (all living squads in a group are selected)
 /// Uses squad_manager to filter out dead squads (those with no members)
 pub fn check_is_complete_group(selection_state: &SelectionState, squad_manager: &SquadManager) -> Option<u32> {
-    if selection_state.selected_squads.is_empty() {
+    find_complete_group(selection_state, &selection_state.selected_squads, squad_manager)
+}
+
+/// Check if the given squads are exactly one complete group (all of its living squads included)
+pub fn find_complete_group(selection_state: &SelectionState, squad_ids: &[u32], squad_manager: &SquadManager) -> Option<u32> {
+    if squad_ids.is_empty() {
         return None;
     }
 
-    // Check if all selected squads belong to the same group
+    // Check if all squads belong to the same group
     let mut group_id: Option<u32> = None;
-    for &squad_id in &selection_state.selected_squads {
+    for &squad_id in squad_ids {
         if let Some(&gid) = selection_state.squad_to_group.get(&squad_id) {
             if group_id.is_none() {
                 group_id = Some(gid);
@@ -57,7 +63,7 @@ pub fn check_is_complete_group(selection_state: &SelectionState, squad_manager:
             }
 
             let all_living_in_selection = living_squad_ids.iter()
-                .all(|id| selection_state.selected_squads.contains(id));
+                .all(|id| squad_ids.contains(id));
             if all_living_in_selection {
                 return Some(gid);
             }
@@ -70,8 +76,8 @@ pub fn check_is_complete_group(selection_state: &SelectionState, squad_manager:
 /// System: Handle group toggle with G key (group if ungrouped, ungroup if grouped)
 pub fn group_command_system(
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut selection_state: ResMut<SelectionState>,
-    squad_manager: Res<SquadManager>,
+    selection_state: Res<SelectionState>,
+    mut game_commands: EventWriter<GameCommand>,
 ) {
     // Toggle group with G (or U to ungroup)
     if keyboard.just_pressed(KeyCode::KeyG) || keyboard.just_pressed(KeyCode::KeyU) {
@@ -92,98 +98,108 @@ pub fn group_command_system(
 
         // If U is pressed or all are already grouped together, ungroup
         if keyboard.just_pressed(KeyCode::KeyU) || all_in_same_group {
-            let mut ungrouped_count = 0;
-
-            for &squad_id in &selected_squads {
-                if let Some(&group_id) = selection_state.squad_to_group.get(&squad_id) {
-                    selection_state.squad_to_group.remove(&squad_id);
-                    ungrouped_count += 1;
-
-                    // Remove from group
-                    if let Some(group) = selection_state.groups.get_mut(&group_id) {
-                        group.squad_ids.retain(|&id| id != squad_id);
-                        group.squad_offsets.remove(&squad_id);
-                    }
-                }
-            }
+            game_commands.write(GameCommand::Ungroup { squad_ids: selected_squads });
+        } else if selected_squads.len() >= 2 {
+            game_commands.write(GameCommand::Group { squad_ids: selected_squads });
+        } else if selected_squads.len() == 1 {
+            info!("Need at least 2 squads to create a group");
+        }
+    }
+}
 
-            // Clean up empty groups
-            selection_state.groups.retain(|_, group| !group.squad_ids.is_empty());
+/// Remove squads from their groups (empty groups are dropped)
+pub fn execute_ungroup(selection_state: &mut SelectionState, squad_ids: &[u32]) {
+    let mut ungrouped_count = 0;
 
-            if ungrouped_count > 0 {
-                info!("Ungrouped {} squads", ungrouped_count);
-            }
-        } else if selected_squads.len() >= 2 {
-            // Group the selected squads
-
-            // Remove existing group memberships for selected squads
-            for &squad_id in &selected_squads {
-                if let Some(&old_group_id) = selection_state.squad_to_group.get(&squad_id) {
-                    selection_state.squad_to_group.remove(&squad_id);
-
-                    // Remove from old group's squad list
-                    if let Some(old_group) = selection_state.groups.get_mut(&old_group_id) {
-                        old_group.squad_ids.retain(|&id| id != squad_id);
-                        old_group.squad_offsets.remove(&squad_id);
-                    }
-                }
+    for &squad_id in squad_ids {
+        if let Some(&group_id) = selection_state.squad_to_group.get(&squad_id) {
+            selection_state.squad_to_group.remove(&squad_id);
+            ungrouped_count += 1;
+
+            // Remove from group
+            if let Some(group) = selection_state.groups.get_mut(&group_id) {
+                group.squad_ids.retain(|&id| id != squad_id);
+                group.squad_offsets.remove(&squad_id);
             }
+        }
+    }
 
-            // Clean up empty groups
-            selection_state.groups.retain(|_, group| !group.squad_ids.is_empty());
+    // Clean up empty groups
+    selection_state.groups.retain(|_, group| !group.squad_ids.is_empty());
 
-            // Calculate group center from squad positions
-            let mut group_center = Vec3::ZERO;
-            let mut valid_squad_count = 0;
-            let mut avg_facing = Vec3::ZERO;
+    if ungrouped_count > 0 {
+        info!("Ungrouped {} squads", ungrouped_count);
+    }
+}
 
-            for &squad_id in &selected_squads {
-                if let Some(squad) = squad_manager.get_squad(squad_id) {
-                    group_center += squad.center_position;
-                    avg_facing += squad.facing_direction;
-                    valid_squad_count += 1;
-                }
+/// Group squads into a new formation, preserving their current relative positions
+pub fn execute_group(selection_state: &mut SelectionState, squad_manager: &SquadManager, squad_ids: &[u32]) {
+    // Remove existing group memberships for these squads
+    for &squad_id in squad_ids {
+        if let Some(&old_group_id) = selection_state.squad_to_group.get(&squad_id) {
+            selection_state.squad_to_group.remove(&squad_id);
+
+            // Remove from old group's squad list
+            if let Some(old_group) = selection_state.groups.get_mut(&old_group_id) {
+                old_group.squad_ids.retain(|&id| id != squad_id);
+                old_group.squad_offsets.remove(&squad_id);
             }
+        }
+    }
 
-            if valid_squad_count > 0 {
-                group_center /= valid_squad_count as f32;
-                avg_facing = avg_facing.normalize_or_zero();
-
-                // Calculate offsets for each squad
-                let mut squad_offsets = HashMap::new();
-                let mut squad_ids = Vec::new();
-
-                for &squad_id in &selected_squads {
-                    if let Some(squad) = squad_manager.get_squad(squad_id) {
-                        let offset = squad.center_position - group_center;
-                        squad_offsets.insert(squad_id, offset);
-                        squad_ids.push(squad_id);
-                    }
-                }
-
-                // Create new group
-                let group_id = selection_state.next_group_id;
-                selection_state.next_group_id += 1;
-
-                let group = SquadGroup {
-                    id: group_id,
-                    squad_ids: squad_ids.clone(),
-                    squad_offsets,
-                    original_formation_facing: avg_facing,  // Store original facing (never changes)
-                    formation_facing: avg_facing,            // Current facing (will be updated on moves)
-                };
-
-                selection_state.groups.insert(group_id, group);
-
-                // Update squad-to-group mapping
-                for squad_id in squad_ids {
-                    selection_state.squad_to_group.insert(squad_id, group_id);
-                }
-
-                info!("Created group {} with {} squads", group_id, valid_squad_count);
-            }
-        } else if selected_squads.len() == 1 {
-            info!("Need at least 2 squads to create a group");
+    // Clean up empty groups
+    selection_state.groups.retain(|_, group| !group.squad_ids.is_empty());
+
+    // Calculate group center from squad positions
+    let mut group_center = Vec3::ZERO;
+    let mut valid_squad_count = 0;
+    let mut avg_facing = Vec3::ZERO;
+
+    for &squad_id in squad_ids {
+        if let Some(squad) = squad_manager.get_squad(squad_id) {
+            group_center += squad.center_position;
+            avg_facing += squad.facing_direction;
+            valid_squad_count += 1;
+        }
+    }
+
+    if valid_squad_count == 0 {
+        return;
+    }
+
+    group_center /= valid_squad_count as f32;
+    avg_facing = avg_facing.normalize_or_zero();
+
+    // Calculate offsets for each squad
+    let mut squad_offsets = HashMap::new();
+    let mut member_ids = Vec::new();
+
+    for &squad_id in squad_ids {
+        if let Some(squad) = squad_manager.get_squad(squad_id) {
+            let offset = squad.center_position - group_center;
+            squad_offsets.insert(squad_id, offset);
+            member_ids.push(squad_id);
         }
     }
+
+    // Create new group
+    let group_id = selection_state.next_group_id;
+    selection_state.next_group_id += 1;
+
+    let group = SquadGroup {
+        id: group_id,
+        squad_ids: member_ids.clone(),
+        squad_offsets,
+        original_formation_facing: avg_facing,  // Store original facing (never changes)
+        formation_facing: avg_facing,            // Current facing (will be updated on moves)
+    };
+
+    selection_state.groups.insert(group_id, group);
+
+    // Update squad-to-group mapping
+    for squad_id in member_ids {
+        selection_state.squad_to_group.insert(squad_id, group_id);
+    }
+
+    info!("Created group {} with {} squads", group_id, valid_squad_count);
 }
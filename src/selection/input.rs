@@ -5,6 +5,7 @@ use crate::types::*;
 use crate::constants::*;
 use crate::terrain::TerrainHeightmap;
 use crate::artillery::{ArtilleryState, ArtilleryMode};
+use crate::game_command::GameCommand;
 
 use super::state::{SelectionState, SelectionVisual};
 use super::utils::{screen_to_ground_with_heightmap, calculate_squad_centers, find_squad_at_position};
@@ -41,8 +42,8 @@ pub fn selection_input_system(
 
     let hm = heightmap.as_ref().map(|h| h.as_ref());
 
-    // Handle left mouse button press - start selection or box select
-    if mouse_button.just_pressed(MouseButton::Left) {
+    // Handle left mouse button press - start selection or box select (the minimap handles its own clicks)
+    if mouse_button.just_pressed(MouseButton::Left) && !crate::minimap::cursor_over_minimap(window) {
         // Get world position for potential box select start
         if let Some(world_pos) = screen_to_ground_with_heightmap(cursor_pos, camera, camera_transform, hm) {
             selection_state.drag_start_world = Some(world_pos);
@@ -332,7 +333,8 @@ pub fn hold_command_system(
     keyboard: Res<ButtonInput<KeyCode>>,
     selection_state: Res<SelectionState>,
     squad_manager: Res<SquadManager>,
-    mut droid_query: Query<(&SquadMember, &mut MovementMode), With<BattleDroid>>,
+    droid_query: Query<(&SquadMember, &MovementMode), With<BattleDroid>>,
+    mut game_commands: EventWriter<GameCommand>,
 ) {
     if keyboard.just_pressed(KeyCode::KeyH) {
         if selection_state.selected_squads.is_empty() {
@@ -364,21 +366,34 @@ pub fn hold_command_system(
             MovementMode::Hold
         };
 
-        // Apply new mode to all units in selected squads
-        let mut units_affected = 0;
-        for (squad_member, mut mode) in droid_query.iter_mut() {
-            if selection_state.selected_squads.contains(&squad_member.squad_id) {
-                *mode = new_mode;
-                units_affected += 1;
-            }
-        }
+        game_commands.write(GameCommand::SetMovementMode {
+            squad_ids: selection_state.selected_squads.clone(),
+            mode: new_mode,
+        });
+    }
+}
 
-        if units_affected > 0 {
-            info!(
-                "Toggled {} units to {:?} mode",
-                units_affected,
-                new_mode
-            );
-        }
+/// System: Z cycles the formation of the selected squads
+pub fn formation_command_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    selection_state: Res<SelectionState>,
+    squad_manager: Res<SquadManager>,
+    mut game_commands: EventWriter<GameCommand>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyZ) {
+        return;
     }
+
+    // Every selected squad takes the formation after the first one's, so mixed selections line up
+    let Some(current) = selection_state.selected_squads.iter()
+        .find_map(|&id| squad_manager.get_squad(id))
+        .map(|squad| squad.formation_type)
+    else {
+        return;
+    };
+
+    game_commands.write(GameCommand::SetFormation {
+        squad_ids: selection_state.selected_squads.clone(),
+        formation: current.next(),
+    });
 }
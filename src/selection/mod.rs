@@ -20,12 +20,13 @@ mod ui;
 
 // Re-export main types for external use
 pub use state::SelectionState;
+pub use groups::SquadGroup;
 pub use utils::screen_to_ground_with_heightmap;
 
 // Re-export systems for main.rs
-pub use input::{selection_input_system, box_selection_update_system, hold_command_system, update_hovered_squad_system};
-pub use movement::move_command_system;
-pub use groups::group_command_system;
+pub use input::{selection_input_system, box_selection_update_system, hold_command_system, formation_command_system, update_hovered_squad_system};
+pub use movement::{move_command_system, execute_move_squads, execute_set_facing, execute_set_movement_mode};
+pub use groups::{group_command_system, execute_group, execute_ungroup};
 pub use visuals::{
     selection_visual_system,
     move_visual_cleanup_system,
@@ -36,3 +37,41 @@ pub use visuals::{
     update_squad_path_arrows,
 };
 pub use ui::{spawn_squad_details_ui, update_squad_details_ui, SquadCombatCache, spawn_turret_details_ui, update_turret_details_ui, TurretCombatCache, UiUpdateTimer};
+
+use bevy::prelude::*;
+use crate::game_command::PlayerInputSet;
+
+/// RTS selection and command input plus all selection feedback visuals/UI
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SelectionState::default())
+            .insert_resource(SquadCombatCache::default())
+            .insert_resource(TurretCombatCache::default())
+            .insert_resource(UiUpdateTimer::default())
+            .add_systems(Update, (
+                // Selection and command systems (Total War style controls)
+                selection_input_system,
+                box_selection_update_system,
+                // Orders are emitted as GameCommands and applied by the executor
+                move_command_system.in_set(PlayerInputSet),
+                group_command_system.in_set(PlayerInputSet),
+                hold_command_system.in_set(PlayerInputSet),
+                formation_command_system.in_set(PlayerInputSet),
+                update_hovered_squad_system,
+            ).chain())
+            .add_systems(Update, (
+                // Selection visual feedback
+                selection_visual_system,
+                move_visual_cleanup_system,
+                orientation_arrow_system,
+                box_selection_visual_system,
+                update_group_orientation_markers,
+                update_group_bounding_box_debug,
+                update_squad_path_arrows,
+                update_squad_details_ui,
+                update_turret_details_ui,
+            ));
+    }
+}
@@ -1,36 +1,34 @@
 // Movement command systems
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use std::collections::VecDeque;
 use crate::types::*;
 use crate::constants::*;
-use crate::formation::calculate_formation_offset;
+use crate::formation::squad_slot_offset;
 use crate::terrain::TerrainHeightmap;
-use crate::scenario::{ScenarioState, WaveManager, WaveState};
+use crate::navigation::{FlowField, NavGrid};
+use crate::cover::CoverMap;
+use crate::game_command::GameCommand;
 
 use super::state::{SelectionState, OrientationArrowVisual};
-use super::groups::check_is_complete_group;
+use super::groups::find_complete_group;
 use super::utils::{screen_to_ground_with_heightmap, calculate_default_facing, calculate_filtered_squad_centers, horizontal_distance, horizontal_direction};
 use super::visuals::{spawn_move_indicator, spawn_move_indicator_with_color, spawn_path_line};
 
 /// System: Handle right-click move commands for selected squads
 /// Supports drag-to-set-orientation (CoH1-style)
 /// Shift+RMB = Attack Move (units stop when engaged)
-/// During preparation phase, units teleport instantly to their destination
+/// Emits GameCommand::MoveSquads; execute_move_squads applies it
 pub fn move_command_system(
     mut commands: Commands,
     mouse_button: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<RtsCamera>>,
-    mut squad_manager: ResMut<SquadManager>,
     mut selection_state: ResMut<SelectionState>,
-    mut droid_query: Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
     arrow_query: Query<Entity, With<OrientationArrowVisual>>,
     heightmap: Option<Res<TerrainHeightmap>>,
-    scenario_state: Option<Res<ScenarioState>>,
-    wave_manager: Option<Res<WaveManager>>,
+    mut game_commands: EventWriter<GameCommand>,
 ) {
     let Ok(window) = window_query.single() else { return };
     let Ok((camera, camera_transform)) = camera_query.single() else { return };
@@ -41,8 +39,8 @@ pub fn move_command_system(
     // Get current world position under cursor
     let current_world_pos = screen_to_ground_with_heightmap(cursor_pos, camera, camera_transform, hm);
 
-    // Handle right mouse button press - start potential drag
-    if mouse_button.just_pressed(MouseButton::Right) {
+    // Handle right mouse button press - start potential drag (the minimap handles its own clicks)
+    if mouse_button.just_pressed(MouseButton::Right) && !crate::minimap::cursor_over_minimap(window) {
         if !selection_state.selected_squads.is_empty() {
             if let Some(pos) = current_world_pos {
                 selection_state.move_drag_start = Some(pos);
@@ -65,86 +63,39 @@ pub fn move_command_system(
         }
     }
 
-    // Handle right mouse button release - execute move command
+    // Handle right mouse button release - issue move command
     if mouse_button.just_released(MouseButton::Right) {
         // Clean up any existing arrow visual
         for entity in arrow_query.iter() {
             commands.entity(entity).despawn();
         }
 
-        let Some(destination) = selection_state.move_drag_start else {
-            // Clear state and return
-            selection_state.move_drag_start = None;
-            selection_state.move_drag_current = None;
-            selection_state.is_orientation_dragging = false;
-            return;
-        };
-
-        if selection_state.selected_squads.is_empty() {
-            selection_state.move_drag_start = None;
-            selection_state.move_drag_current = None;
-            selection_state.is_orientation_dragging = false;
-            return;
-        }
-
-        // Determine facing direction
-        let unified_facing = if selection_state.is_orientation_dragging {
-            // Use drag direction for orientation
-            if let Some(current) = selection_state.move_drag_current {
-                let drag_dir = horizontal_direction(destination, current);
-                if drag_dir.length() > 0.1 {
-                    drag_dir.normalize()
+        if let Some(destination) = selection_state.move_drag_start {
+            if !selection_state.selected_squads.is_empty() {
+                // Drag direction sets orientation; otherwise the executor faces toward the destination
+                let facing = selection_state.move_drag_current
+                    .filter(|_| selection_state.is_orientation_dragging)
+                    .map(|current| horizontal_direction(destination, current))
+                    .filter(|drag_dir| drag_dir.length() > 0.1)
+                    .map(|drag_dir| drag_dir.normalize());
+
+                // Shift+RMB = Attack Move, regular RMB = Move
+                let shift_held = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+                let mode = if shift_held {
+                    MovementMode::AttackMove
                 } else {
-                    // Fallback to movement direction
-                    calculate_default_facing(&selection_state.selected_squads, &squad_manager, destination)
-                }
-            } else {
-                calculate_default_facing(&selection_state.selected_squads, &squad_manager, destination)
+                    MovementMode::Move
+                };
+
+                game_commands.write(GameCommand::MoveSquads {
+                    squad_ids: selection_state.selected_squads.clone(),
+                    destination,
+                    facing,
+                    mode,
+                });
             }
-        } else {
-            // No drag - use default facing (toward destination from average position)
-            calculate_default_facing(&selection_state.selected_squads, &squad_manager, destination)
-        };
-
-        // Check if we're in preparation phase - instant teleport
-        let is_prep_phase = scenario_state.as_ref()
-            .zip(wave_manager.as_ref())
-            .map(|(ss, wm)| ss.active && wm.wave_state == WaveState::Preparation)
-            .unwrap_or(false);
-
-        // Shift+RMB = Attack Move, regular RMB = Move
-        let shift_held = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
-        let new_mode = if shift_held {
-            MovementMode::AttackMove
-        } else {
-            MovementMode::Move
-        };
-
-        if is_prep_phase {
-            info!("Prep phase: Teleporting {} squads to ({:.1}, {:.1})",
-                  selection_state.selected_squads.len(), destination.x, destination.z);
-        } else {
-            let mode_str = if shift_held { "Attack Move" } else { "Move" };
-            info!("{} command to ({:.1}, {:.1}) for {} squads, orientation: ({:.2}, {:.2})",
-                  mode_str, destination.x, destination.z, selection_state.selected_squads.len(),
-                  unified_facing.x, unified_facing.z);
         }
 
-        // Execute the move command
-        execute_move_command(
-            &mut commands,
-            &mut squad_manager,
-            &mut selection_state,
-            &mut droid_query,
-            &mut meshes,
-            &mut materials,
-            destination,
-            unified_facing,
-            hm,
-            is_prep_phase,
-            new_mode,
-        );
-
         // Clear drag state
         selection_state.move_drag_start = None;
         selection_state.move_drag_current = None;
@@ -152,23 +103,179 @@ pub fn move_command_system(
     }
 }
 
-/// Execute a group move - maintains relative formation positions
-/// If instant_teleport is true, units are teleported directly to their destination
-fn execute_group_move(
+/// Move squads to a destination. Squads forming one complete group keep their relative
+/// positions; otherwise they spread into a line. With no facing given, squads face the
+/// destination from their current average position.
+/// If instant_teleport is true (preparation phase), units are teleported directly to their destination.
+pub fn execute_move_squads(
     commands: &mut Commands,
-    squad_manager: &mut ResMut<SquadManager>,
+    squad_manager: &mut SquadManager,
     selection_state: &mut SelectionState,
     droid_query: &mut Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
+    squad_ids: &[u32],
     destination: Vec3,
-    unified_facing: Vec3,
-    group_id: u32,
+    facing: Option<Vec3>,
+    new_mode: MovementMode,
     heightmap: Option<&TerrainHeightmap>,
+    nav_grid: Option<&NavGrid>,
+    cover_map: Option<&CoverMap>,
     instant_teleport: bool,
+) {
+    if squad_ids.is_empty() {
+        return;
+    }
+
+    let unified_facing = facing
+        .filter(|facing| facing.length() > 0.1)
+        .map(|facing| facing.normalize())
+        .unwrap_or_else(|| calculate_default_facing(squad_ids, squad_manager, destination));
+
+    if instant_teleport {
+        info!("Prep phase: Teleporting {} squads to ({:.1}, {:.1})",
+              squad_ids.len(), destination.x, destination.z);
+    } else {
+        let mode_str = if new_mode == MovementMode::AttackMove { "Attack Move" } else { "Move" };
+        info!("{} command to ({:.1}, {:.1}) for {} squads, orientation: ({:.2}, {:.2})",
+              mode_str, destination.x, destination.z, squad_ids.len(),
+              unified_facing.x, unified_facing.z);
+    }
+
+    let orders = resolve_move_orders(
+        commands,
+        squad_manager,
+        selection_state,
+        droid_query,
+        meshes,
+        materials,
+        squad_ids,
+        destination,
+        unified_facing,
+        heightmap,
+    );
+    let orders = match cover_map {
+        Some(cover_map) => snap_orders_to_cover(orders, cover_map, unified_facing, heightmap),
+        None => orders,
+    };
+
+    // One flow field toward the destination routes every squad (teleports need no path)
+    let flow_field = nav_grid
+        .filter(|_| !instant_teleport)
+        .and_then(|grid| grid.flow_field(destination).map(|field| (grid, field)));
+
+    apply_squad_moves(
+        commands,
+        squad_manager,
+        droid_query,
+        meshes,
+        materials,
+        &orders,
+        unified_facing,
+        heightmap,
+        flow_field.as_ref().map(|(grid, field)| (*grid, field)),
+        instant_teleport,
+        new_mode,
+    );
+}
+
+/// Move squads whose destination lies near cover to a spot behind it, facing the enemy.
+/// Each cover object takes at most one squad.
+fn snap_orders_to_cover(
+    orders: Vec<(u32, Vec3)>,
+    cover_map: &CoverMap,
+    unified_facing: Vec3,
+    heightmap: Option<&TerrainHeightmap>,
+) -> Vec<(u32, Vec3)> {
+    let mut taken: Vec<usize> = Vec::new();
+    orders.into_iter()
+        .map(|(squad_id, destination)| {
+            let Some((cover_index, spot)) = cover_map.snap_spot(destination, unified_facing, &taken) else {
+                return (squad_id, destination);
+            };
+            taken.push(cover_index);
+            let y = heightmap.map_or(destination.y, |hm| hm.sample_height(spot.x, spot.z));
+            info!("Squad {} snapped to cover at ({:.1}, {:.1})", squad_id, spot.x, spot.z);
+            (squad_id, Vec3::new(spot.x, y, spot.z))
+        })
+        .collect()
+}
+
+/// Set the movement mode of every unit in the given squads
+pub fn execute_set_movement_mode(
+    droid_query: &mut Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
+    squad_ids: &[u32],
     new_mode: MovementMode,
 ) {
-    let Some(group) = selection_state.groups.get_mut(&group_id) else { return };
+    let mut units_affected = 0;
+    for (_, _, squad_member, _, _, mut mode) in droid_query.iter_mut() {
+        if squad_ids.contains(&squad_member.squad_id) {
+            *mode = new_mode;
+            units_affected += 1;
+        }
+    }
+
+    if units_affected > 0 {
+        info!(
+            "Toggled {} units to {:?} mode",
+            units_affected,
+            new_mode
+        );
+    }
+}
+
+/// Turn squads to a new facing in place: units re-form around the squad's current target
+pub fn execute_set_facing(
+    squad_manager: &mut SquadManager,
+    droid_query: &mut Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
+    squad_ids: &[u32],
+    facing: Vec3,
+    heightmap: Option<&TerrainHeightmap>,
+) {
+    let facing = Vec3::new(facing.x, 0.0, facing.z);
+    if facing.length() < 0.1 {
+        return;
+    }
+    let facing = facing.normalize();
+
+    for &squad_id in squad_ids {
+        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
+            squad.target_facing_direction = facing;
+            squad.facing_direction = facing;
+        }
+    }
+
+    for (_entity, mut droid, squad_member, _, _, _) in droid_query.iter_mut() {
+        if !squad_ids.contains(&squad_member.squad_id) {
+            continue;
+        }
+        let Some(squad) = squad_manager.get_squad(squad_member.squad_id) else { continue };
+
+        let new_offset = squad_slot_offset(squad, squad_member.formation_position);
+        let target_xz = squad.target_position + new_offset;
+        let target_y = heightmap
+            .map(|hm| hm.sample_height(target_xz.x, target_xz.z))
+            .unwrap_or(droid.target_position.y);
+        droid.target_position = Vec3::new(target_xz.x, target_y, target_xz.z);
+    }
+
+    info!("Set facing ({:.2}, {:.2}) for {} squads", facing.x, facing.z, squad_ids.len());
+}
+
+/// Resolve a group move - squads keep their relative formation positions
+/// Returns destinations for living squads; dead squads get a grey indicator only
+fn resolve_group_move_orders(
+    commands: &mut Commands,
+    squad_manager: &SquadManager,
+    selection_state: &mut SelectionState,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    destination: Vec3,
+    unified_facing: Vec3,
+    group_id: u32,
+    heightmap: Option<&TerrainHeightmap>,
+) -> Vec<(u32, Vec3)> {
+    let Some(group) = selection_state.groups.get_mut(&group_id) else { return Vec::new() };
 
     // Get the ORIGINAL formation facing (from when group was created - this never changes)
     let original_facing = group.original_formation_facing;
@@ -190,15 +297,8 @@ fn execute_group_move(
         group.formation_facing = unified_facing;
     }
 
-    // Calculate actual current positions for path visuals
-    let group_squad_ids = group.squad_ids.clone();
-    let squad_current_positions = calculate_filtered_squad_centers(
-        droid_query.iter().map(|(_, _, sm, _, t, _)| (sm.squad_id, t.translation)),
-        |id| group_squad_ids.contains(&id),
-        squad_manager,
-    );
-
     // Apply rotated offsets to each squad
+    let mut orders = Vec::with_capacity(group.squad_offsets.len());
     for (&squad_id, &offset) in &group.squad_offsets {
         // Rotate the offset
         let rotated_offset = rotation * offset;
@@ -208,32 +308,8 @@ fn execute_group_move(
         let is_alive = squad_manager.get_squad(squad_id)
             .map_or(false, |s| !s.members.is_empty());
 
-        // Get terrain height at destination
-        let _terrain_y = heightmap
-            .map(|hm| hm.sample_height(squad_dest.x, squad_dest.z))
-            .unwrap_or(-1.0);
-
         if is_alive {
-            if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
-                // Set facing direction
-                if unified_facing.length() > 0.1 {
-                    squad.target_facing_direction = unified_facing;
-                    squad.facing_direction = unified_facing;
-                }
-
-                // Set target position
-                squad.target_position = squad_dest;
-
-                // Spawn green visual indicator for living squad
-                spawn_move_indicator(commands, meshes, materials, squad_dest, heightmap);
-
-                if let Some(&start_pos) = squad_current_positions.get(&squad_id) {
-                    let start_terrain_y = heightmap
-                        .map(|hm| hm.sample_height(start_pos.x, start_pos.z))
-                        .unwrap_or(-1.0);
-                    spawn_path_line(commands, meshes, materials, start_pos, squad_dest, start_terrain_y);
-                }
-            }
+            orders.push((squad_id, squad_dest));
         } else {
             // Dead squad - spawn grey indicator to show where it would have been
             let dead_color = Color::srgba(0.4, 0.4, 0.4, 0.8);
@@ -241,82 +317,47 @@ fn execute_group_move(
         }
     }
 
-    // Update individual unit targets using standard formation calculation
-    for (_entity, mut droid, squad_member, mut formation_offset, mut transform, mut mode) in droid_query.iter_mut() {
-        if group.squad_ids.contains(&squad_member.squad_id) {
-            // Set movement mode
-            *mode = new_mode;
-
-            if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
-                // Calculate formation offset with new facing direction
-                let new_offset = calculate_formation_offset(
-                    squad.formation_type,
-                    squad_member.formation_position.0,
-                    squad_member.formation_position.1,
-                    squad.facing_direction,
-                );
-
-                // Calculate target position with formation offset
-                let target_xz = squad.target_position + new_offset;
-                let target_y = heightmap
-                    .map(|hm| hm.sample_height(target_xz.x, target_xz.z))
-                    .unwrap_or(droid.spawn_position.y);
-                let target_pos = Vec3::new(target_xz.x, target_y, target_xz.z);
-
-                droid.target_position = target_pos;
-                droid.returning_to_spawn = false;
-
-                // Instant teleport during preparation phase
-                if instant_teleport {
-                    transform.translation = target_pos;
-                    droid.spawn_position = target_pos;
-                    formation_offset.target_world_position = target_pos;
-                }
-            }
-        }
-    }
+    // Squad offsets live in a HashMap - sort so the order is stable across runs
+    orders.sort_by_key(|&(squad_id, _)| squad_id);
 
     info!("Group {} moved to destination with maintained formation", group_id);
+    orders
 }
 
-fn execute_move_command(
+/// Resolve a move order for the given squads into per-squad destinations
+fn resolve_move_orders(
     commands: &mut Commands,
-    squad_manager: &mut ResMut<SquadManager>,
+    squad_manager: &SquadManager,
     selection_state: &mut SelectionState,
-    droid_query: &mut Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
+    droid_query: &Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
+    squad_ids: &[u32],
     destination: Vec3,
     unified_facing: Vec3,
     heightmap: Option<&TerrainHeightmap>,
-    instant_teleport: bool,
-    new_mode: MovementMode,
-) {
+) -> Vec<(u32, Vec3)> {
     // Check if this is a complete group move
-    if let Some(group_id) = check_is_complete_group(selection_state, squad_manager) {
+    if let Some(group_id) = find_complete_group(selection_state, squad_ids, squad_manager) {
         // Group move - maintain relative positions
-        execute_group_move(
+        return resolve_group_move_orders(
             commands,
             squad_manager,
             selection_state,
-            droid_query,
             meshes,
             materials,
             destination,
             unified_facing,
             group_id,
             heightmap,
-            instant_teleport,
-            new_mode,
         );
-        return;
     }
 
     // Regular move - create line formation
     // Perpendicular direction for spreading squads in a line (orthogonal to facing)
     let spread_direction = Vec3::new(unified_facing.z, 0.0, -unified_facing.x);
 
-    let num_squads = selection_state.selected_squads.len();
+    let num_squads = squad_ids.len();
 
     // Pre-calculate all destination slots
     let destination_slots: Vec<Vec3> = (0..num_squads)
@@ -332,15 +373,14 @@ fn execute_move_command(
         .collect();
 
     // Calculate actual current positions of squads from unit transforms (not squad.center_position which lags)
-    let selected = selection_state.selected_squads.clone();
     let squad_current_positions = calculate_filtered_squad_centers(
         droid_query.iter().map(|(_, _, sm, _, t, _)| (sm.squad_id, t.translation)),
-        |id| selected.contains(&id),
+        |id| squad_ids.contains(&id),
         squad_manager,
     );
 
     // Collect squad IDs and their current positions
-    let squad_positions: Vec<(u32, Vec3)> = selection_state.selected_squads.iter()
+    let squad_positions: Vec<(u32, Vec3)> = squad_ids.iter()
         .filter_map(|&id| squad_current_positions.get(&id).map(|&pos| (id, pos)))
         .collect();
 
@@ -375,69 +415,109 @@ fn execute_move_command(
         }
     }
 
-    // Use actual current positions for path visuals (already calculated above)
-    let squad_start_positions = squad_current_positions.clone();
+    assigned_destinations
+}
+
+/// Execute resolved move orders: every squad faces the same direction and its units
+/// march to their formation slot around the squad destination.
+fn apply_squad_moves(
+    commands: &mut Commands,
+    squad_manager: &mut SquadManager,
+    droid_query: &mut Query<(Entity, &mut BattleDroid, &SquadMember, &mut FormationOffset, &mut Transform, &mut MovementMode)>,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    orders: &[(u32, Vec3)],
+    unified_facing: Vec3,
+    heightmap: Option<&TerrainHeightmap>,
+    route: Option<(&NavGrid, &FlowField)>,
+    instant_teleport: bool,
+    new_mode: MovementMode,
+) {
+    // Actual current positions for path visuals (squad.center_position lags)
+    let squad_start_positions = calculate_filtered_squad_centers(
+        droid_query.iter().map(|(_, _, sm, _, t, _)| (sm.squad_id, t.translation)),
+        |id| orders.iter().any(|&(order_id, _)| order_id == id),
+        squad_manager,
+    );
 
     // Apply the assignments
-    for (squad_id, squad_destination) in assigned_destinations.iter() {
-        if let Some(squad) = squad_manager.get_squad_mut(*squad_id) {
+    for &(squad_id, squad_destination) in orders {
+        if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
             // ALL squads face the same unified direction
             if unified_facing.length() > 0.1 {
                 squad.target_facing_direction = unified_facing;
                 squad.facing_direction = unified_facing;
             }
 
-            // Set target position
-            squad.target_position = *squad_destination;
+            // Head for the first waypoint; the rest wait in squad.path
+            let start = squad_start_positions.get(&squad_id).copied().unwrap_or(squad.center_position);
+            let mut waypoints: VecDeque<Vec3> = match route {
+                Some((grid, field)) => grid.route(field, start, squad_destination)
+                    .into_iter()
+                    .map(|point| Vec3::new(
+                        point.x,
+                        heightmap.map_or(squad_destination.y, |hm| hm.sample_height(point.x, point.z)),
+                        point.z,
+                    ))
+                    .collect(),
+                None => VecDeque::from([squad_destination]),
+            };
+            // The final waypoint is the destination itself
+            if let Some(last) = waypoints.back_mut() {
+                *last = squad_destination;
+            }
+            squad.target_position = waypoints.pop_front().unwrap_or(squad_destination);
+            squad.path = waypoints;
         }
     }
 
     // Update individual unit targets
     for (_entity, mut droid, squad_member, mut formation_offset, mut transform, mut mode) in droid_query.iter_mut() {
-        if selection_state.selected_squads.contains(&squad_member.squad_id) {
-            // Set movement mode
-            *mode = new_mode;
+        if !orders.iter().any(|&(squad_id, _)| squad_id == squad_member.squad_id) {
+            continue;
+        }
 
-            if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
-                // Calculate new formation offset with new facing direction
-                let new_offset = calculate_formation_offset(
-                    squad.formation_type,
-                    squad_member.formation_position.0,
-                    squad_member.formation_position.1,
-                    squad.facing_direction,
-                );
-
-                // Calculate target position with formation offset
-                let target_xz = squad.target_position + new_offset;
-                let target_y = heightmap
-                    .map(|hm| hm.sample_height(target_xz.x, target_xz.z))
-                    .unwrap_or(droid.spawn_position.y);
-                let target_pos = Vec3::new(target_xz.x, target_y, target_xz.z);
-
-                droid.target_position = target_pos;
-                droid.returning_to_spawn = false;
-
-                // Instant teleport during preparation phase
-                if instant_teleport {
-                    transform.translation = target_pos;
-                    droid.spawn_position = target_pos;
-                    formation_offset.target_world_position = target_pos;
-                }
+        // Set movement mode
+        *mode = new_mode;
+
+        if let Some(squad) = squad_manager.get_squad(squad_member.squad_id) {
+            // Calculate new formation offset with new facing direction
+            let new_offset = squad_slot_offset(squad, squad_member.formation_position);
+
+            // Calculate target position with formation offset
+            let target_xz = squad.target_position + new_offset;
+            let target_y = heightmap
+                .map(|hm| hm.sample_height(target_xz.x, target_xz.z))
+                .unwrap_or(droid.spawn_position.y);
+            let target_pos = Vec3::new(target_xz.x, target_y, target_xz.z);
+
+            droid.target_position = target_pos;
+            droid.returning_to_spawn = false;
+
+            // Instant teleport during preparation phase
+            if instant_teleport {
+                transform.translation = target_pos;
+                droid.spawn_position = target_pos;
+                formation_offset.target_world_position = target_pos;
             }
         }
     }
 
-    // Spawn move indicator visuals for each squad
-    for (squad_id, squad_destination) in assigned_destinations.iter() {
+    // Spawn move indicator visuals for each squad (player squads only - AI orders stay hidden)
+    for &(squad_id, squad_destination) in orders {
+        if squad_manager.get_squad(squad_id).map_or(true, |squad| squad.team != Team::A) {
+            continue;
+        }
+
         // Spawn destination circle
-        spawn_move_indicator(commands, meshes, materials, *squad_destination, heightmap);
+        spawn_move_indicator(commands, meshes, materials, squad_destination, heightmap);
 
         // Spawn path line from squad current position to destination
-        if let Some(&start_pos) = squad_start_positions.get(squad_id) {
+        if let Some(&start_pos) = squad_start_positions.get(&squad_id) {
             let start_terrain_y = heightmap
                 .map(|hm| hm.sample_height(start_pos.x, start_pos.z))
                 .unwrap_or(-1.0);
-            spawn_path_line(commands, meshes, materials, start_pos, *squad_destination, start_terrain_y);
+            spawn_path_line(commands, meshes, materials, start_pos, squad_destination, start_terrain_y);
         }
     }
 }
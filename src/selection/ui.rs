@@ -3,12 +3,14 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-use crate::types::{BattleDroid, CombatUnit, MovementMode, MovementTracker, SquadManager, SquadMember, Team, TurretBase, MgTurret, Health};
+use crate::types::{BattleDroid, CombatUnit, MovementMode, MovementTracker, SquadManager, SquadMember, Team, TurretBase, MgTurret, Health, WoundState};
 use crate::constants::{
-    SQUAD_SIZE, INFANTRY_BASE_ACCURACY, TURRET_BASE_ACCURACY, ACCURACY_STATIONARY_BONUS, ACCURACY_HIGH_GROUND_BONUS,
-    ACCURACY_TARGET_MOVING_PENALTY, HIGH_GROUND_HEIGHT_THRESHOLD,
+    TURRET_BASE_ACCURACY, ACCURACY_STATIONARY_BONUS, ACCURACY_HIGH_GROUND_BONUS,
+    ACCURACY_TARGET_MOVING_PENALTY, HIGH_GROUND_HEIGHT_THRESHOLD, MORALE_RALLY_THRESHOLD,
 };
-use crate::combat::{calculate_hit_chance, calculate_range_penalty};
+use crate::archetype::UnitArchetypes;
+use crate::cover::CoverMap;
+use crate::combat::{calculate_hit_chance, calculate_range_penalty, turret_weapon, HitModifiers};
 use super::state::SelectionState;
 
 /// Marker component for the squad details UI panel
@@ -53,6 +55,24 @@ const COLOR_DEFAULT: Color = Color::srgba(0.9, 0.9, 0.9, 0.9);
 const COLOR_GREEN: Color = Color::srgba(0.4, 0.9, 0.4, 1.0);
 const COLOR_GREY: Color = Color::srgba(0.5, 0.5, 0.5, 0.8);
 const COLOR_RED: Color = Color::srgba(0.9, 0.4, 0.4, 1.0);
+const COLOR_YELLOW: Color = Color::srgba(0.9, 0.7, 0.2, 1.0);
+
+/// Fixed-width text bar for a 0-1 value, e.g. [######----]
+fn text_bar(fraction: f32, width: usize) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * width as f32).round() as usize).min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// Green above half health, yellow above a quarter, red below
+fn health_color(health_pct: i32) -> Color {
+    if health_pct > 50 {
+        COLOR_GREEN
+    } else if health_pct > 25 {
+        COLOR_YELLOW
+    } else {
+        COLOR_RED
+    }
+}
 
 /// A text segment with color
 struct ColoredSegment {
@@ -73,6 +93,8 @@ impl ColoredSegment {
 /// Wrapper to call calculate_hit_chance with pre-computed boolean conditions.
 /// Constructs synthetic positions that yield the same results as the booleans.
 fn ui_accuracy_estimate(
+    base_accuracy: f32,
+    modifiers: HitModifiers,
     shooter_stationary: bool,
     has_high_ground: bool,
     target_stationary: bool,
@@ -85,7 +107,7 @@ fn ui_accuracy_estimate(
     let horiz_dist = (avg_distance.powi(2) - height.powi(2)).max(0.0).sqrt();
     let target_pos = Vec3::new(horiz_dist, 0.0, 0.0);
 
-    calculate_hit_chance(INFANTRY_BASE_ACCURACY, shooter_pos, target_pos, shooter_stationary, target_stationary)
+    calculate_hit_chance(base_accuracy, shooter_pos, target_pos, shooter_stationary, target_stationary, modifiers)
 }
 
 /// Build accuracy modifier segments (shared between squad and turret UI)
@@ -132,29 +154,35 @@ fn build_accuracy_modifiers(
 /// Build accuracy modifier segments for engaged combat state (infantry)
 fn build_accuracy_segments_engaged(
     segments: &mut Vec<ColoredSegment>,
+    base_accuracy: f32,
+    modifiers: HitModifiers,
     has_stationary_bonus: bool,
     has_high_ground: bool,
     targets_moving: bool,
     avg_distance: f32,
     avg_height_diff: f32,
 ) {
-    let final_acc = ui_accuracy_estimate(has_stationary_bonus, has_high_ground, !targets_moving, avg_distance);
+    let final_acc = ui_accuracy_estimate(base_accuracy, modifiers, has_stationary_bonus, has_high_ground, !targets_moving, avg_distance);
     build_accuracy_modifiers(segments, has_high_ground, targets_moving, avg_distance, avg_height_diff, final_acc, "");
 }
 
 /// Build accuracy modifier segments for cached (last known) combat state (infantry)
 fn build_accuracy_segments_cached(
     segments: &mut Vec<ColoredSegment>,
+    base_accuracy: f32,
+    modifiers: HitModifiers,
     has_stationary_bonus: bool,
     cached: &CachedCombatState,
 ) {
-    let final_acc = ui_accuracy_estimate(has_stationary_bonus, cached.has_high_ground, !cached.targets_moving, cached.avg_distance);
+    let final_acc = ui_accuracy_estimate(base_accuracy, modifiers, has_stationary_bonus, cached.has_high_ground, !cached.targets_moving, cached.avg_distance);
     build_accuracy_modifiers(segments, cached.has_high_ground, cached.targets_moving, cached.avg_distance, cached.avg_height_diff, final_acc, " (last)");
 }
 
 /// Build accuracy modifier segments for idle state (no combat data)
 fn build_accuracy_segments_idle(
     segments: &mut Vec<ColoredSegment>,
+    base_accuracy: f32,
+    modifiers: HitModifiers,
     has_stationary_bonus: bool,
     avg_pos: Vec3,
 ) {
@@ -163,7 +191,7 @@ fn build_accuracy_segments_idle(
     segments.push(ColoredSegment::new("\n  Range: --".to_string(), COLOR_GREY));
 
     // Idle accuracy: base + stationary bonus if applicable, no combat modifiers
-    let final_acc = ui_accuracy_estimate(has_stationary_bonus, false, true, 0.0);
+    let final_acc = ui_accuracy_estimate(base_accuracy, modifiers, has_stationary_bonus, false, true, 0.0);
     segments.push(ColoredSegment::default_color(format!("\n  = Hit Chance: {:.0}% (idle)", final_acc * 100.0)));
     segments.push(ColoredSegment::new(format!("\n  Height: {}m", avg_pos.y as i32), COLOR_GREY));
 }
@@ -196,10 +224,12 @@ pub fn update_squad_details_ui(
     mut ui_timer: ResMut<UiUpdateTimer>,
     selection_state: Res<SelectionState>,
     squad_manager: Res<SquadManager>,
+    archetypes: Res<UnitArchetypes>,
+    cover_map: Res<CoverMap>,
     mut combat_cache: ResMut<SquadCombatCache>,
     ui_query: Query<(Entity, Option<&Children>), With<SquadDetailsUI>>,
     mut text_query: Query<&mut Text, With<SquadDetailsUI>>,
-    droid_query: Query<(&SquadMember, &BattleDroid, &Transform, &MovementMode, &CombatUnit, &MovementTracker)>,
+    droid_query: Query<(&SquadMember, &BattleDroid, &Transform, &MovementMode, &CombatUnit, &MovementTracker, Option<&Health>)>,
     target_query: Query<(&Transform, &MovementTracker), With<BattleDroid>>,
 ) {
     // Throttle UI updates for performance
@@ -242,8 +272,12 @@ pub fn update_squad_details_ui(
     let mut high_ground_count: u32 = 0;
     let mut targets_sampled: u32 = 0;
     let mut total_height_diff: f32 = 0.0;
+    let mut current_hp: f32 = 0.0;
+    let mut wounded_count: u32 = 0;
+    let mut critical_count: u32 = 0;
+    let mut in_cover_count: u32 = 0;
 
-    for (sm, _droid, transform, mode, combat, tracker) in droid_query.iter() {
+    for (sm, _droid, transform, mode, combat, tracker, health) in droid_query.iter() {
         if sm.squad_id != squad_id {
             continue;
         }
@@ -251,6 +285,15 @@ pub fn update_squad_details_ui(
         alive_count += 1;
         avg_pos += transform.translation;
 
+        if let Some(health) = health {
+            current_hp += health.current;
+            match health.wound_state() {
+                WoundState::Healthy => {}
+                WoundState::Wounded => wounded_count += 1,
+                WoundState::Critical => critical_count += 1,
+            }
+        }
+
         match mode {
             MovementMode::Hold => hold_count += 1,
             MovementMode::AttackMove => attack_move_count += 1,
@@ -261,6 +304,9 @@ pub fn update_squad_details_ui(
             engaged_count += 1;
 
             if let Ok((target_transform, target_tracker)) = target_query.get(target_entity) {
+                if cover_map.is_covered(transform.translation, target_transform.translation) {
+                    in_cover_count += 1;
+                }
                 targets_sampled += 1;
                 total_distance += transform.translation.distance(target_transform.translation);
                 total_height_diff += transform.translation.y - target_transform.translation.y;
@@ -273,6 +319,13 @@ pub fn update_squad_details_ui(
             }
         }
 
+        // Not engaged: cover against whatever lies ahead of the squad
+        if combat.current_target.is_none()
+            && cover_map.is_covered_from(transform.translation, squad.facing_direction, f32::INFINITY)
+        {
+            in_cover_count += 1;
+        }
+
         if tracker.is_stationary {
             stationary_count += 1;
         }
@@ -294,28 +347,78 @@ pub fn update_squad_details_ui(
         "Move"
     };
 
-    let team_str = match squad.team {
-        Team::A => "A (Blue)",
-        Team::B => "B (Red)",
-    };
+    let team_str = format!("{:?} ({})", squad.team, squad.team.palette().name);
+    let archetype = archetypes.get(&squad.archetype);
 
     segments.push(ColoredSegment::default_color(format!("=== Squad #{} ===", squad_id)));
     segments.push(ColoredSegment::default_color(format!("\n  Team: {}", team_str)));
-    segments.push(ColoredSegment::default_color(format!("\n  Units: {}/{} alive", alive_count, SQUAD_SIZE)));
+    segments.push(ColoredSegment::default_color(format!("\n  Unit: {} ({:?} armor)", archetype.name, archetype.armor)));
+    segments.push(ColoredSegment::default_color(format!("\n  Weapon: {:?}, {:.0} dmg", archetype.weapon.weapon_type, archetype.weapon.damage)));
+    segments.push(ColoredSegment::default_color(format!("\n  Units: {}/{} alive", alive_count, archetype.squad_size)));
+
+    // Health: squad average, hit points left, and damage soaked including the fallen
+    let avg_health_pct = (squad.average_health * 100.0) as i32;
+    let full_hp = archetype.squad_size as f32 * archetype.health;
+    segments.push(ColoredSegment::new(
+        format!("\n  Health: {}% avg ({:.0}/{:.0} HP)", avg_health_pct, current_hp, full_hp),
+        health_color(avg_health_pct),
+    ));
+    segments.push(ColoredSegment::default_color(format!("\n  Damage taken: {:.0}", (full_hp - current_hp).max(0.0))));
+    if wounded_count + critical_count > 0 {
+        segments.push(ColoredSegment::new(format!("\n  Wounded: {} | Critical: {}", wounded_count, critical_count), COLOR_YELLOW));
+    } else {
+        segments.push(ColoredSegment::new("\n  Wounded: --".to_string(), COLOR_GREY));
+    }
+
+    // Veterancy: rank from kills, with the kills still needed for the next one
+    let rank_text = match squad.kills_to_next_rank() {
+        Some(needed) => format!("\n  Rank: {} ({} kills, {} to next)", squad.rank_name(), squad.kills, needed),
+        None => format!("\n  Rank: {} ({} kills)", squad.rank_name(), squad.kills),
+    };
+    segments.push(ColoredSegment::new(rank_text, if squad.rank() > 0 { COLOR_GREEN } else { COLOR_DEFAULT }));
+
+    // Morale: routing squads ignore orders until they rally
+    let morale_pct = (squad.morale * 100.0) as i32;
+    if squad.is_routing() {
+        segments.push(ColoredSegment::new(format!("\n  Morale: {}% - ROUTING (ignores orders)", morale_pct), COLOR_RED));
+    } else {
+        let morale_state = if squad.morale >= MORALE_RALLY_THRESHOLD { "Steady" } else { "Shaken" };
+        segments.push(ColoredSegment::new(format!("\n  Morale: {}% ({})", morale_pct, morale_state), health_color(morale_pct)));
+    }
+
+    // Suppression bar: yellow while under fire, red once pinned
+    let suppression_color = if squad.suppression >= 0.5 {
+        COLOR_RED
+    } else if squad.suppression > 0.0 {
+        COLOR_YELLOW
+    } else {
+        COLOR_GREY
+    };
+    segments.push(ColoredSegment::new(
+        format!("\n  Suppression: {} {:.0}%", text_bar(squad.suppression, 10), squad.suppression * 100.0),
+        suppression_color,
+    ));
+    if in_cover_count > 0 {
+        segments.push(ColoredSegment::new(format!("\n  In cover ({}/{} units)", in_cover_count, alive_count), COLOR_GREEN));
+    } else {
+        segments.push(ColoredSegment::new("\n  Cover: --".to_string(), COLOR_GREY));
+    }
     segments.push(ColoredSegment::default_color(format!("\n  Mode: {} ({}/{})", mode_str,
         if mode_str == "Hold" { hold_count }
         else if mode_str == "AttackMove" { attack_move_count }
         else { move_count },
         alive_count
     )));
+    let reshaping = if squad.formation_blend < 1.0 { " (reforming)" } else { "" };
+    segments.push(ColoredSegment::default_color(format!("\n  Formation: {:?}{}", squad.formation_type, reshaping)));
     segments.push(ColoredSegment::default_color(format!("\n  Engaged: {}", engaged_count)));
     segments.push(ColoredSegment::default_color(format!("\n  Stationary: {}/{}", stationary_count, alive_count)));
     segments.push(ColoredSegment::default_color(format!("\n  Pos: ({:.0}, {:.0}, h={:.0})", avg_pos.x, avg_pos.z, avg_pos.y)));
-    segments.push(ColoredSegment::default_color(format!("\n  Target: ({:.0}, {:.0})", squad.target_position.x, squad.target_position.z)));
+    segments.push(ColoredSegment::default_color(format!("\n  Target: ({:.0}, {:.0})", squad.destination().x, squad.destination().z)));
 
     // Accuracy breakdown
     segments.push(ColoredSegment::default_color("\n  --- Accuracy ---".to_string()));
-    segments.push(ColoredSegment::default_color(format!("\n  Base: {:.0}%", INFANTRY_BASE_ACCURACY * 100.0)));
+    segments.push(ColoredSegment::default_color(format!("\n  Base: {:.0}%", archetype.weapon.accuracy * 100.0)));
 
     let stationary_ratio = if alive_count > 0 { stationary_count as f32 / alive_count as f32 } else { 0.0 };
     let has_stationary_bonus = stationary_ratio > 0.5;
@@ -327,6 +430,35 @@ pub fn update_squad_details_ui(
         segments.push(ColoredSegment::new("\n  Stationary: --".to_string(), COLOR_GREY));
     }
 
+    // Formation multiplier - green above 1, red below, grey when neutral
+    let formation_mod = squad.formation_type.accuracy_modifier();
+    if formation_mod > 1.0 {
+        segments.push(ColoredSegment::new(format!("\n  +Formation: x{:.2}", formation_mod), COLOR_GREEN));
+    } else if formation_mod < 1.0 {
+        segments.push(ColoredSegment::new(format!("\n  -Formation: x{:.2}", formation_mod), COLOR_RED));
+    } else {
+        segments.push(ColoredSegment::new("\n  Formation: --".to_string(), COLOR_GREY));
+    }
+
+    // Suppression multiplier - red while pinned down
+    let suppression_mod = squad.suppression_accuracy_modifier();
+    if suppression_mod < 1.0 {
+        segments.push(ColoredSegment::new(format!("\n  -Suppressed: x{:.2}", suppression_mod), COLOR_RED));
+    } else {
+        segments.push(ColoredSegment::new("\n  Suppressed: --".to_string(), COLOR_GREY));
+    }
+
+    // Veterancy multiplier - green once the squad has earned a rank
+    let veterancy_mod = squad.veterancy_accuracy_modifier();
+    if veterancy_mod > 1.0 {
+        segments.push(ColoredSegment::new(format!("\n  +{}: x{:.2}", squad.rank_name(), veterancy_mod), COLOR_GREEN));
+    } else {
+        segments.push(ColoredSegment::new("\n  Veterancy: --".to_string(), COLOR_GREY));
+    }
+    let modifiers = HitModifiers::from_formations(Some(squad.formation_type), None)
+        .with_shooter(suppression_mod)
+        .with_shooter(veterancy_mod);
+
     // If engaged, show combat-specific accuracy modifiers
     if targets_sampled > 0 {
         let avg_distance = total_distance / targets_sampled as f32;
@@ -347,6 +479,8 @@ pub fn update_squad_details_ui(
 
         build_accuracy_segments_engaged(
             &mut segments,
+            archetype.weapon.accuracy,
+            modifiers,
             has_stationary_bonus,
             has_high_ground,
             targets_moving,
@@ -356,9 +490,9 @@ pub fn update_squad_details_ui(
     } else {
         // Not engaged - show cached combat state if available
         if let Some(cached) = combat_cache.cache.get(&squad_id) {
-            build_accuracy_segments_cached(&mut segments, has_stationary_bonus, cached);
+            build_accuracy_segments_cached(&mut segments, archetype.weapon.accuracy, modifiers, has_stationary_bonus, cached);
         } else {
-            build_accuracy_segments_idle(&mut segments, has_stationary_bonus, avg_pos);
+            build_accuracy_segments_idle(&mut segments, archetype.weapon.accuracy, modifiers, has_stationary_bonus, avg_pos);
         }
     }
 
@@ -467,22 +601,12 @@ pub fn update_turret_details_ui(
     let mut segments: Vec<ColoredSegment> = Vec::new();
     segments.push(ColoredSegment::default_color("=== Selected Turret ===".to_string()));
 
-    let team_str = match turret_base.team {
-        Team::A => "A (Blue)",
-        Team::B => "B (Red)",
-    };
+    let team_str = format!("{:?} ({})", turret_base.team, turret_base.team.palette().name);
     segments.push(ColoredSegment::default_color(format!("\n  Team: {}", team_str)));
 
     // Health with color
     let health_pct = (health.current / health.max * 100.0) as i32;
-    let health_color = if health_pct > 50 {
-        COLOR_GREEN
-    } else if health_pct > 25 {
-        Color::srgba(0.9, 0.7, 0.2, 1.0) // Yellow-orange
-    } else {
-        COLOR_RED
-    };
-    segments.push(ColoredSegment::new(format!("\n  Health: {:.0}/{:.0} ({}%)", health.current, health.max, health_pct), health_color));
+    segments.push(ColoredSegment::new(format!("\n  Health: {:.0}/{:.0} ({}%)", health.current, health.max, health_pct), health_color(health_pct)));
 
     // Position
     segments.push(ColoredSegment::default_color(format!("\n  Pos: ({:.0}, {:.0}, h={:.0})", transform.translation.x, transform.translation.z, transform.translation.y)));
@@ -547,6 +671,7 @@ pub fn update_turret_details_ui(
         // MG turret specific info
         if let Some(mg) = mg_turret {
             segments.push(ColoredSegment::default_color("\n  --- MG Status ---".to_string()));
+            segments.push(ColoredSegment::default_color(format!("\n  Weapon: {:?}", turret_weapon(true))));
             segments.push(ColoredSegment::default_color(format!("\n  Burst: {}/{}", mg.shots_in_burst, mg.max_burst_shots)));
             if mg.cooldown_timer > 0.0 {
                 segments.push(ColoredSegment::new(format!("\n  Cooldown: {:.1}s", mg.cooldown_timer), COLOR_GREY));
@@ -554,7 +679,7 @@ pub fn update_turret_details_ui(
                 segments.push(ColoredSegment::new("\n  Ready to fire".to_string(), COLOR_GREEN));
             }
         } else {
-            segments.push(ColoredSegment::default_color("\n  Type: Heavy Turret".to_string()));
+            segments.push(ColoredSegment::default_color(format!("\n  Type: Heavy Turret ({:?})", turret_weapon(false))));
         }
     }
 
@@ -585,5 +710,5 @@ fn turret_accuracy_estimate(has_high_ground: bool, target_stationary: bool, dist
     let horiz_dist = (distance.powi(2) - height.powi(2)).max(0.0).sqrt();
     let target_pos = Vec3::new(horiz_dist, 0.0, 0.0);
 
-    calculate_hit_chance(TURRET_BASE_ACCURACY, shooter_pos, target_pos, true, target_stationary)
+    calculate_hit_chance(TURRET_BASE_ACCURACY, shooter_pos, target_pos, true, target_stationary, HitModifiers::default())
 }
@@ -4,6 +4,7 @@ use bevy::core_pipeline::prepass::DepthPrepass;
 use rand::Rng;
 use std::f32::consts::PI;
 use crate::types::*;
+use crate::archetype::UnitArchetypes;
 use crate::constants::*;
 use crate::formation::*;
 use crate::terrain::TerrainHeightmap;
@@ -130,117 +131,38 @@ pub fn spawn_army_with_squads(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut squad_manager: ResMut<SquadManager>,
+    mut archetypes: ResMut<UnitArchetypes>,
+    mut sim_rng: ResMut<SimRng>,
     heightmap: Res<TerrainHeightmap>,
+    diplomacy: Res<Diplomacy>,
 ) {
-    // Create battle droid mesh (simple humanoid shape using cubes)
-    let droid_mesh = create_battle_droid_mesh_internal(&mut meshes);
-    
-    // Team A materials (current blue-gray theme)
-    let team_a_body_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.7, 0.7, 0.8),
-        metallic: 0.3,
-        perceptual_roughness: 0.5,
-        ..default()
-    });
-    
-    let team_a_head_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.8, 0.6, 0.4),
-        metallic: 0.2,
-        perceptual_roughness: 0.6,
-        ..default()
-    });
-    
-    // Team A commander materials (yellow/gold like SW)
-    let team_a_commander_body_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.8, 0.4), // Golden yellow
-        metallic: 0.5,
-        perceptual_roughness: 0.3,
-        ..default()
-    });
-    
-    let team_a_commander_head_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.9, 0.5), // Bright gold
-        metallic: 0.4,
-        perceptual_roughness: 0.4,
-        ..default()
-    });
-
-    // Team B materials (white/light theme)
-    let team_b_body_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.9, 0.95),
-        metallic: 0.4,
-        perceptual_roughness: 0.3,
-        ..default()
-    });
-    
-    let team_b_head_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.95, 0.95, 1.0),
-        metallic: 0.3,
-        perceptual_roughness: 0.4,
-        ..default()
-    });
-    
-    // Team B commander materials (red/orange like enemy commanders)
-    let team_b_commander_body_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.5, 0.3), // Orange-red
-        metallic: 0.5,
-        perceptual_roughness: 0.3,
-        ..default()
-    });
-    
-    let team_b_commander_head_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.6, 0.4), // Bright orange
-        metallic: 0.4,
-        perceptual_roughness: 0.4,
-        ..default()
-    });
-
-    let mut rng = rand::thread_rng();
-    
-    // Calculate number of squads per team
-    let squads_per_team = ARMY_SIZE_PER_TEAM / SQUAD_SIZE;
+    // Calculate number of squads per team (the total stays the same with more teams)
+    let team_count = diplomacy.teams.len().max(1);
+    let squads_per_team = ARMY_SIZE_PER_TEAM * 2 / team_count / SQUAD_SIZE;
     let squads_per_row = (squads_per_team as f32).sqrt().ceil() as usize;
-    
-    // Spawn Team A squads (left side, facing right)
-    spawn_team_squads(
-        &mut commands,
-        &mut squad_manager,
-        &mut rng,
-        &droid_mesh,
-        &team_a_body_material,
-        &team_a_head_material,
-        &team_a_commander_body_material,
-        &team_a_commander_head_material,
-        &mut materials,
-        Team::A,
-        Vec3::new(-BATTLEFIELD_SIZE / 2.0, 0.0, 0.0),
-        Vec3::new(1.0, 0.0, 0.0), // Facing right
-        squads_per_team,
-        squads_per_row,
-        &heightmap,
-    );
 
-    // Spawn Team B squads (right side, facing left)
-    spawn_team_squads(
-        &mut commands,
-        &mut squad_manager,
-        &mut rng,
-        &droid_mesh,
-        &team_b_body_material,
-        &team_b_head_material,
-        &team_b_commander_body_material,
-        &team_b_commander_head_material,
-        &mut materials,
-        Team::B,
-        Vec3::new(BATTLEFIELD_SIZE / 2.0, 0.0, 0.0),
-        Vec3::new(-1.0, 0.0, 0.0), // Facing left
-        squads_per_team,
-        squads_per_row,
-        &heightmap,
-    );
-    
-    info!("Spawned {} squads per team ({} droids per squad, {} total units)", 
-          squads_per_team, SQUAD_SIZE, squads_per_team * SQUAD_SIZE * 2);
+    // Each team starts on its own side of the battlefield, facing the center
+    for &team in &diplomacy.teams {
+        let team_materials = create_team_materials(&mut materials, team);
+        spawn_team_squads(
+            &mut commands,
+            &mut squad_manager,
+            &mut *sim_rng,
+            &mut archetypes,
+            &mut meshes,
+            &team_materials,
+            &mut materials,
+            team,
+            team.home_position(team_count),
+            -team.home_direction(),
+            squads_per_team,
+            squads_per_row,
+            &heightmap,
+        );
+    }
+
+    info!("Spawned {} squads per team for {} teams ({} droids per squad, {} total units)",
+          squads_per_team, team_count, SQUAD_SIZE, squads_per_team * SQUAD_SIZE * team_count);
 }
 
 /// Materials needed for spawning units
@@ -253,35 +175,48 @@ pub struct UnitMaterials {
     pub commander_head_material: Handle<StandardMaterial>,
 }
 
-/// Spawn a single squad at a specific position
+/// Spawn a single squad of the given unit archetype at a specific position
 /// Returns the squad_id of the newly created squad
 pub fn spawn_single_squad(
     commands: &mut Commands,
     squad_manager: &mut SquadManager,
-    droid_mesh: &Handle<Mesh>,
+    archetypes: &mut UnitArchetypes,
+    archetype_id: &str,
+    meshes: &mut Assets<Mesh>,
     unit_materials: &UnitMaterials,
     materials: &mut Assets<StandardMaterial>,
     team: Team,
     position: Vec3,
     facing_direction: Vec3,
     heightmap: &TerrainHeightmap,
+    rng: &mut impl Rng,
 ) -> u32 {
-    let mut rng = rand::thread_rng();
+    let archetype = archetypes.get(archetype_id).clone();
+    let unit_mesh = archetypes.mesh(archetype.mesh, meshes);
+    let stats = archetype.stats();
 
     // Create the squad
     let squad_id = squad_manager.create_squad(team, position, facing_direction);
+    if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
+        squad.archetype = if archetypes.contains(archetype_id) { archetype_id } else { DEFAULT_UNIT_ARCHETYPE }.to_string();
+    }
 
     // Get formation positions for this squad
     let formation_positions = assign_formation_positions(FormationType::Rectangle);
     let commander_pos = get_commander_position(FormationType::Rectangle);
 
-    // Spawn units for this squad
-    for (unit_index, &(row, col)) in formation_positions.iter().enumerate() {
-        if unit_index >= SQUAD_SIZE {
-            break;
-        }
+    // Small squads leave their trailing slots empty but always field a commander
+    let mut troopers_left = archetype.squad_size.clamp(1, SQUAD_SIZE) - 1;
 
+    // Spawn units for this squad
+    for &(row, col) in formation_positions.iter().take(SQUAD_SIZE) {
         let is_commander = (row, col) == commander_pos;
+        if !is_commander {
+            if troopers_left == 0 {
+                continue;
+            }
+            troopers_left -= 1;
+        }
 
         // Calculate unit position within the squad formation
         let formation_offset = calculate_formation_offset(
@@ -298,18 +233,16 @@ pub fn spawn_single_squad(
 
         // Add some randomness to march timing but reduce speed variance
         let march_offset = rng.gen_range(0.0..2.0 * PI);
-        let march_speed = rng.gen_range(0.96..1.04);
+        let march_speed = rng.gen_range(0.96..1.04); // Much smaller variance for tighter formations
 
         // Units start stationary
         let target_position = unit_position;
 
-        // Choose materials based on commander status
+        // Choose materials based on commander status - unique materials for commanders only,
+        // regular units share materials to keep batching
         let unit_body_material = if is_commander {
             materials.add(StandardMaterial {
-                base_color: match team {
-                    Team::A => Color::srgb(0.9, 0.8, 0.4),
-                    Team::B => Color::srgb(0.9, 0.5, 0.3),
-                },
+                base_color: team.palette().commander_body,
                 metallic: 0.5,
                 perceptual_roughness: 0.3,
                 ..default()
@@ -319,10 +252,7 @@ pub fn spawn_single_squad(
         };
         let unit_head_material = if is_commander {
             materials.add(StandardMaterial {
-                base_color: match team {
-                    Team::A => Color::srgb(1.0, 0.9, 0.5),
-                    Team::B => Color::srgb(1.0, 0.6, 0.4),
-                },
+                base_color: team.palette().commander_head,
                 metallic: 0.4,
                 perceptual_roughness: 0.4,
                 ..default()
@@ -331,13 +261,17 @@ pub fn spawn_single_squad(
             unit_materials.head_material.clone()
         };
 
-        // Spawn the battle droid
+        // Commanders are drawn slightly larger
+        let scale = if is_commander { archetype.scale * 1.125 } else { archetype.scale };
+
+        // Spawn the unit
         let droid_entity = commands.spawn((
-            Mesh3d(droid_mesh.clone()),
+            Mesh3d(unit_mesh.clone()),
             MeshMaterial3d(unit_body_material),
+            // Same yaw animate_march turns units to, so slow turners don't start by turning around
             Transform::from_translation(unit_position)
-                .with_scale(if is_commander { Vec3::splat(0.9) } else { Vec3::splat(0.8) })
-                .looking_at(unit_position + facing_direction, Vec3::Y),
+                .with_scale(Vec3::splat(scale))
+                .with_rotation(Quat::from_rotation_y(facing_direction.x.atan2(facing_direction.z))),
             BattleDroid {
                 march_speed,
                 spawn_position: unit_position,
@@ -348,7 +282,7 @@ pub fn spawn_single_squad(
             },
             CombatUnit {
                 target_scan_timer: rng.gen_range(0.0..TARGET_SCAN_INTERVAL),
-                auto_fire_timer: rng.gen_range(0.0..AUTO_FIRE_INTERVAL),
+                auto_fire_timer: rng.gen_range(0.0..stats.fire_interval),
                 current_target: None,
                 blocked_timer: 0.0,
             },
@@ -361,7 +295,10 @@ pub fn spawn_single_squad(
                 local_offset: formation_offset,
                 target_world_position: unit_position,
             },
-            UnitMass(DEFAULT_UNIT_MASS),
+            stats,
+            Health::new(archetype.health),
+            archetype.armor,
+            UnitMass(archetype.mass),
             MovementMode::default(),
             MovementTracker::new(unit_position),
         )).id();
@@ -377,74 +314,49 @@ pub fn spawn_single_squad(
         }
 
         // Add a head (separate entity as child)
-        let head_entity = commands.spawn((
-            Mesh3d(droid_mesh.clone()),
-            MeshMaterial3d(unit_head_material),
-            Transform::from_xyz(0.0, 2.8, 0.0)
-                .with_scale(Vec3::splat(0.3)),
-        )).id();
+        if archetype.mesh.has_head() {
+            let head_entity = commands.spawn((
+                Mesh3d(unit_mesh.clone()),
+                MeshMaterial3d(unit_head_material),
+                Transform::from_xyz(0.0, 2.8, 0.0)
+                    .with_scale(Vec3::splat(0.3)),
+            )).id();
 
-        commands.entity(droid_entity).add_children(&[head_entity]);
+            commands.entity(droid_entity).add_children(&[head_entity]);
+        }
     }
 
     squad_id
 }
 
-/// Create unit materials for a team
+/// Create unit materials for a team (colors from Team::palette)
 pub fn create_team_materials(materials: &mut Assets<StandardMaterial>, team: Team) -> UnitMaterials {
-    match team {
-        Team::A => UnitMaterials {
-            body_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.7, 0.7, 0.8),
-                metallic: 0.3,
-                perceptual_roughness: 0.5,
-                ..default()
-            }),
-            head_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.8, 0.6, 0.4),
-                metallic: 0.2,
-                perceptual_roughness: 0.6,
-                ..default()
-            }),
-            commander_body_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.9, 0.8, 0.4),
-                metallic: 0.5,
-                perceptual_roughness: 0.3,
-                ..default()
-            }),
-            commander_head_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(1.0, 0.9, 0.5),
-                metallic: 0.4,
-                perceptual_roughness: 0.4,
-                ..default()
-            }),
-        },
-        Team::B => UnitMaterials {
-            body_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.9, 0.9, 0.95),
-                metallic: 0.4,
-                perceptual_roughness: 0.3,
-                ..default()
-            }),
-            head_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.95, 0.95, 1.0),
-                metallic: 0.3,
-                perceptual_roughness: 0.4,
-                ..default()
-            }),
-            commander_body_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(0.9, 0.5, 0.3),
-                metallic: 0.5,
-                perceptual_roughness: 0.3,
-                ..default()
-            }),
-            commander_head_material: materials.add(StandardMaterial {
-                base_color: Color::srgb(1.0, 0.6, 0.4),
-                metallic: 0.4,
-                perceptual_roughness: 0.4,
-                ..default()
-            }),
-        },
+    let palette = team.palette();
+    UnitMaterials {
+        body_material: materials.add(StandardMaterial {
+            base_color: palette.body,
+            metallic: palette.body_metallic,
+            perceptual_roughness: 0.8 - palette.body_metallic, // Shinier armor is smoother
+            ..default()
+        }),
+        head_material: materials.add(StandardMaterial {
+            base_color: palette.head,
+            metallic: palette.body_metallic - 0.1,
+            perceptual_roughness: 0.9 - palette.body_metallic,
+            ..default()
+        }),
+        commander_body_material: materials.add(StandardMaterial {
+            base_color: palette.commander_body,
+            metallic: 0.5,
+            perceptual_roughness: 0.3,
+            ..default()
+        }),
+        commander_head_material: materials.add(StandardMaterial {
+            base_color: palette.commander_head,
+            metallic: 0.4,
+            perceptual_roughness: 0.4,
+            ..default()
+        }),
     }
 }
 
@@ -455,14 +367,12 @@ pub fn create_droid_mesh(meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
 
 fn spawn_team_squads(
     commands: &mut Commands,
-    squad_manager: &mut ResMut<SquadManager>,
-    rng: &mut rand::rngs::ThreadRng,
-    droid_mesh: &Handle<Mesh>,
-    body_material: &Handle<StandardMaterial>,
-    head_material: &Handle<StandardMaterial>,
-    _commander_body_material: &Handle<StandardMaterial>,
-    _commander_head_material: &Handle<StandardMaterial>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    squad_manager: &mut SquadManager,
+    rng: &mut impl Rng,
+    archetypes: &mut UnitArchetypes,
+    meshes: &mut Assets<Mesh>,
+    unit_materials: &UnitMaterials,
+    materials: &mut Assets<StandardMaterial>,
     team: Team,
     team_center: Vec3,
     facing_direction: Vec3,
@@ -507,126 +417,20 @@ fn spawn_team_squads(
         let right = Vec3::new(facing_direction.z, 0.0, -facing_direction.x).normalize();
         let squad_center = team_center + right * squad_offset_x + facing_direction * squad_offset_z;
         
-        // Create the squad
-        let squad_id = squad_manager.create_squad(team, squad_center, facing_direction);
-        
-        // Get formation positions for this squad
-        let formation_positions = assign_formation_positions(FormationType::Rectangle);
-        let commander_pos = get_commander_position(FormationType::Rectangle);
-        
-        // Spawn units for this squad
-        for (unit_index, &(row, col)) in formation_positions.iter().enumerate() {
-            if unit_index >= SQUAD_SIZE {
-                break;
-            }
-            
-            let is_commander = (row, col) == commander_pos;
-            
-            // Calculate unit position within the squad formation
-            let formation_offset = calculate_formation_offset(
-                FormationType::Rectangle,
-                row,
-                col,
-                facing_direction,
-            );
-            // Calculate XZ position first, then sample terrain height
-            let xz_position = squad_center + formation_offset;
-            let terrain_height = heightmap.sample_height(xz_position.x, xz_position.z);
-            // Mesh origin is at feet, so just place at terrain height
-            let unit_position = Vec3::new(xz_position.x, terrain_height, xz_position.z);
-            
-            // Add some randomness to march timing but reduce speed variance
-            let march_offset = rng.gen_range(0.0..2.0 * PI);
-            let march_speed = rng.gen_range(0.96..1.04); // Much smaller variance for tighter formations
-            
-            // Units start stationary - target position same as spawn position initially
-            let target_position = unit_position;
-            
-            // Choose materials based on commander status - create unique materials for commanders only
-            let unit_body_material = if is_commander {
-                // Create a unique commander material for this specific unit
-                materials.add(StandardMaterial {
-                    base_color: match team {
-                        Team::A => Color::srgb(0.9, 0.8, 0.4), // Golden yellow
-                        Team::B => Color::srgb(0.9, 0.5, 0.3), // Orange-red
-                    },
-                    metallic: 0.5,
-                    perceptual_roughness: 0.3,
-                    ..default()
-                })
-            } else {
-                body_material.clone() // Share materials for regular units to maintain performance
-            };
-            let unit_head_material = if is_commander {
-                // Create a unique commander head material for this specific unit
-                materials.add(StandardMaterial {
-                    base_color: match team {
-                        Team::A => Color::srgb(1.0, 0.9, 0.5), // Bright gold
-                        Team::B => Color::srgb(1.0, 0.6, 0.4), // Bright orange
-                    },
-                    metallic: 0.4,
-                    perceptual_roughness: 0.4,
-                    ..default()
-                })
-            } else {
-                head_material.clone() // Share materials for regular units to maintain performance
-            };
-            
-            // Spawn the battle droid
-            let droid_entity = commands.spawn((
-                Mesh3d(droid_mesh.clone()),
-                MeshMaterial3d(unit_body_material),
-                Transform::from_translation(unit_position)
-                    .with_scale(if is_commander { Vec3::splat(0.9) } else { Vec3::splat(0.8) }) // Commanders slightly larger
-                    .looking_at(unit_position + facing_direction, Vec3::Y),
-                BattleDroid {
-                    march_speed,
-                    spawn_position: unit_position,
-                    target_position,
-                    march_offset,
-                    returning_to_spawn: false, // Start stationary at spawn
-                    team,
-                },
-                CombatUnit {
-                    target_scan_timer: rng.gen_range(0.0..TARGET_SCAN_INTERVAL),
-                    auto_fire_timer: rng.gen_range(0.0..AUTO_FIRE_INTERVAL),
-                    current_target: None,
-                    blocked_timer: 0.0,
-                },
-                SquadMember {
-                    squad_id,
-                    formation_position: (row, col),
-                    is_commander,
-                },
-                FormationOffset {
-                    local_offset: formation_offset,
-                    target_world_position: unit_position,
-                },
-                UnitMass(DEFAULT_UNIT_MASS),
-                MovementMode::default(),
-                MovementTracker::new(unit_position),
-            )).id();
-
-            // Add unit to squad manager
-            squad_manager.add_unit_to_squad(squad_id, droid_entity);
-
-            // Set commander if this is the commander unit
-            if is_commander {
-                if let Some(squad) = squad_manager.get_squad_mut(squad_id) {
-                    squad.commander = Some(droid_entity);
-                }
-            }
-            
-            // Add a head (separate entity as child)
-            let head_entity = commands.spawn((
-                Mesh3d(droid_mesh.clone()),
-                MeshMaterial3d(unit_head_material),
-                Transform::from_xyz(0.0, 2.8, 0.0)
-                    .with_scale(Vec3::splat(0.3)),
-            )).id();
-
-            commands.entity(droid_entity).add_children(&[head_entity]);
-        }
+        spawn_single_squad(
+            commands,
+            squad_manager,
+            archetypes,
+            DEFAULT_UNIT_ARCHETYPE,
+            meshes,
+            unit_materials,
+            materials,
+            team,
+            squad_center,
+            facing_direction,
+            heightmap,
+            rng,
+        );
     }
 }
 
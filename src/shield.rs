@@ -358,6 +358,7 @@ pub fn shield_collision_system(
     camera_query: Query<&Transform, With<Camera3d>>,
     // particle_effects: Res<crate::particles::ExplosionParticleEffects>,  // Temporarily disabled
     audio_assets: Res<crate::types::AudioAssets>,
+    diplomacy: Res<crate::types::Diplomacy>,
 ) {
     let current_time = time.elapsed_secs();
 
@@ -370,8 +371,8 @@ pub fn shield_collision_system(
 
     for (shield_entity, mut shield) in shield_query.iter_mut() {
         for (laser_entity, laser, laser_transform) in laser_query.iter() {
-            // Only check enemy lasers
-            if laser.team == shield.team {
+            // Only check enemy lasers (allied fire passes through)
+            if diplomacy.is_allied(laser.team, shield.team) {
                 continue;
             }
 
@@ -613,6 +614,7 @@ pub fn debug_destroy_enemy_shield(
     mut shield_query: Query<&mut Shield>,
     time: Res<Time>,
     debug_mode: Res<crate::objective::ExplosionDebugMode>,
+    diplomacy: Res<crate::types::Diplomacy>,
 ) {
     // Only work when debug mode is active
     if !debug_mode.explosion_mode {
@@ -623,8 +625,8 @@ pub fn debug_destroy_enemy_shield(
         let current_time = time.elapsed_secs();
 
         for mut shield in shield_query.iter_mut() {
-            if shield.team == Team::B {
-                info!("DEBUG: Setting Team B shield HP to 0");
+            if diplomacy.is_hostile(Team::A, shield.team) {
+                info!("DEBUG: Setting Team {:?} shield HP to 0", shield.team);
                 shield.current_hp = 0.0;
                 shield.last_hit_time = current_time;
                 shield.impact_flash_timer = config.impact_flash_duration;
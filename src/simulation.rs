@@ -0,0 +1,49 @@
+// Simulation module - render-free gameplay plugins
+//
+// SimulationPlugin bundles SquadPlugin, CombatPlugin, ObjectivePlugin, CapturePlugin,
+// EconomyPlugin and NavigationPlugin: formations, morale, movement, pathing, team vision,
+// targeting, hitscan, shields, towers, turret and explosion despawns, capture points,
+// credits and production, area damage and the win condition.
+// None of these systems require a window, camera or render assets, so the bundle
+// runs under DefaultPlugins (windowed game) and MinimalPlugins (headless mode) alike.
+//
+// Gameplay systems run in FixedUpdate so results don't depend on frame rate. The tick
+// rate comes from the Time<Fixed> resource (see CliArgs::fixed_time). Droid and
+// projectile transforms are interpolated between ticks for rendering.
+//
+// All outcome-affecting randomness draws from the SimRng resource. Insert one built
+// from the --seed flag before adding the plugin; otherwise a random seed is used.
+// Likewise the Diplomacy resource (--teams) defaults to Team A vs Team B.
+//
+// Unit archetypes are read from assets/units when the plugin is built (see archetype.rs).
+
+use bevy::prelude::*;
+
+use crate::archetype::UnitArchetypes;
+use crate::capture::CapturePlugin;
+use crate::combat::CombatPlugin;
+use crate::constants::UNIT_ARCHETYPE_DIR;
+use crate::economy::EconomyPlugin;
+use crate::formation::SquadPlugin;
+use crate::interpolation::TransformInterpolationPlugin;
+use crate::navigation::NavigationPlugin;
+use crate::objective::ObjectivePlugin;
+use crate::types::{Diplomacy, SimRng, SimTick};
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimRng>()
+            .init_resource::<Diplomacy>()
+            .init_resource::<SimTick>()
+            .insert_resource(UnitArchetypes::load_dir(UNIT_ARCHETYPE_DIR))
+            .add_plugins((SquadPlugin, CombatPlugin, ObjectivePlugin, CapturePlugin, EconomyPlugin, NavigationPlugin, TransformInterpolationPlugin))
+            .add_systems(FixedLast, advance_sim_tick);
+    }
+}
+
+/// Count completed ticks (player commands are stamped with this value)
+fn advance_sim_tick(mut sim_tick: ResMut<SimTick>) {
+    sim_tick.0 += 1;
+}
@@ -9,8 +9,9 @@ use noise::{NoiseFn, Perlin, Fbm, MultiFractal};
 use std::f32::consts::PI;
 use crate::constants::*;
 use crate::types::*;
-use crate::scenario::CommandBunker;
+use crate::scenario::{map_starts_new_battle, CommandBunker, ScenarioLibrary, ScenarioState};
 use crate::shield::Shield;
+use crate::archetype::UnitArchetypes;
 
 /// Marker component for skybox - used to remove skybox when switching maps
 #[derive(Component)]
@@ -24,9 +25,11 @@ impl Plugin for TerrainPlugin {
             .add_event::<MapSwitchEvent>()
             .add_systems(Startup, spawn_initial_terrain)
             .add_systems(Update, (
-                terrain_map_switching,
+                map_switch_hotkey_system.in_set(crate::game_command::PlayerInputSet),
+                terrain_map_switching.after(crate::game_command::execute_game_commands),
                 handle_pending_heightmap,
-                handle_map_switch_units,
+                // Same frame as the switch, so units are repositioned on the tick the command ran
+                handle_map_switch_units.after(terrain_map_switching).after(handle_pending_heightmap),
                 // spawn_debug_map_entities must run AFTER handle_map_switch_units
                 // because handle_map_switch_units clears the squad_manager
                 spawn_debug_map_entities.after(handle_map_switch_units),
@@ -43,7 +46,7 @@ pub struct MapSwitchEvent {
 }
 
 /// Map preset types
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub enum MapPreset {
     #[default]
     Flat,
@@ -55,6 +58,14 @@ pub enum MapPreset {
     DebugWhiteSand,
 }
 
+impl MapPreset {
+    /// Maps that despawn the default armies and spawn their own (scenario/debug setups).
+    /// Flat and RollingHills keep the current units and just reposition them.
+    pub fn starts_new_battle(&self) -> bool {
+        matches!(self, MapPreset::FirebaseDelta | MapPreset::Debug | MapPreset::DebugWhiteSand)
+    }
+}
+
 /// Terrain configuration resource
 #[derive(Resource)]
 pub struct TerrainConfig {
@@ -67,6 +78,8 @@ pub struct TerrainConfig {
     pub pending_heightmap: Option<Handle<Image>>,
     /// Target map preset to switch to once heightmap loads
     pub pending_map: Option<MapPreset>,
+    /// Map switch requested by GameCommand::SwitchMap, applied by terrain_map_switching
+    pub requested_map: Option<MapPreset>,
 }
 
 impl Default for TerrainConfig {
@@ -79,6 +92,7 @@ impl Default for TerrainConfig {
             seed: 42,
             pending_heightmap: None,
             pending_map: None,
+            requested_map: None,
         }
     }
 }
@@ -462,21 +476,13 @@ pub fn spawn_initial_terrain(
     info!("Flat ground spawned at Y=-1.0");
 }
 
-/// System to switch between map presets using 1-3 keys
+/// System to request a map switch with F1-F5
 /// Skips map switching when explosion debug mode is active (0 -> 1/2/3)
-fn terrain_map_switching(
+fn map_switch_hotkey_system(
     keys: Res<ButtonInput<KeyCode>>,
-    mut config: ResMut<TerrainConfig>,
-    terrain_query: Query<Entity, With<TerrainMarker>>,
-    skybox_entity_query: Query<Entity, With<MapSkybox>>,
-    camera_query: Query<Entity, With<crate::types::RtsCamera>>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut images: ResMut<Assets<Image>>,
-    asset_server: Res<AssetServer>,
-    mut map_switch_events: EventWriter<MapSwitchEvent>,
+    config: Res<TerrainConfig>,
     debug_mode: Res<crate::objective::ExplosionDebugMode>,
+    mut game_commands: EventWriter<crate::game_command::GameCommand>,
 ) {
     // Skip map switching when debug mode is active (digit keys used for debug spawns)
     if debug_mode.explosion_mode {
@@ -497,6 +503,26 @@ fn terrain_map_switching(
         None
     };
 
+    if let Some(preset) = new_preset.filter(|&preset| preset != config.current_map) {
+        game_commands.write(crate::game_command::GameCommand::SwitchMap(preset));
+    }
+}
+
+/// System to switch to the requested map preset
+pub fn terrain_map_switching(
+    mut config: ResMut<TerrainConfig>,
+    terrain_query: Query<Entity, With<TerrainMarker>>,
+    skybox_entity_query: Query<Entity, With<MapSkybox>>,
+    camera_query: Query<Entity, With<crate::types::RtsCamera>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    mut map_switch_events: EventWriter<MapSwitchEvent>,
+) {
+    let new_preset = config.requested_map.take();
+
     if let Some(preset) = new_preset {
         if config.current_map != preset {
             info!("Switching terrain to: {:?}", preset);
@@ -751,19 +777,28 @@ pub fn handle_map_switch_units(
     mut tower_mut_query: Query<(&mut Transform, &mut Health), (With<UplinkTower>, Without<BattleDroid>)>,
     mut squad_manager: ResMut<SquadManager>,
     mut game_state: ResMut<GameState>,
+    mut sim_rng: ResMut<SimRng>,
+    mut sim_tick: ResMut<SimTick>,
+    mut artillery_state: Option<ResMut<crate::artillery::ArtilleryState>>,
+    (scenario_library, scenario_state): (Option<Res<ScenarioLibrary>>, Option<Res<ScenarioState>>),
 ) {
     for event in map_switch_events.read() {
         info!("Repositioning units for map: {:?}", event.new_map);
 
-        // Reset game state
-        game_state.team_a_tower_destroyed = false;
-        game_state.team_b_tower_destroyed = false;
-        game_state.game_ended = false;
-        game_state.winner = None;
+        // Reset game state (towers, points, battle clock)
+        *game_state = GameState::default();
         info!("Game state reset");
 
-        // For FirebaseDelta and Debug maps, despawn all default units and towers
-        if event.new_map == MapPreset::FirebaseDelta || event.new_map == MapPreset::Debug || event.new_map == MapPreset::DebugWhiteSand {
+        // For FirebaseDelta, Debug and scenario maps, despawn all default units and towers
+        if map_starts_new_battle(event.new_map, scenario_library.as_deref(), scenario_state.as_deref()) {
+            // New battle: restart the simulation clock and RNG so it plays out the same
+            // for a given seed no matter what happened before (replays rely on this)
+            *sim_rng = SimRng::new(sim_rng.seed);
+            sim_tick.0 = 0;
+            if let Some(artillery_state) = artillery_state.as_mut() {
+                artillery_state.pending_shells.clear();
+            }
+
             // Collect entities to despawn first (can't despawn while iterating with mutable query)
             let droid_entities: Vec<Entity> = droid_query.iter().map(|(e, _, _, _)| e).collect();
             let despawned_units = droid_entities.len();
@@ -843,6 +878,8 @@ pub fn spawn_debug_map_entities(
     shield_config: Res<crate::shield::ShieldConfig>,
     heightmap: Res<TerrainHeightmap>,
     mut squad_manager: ResMut<SquadManager>,
+    mut archetypes: ResMut<UnitArchetypes>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
     for event in map_switch_events.read() {
         // Only spawn entities for Debug map (Map 4), not DebugWhiteSand (Map 5)
@@ -855,14 +892,8 @@ pub fn spawn_debug_map_entities(
         // Create tower mesh
         let tower_mesh = crate::procedural_meshes::create_uplink_tower_mesh(&mut meshes);
 
-        // Team B tower material (defending team - red/orange)
-        let team_b_tower_material = materials.add(StandardMaterial {
-            base_color: Color::srgb(0.9, 0.3, 0.2),
-            emissive: Color::srgb(0.6, 0.2, 0.1).into(),
-            metallic: 0.8,
-            perceptual_roughness: 0.2,
-            ..default()
-        });
+        // Team B tower material (defending team)
+        let team_b_tower_material = crate::objective::create_tower_material(&mut materials, Team::B);
 
         // Spawn Team B tower at center (this is the target for Team A to attack)
         let tower_pos = Vec3::new(0.0, 0.0, 0.0);
@@ -880,6 +911,7 @@ pub fn spawn_debug_map_entities(
                 is_primary: true,
             },
             Health::new(crate::constants::TOWER_MAX_HEALTH),
+            ArmorClass::Structure,
             BuildingCollider { radius: 5.0 },
         ));
 
@@ -895,9 +927,6 @@ pub fn spawn_debug_map_entities(
             &shield_config,
         );
 
-        // Create droid mesh for squads
-        let droid_mesh = crate::setup::create_droid_mesh(&mut meshes);
-
         // Create unit materials for Team A (attackers)
         let team_a_materials = crate::setup::create_team_materials(&mut materials, Team::A);
 
@@ -911,13 +940,16 @@ pub fn spawn_debug_map_entities(
             crate::setup::spawn_single_squad(
                 &mut commands,
                 &mut squad_manager,
-                &droid_mesh,
+                &mut archetypes,
+                DEFAULT_UNIT_ARCHETYPE,
+                &mut meshes,
                 &team_a_materials,
                 &mut materials,
                 Team::A,
                 *pos,
                 *facing,
                 &heightmap,
+                &mut *sim_rng,
             );
         }
 
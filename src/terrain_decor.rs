@@ -1,7 +1,7 @@
 //! Terrain decoration module - rocks, vegetation, and atmospheric effects
 //!
 //! This module adds visual interest to the desert terrain with:
-//! - Procedural rock/boulder placement using noise-based clustering
+//! - Procedural rock/boulder placement using noise-based clustering (larger rocks give cover)
 //! - Blowing sand particles for atmospheric effect
 
 use bevy::prelude::*;
@@ -11,6 +11,8 @@ use noise::{NoiseFn, Perlin};
 use rand::prelude::*;
 use std::f32::consts::PI;
 
+use crate::constants::COVER_MIN_ROCK_SCALE;
+use crate::cover::Cover;
 use crate::terrain::{TerrainHeightmap, MapPreset, TerrainConfig, MapSwitchEvent, TerrainMarker};
 
 // ============================================================================
@@ -240,6 +242,7 @@ fn spawn_terrain_decorations(
     mut materials: ResMut<Assets<StandardMaterial>>,
     config: Res<TerrainConfig>,
     heightmap: Res<TerrainHeightmap>,
+    sim_rng: Res<crate::types::SimRng>,
     mut map_switch_events: EventReader<MapSwitchEvent>,
     rock_query: Query<Entity, With<Rock>>,
     sand_emitter_query: Query<Entity, With<SandEmitter>>,
@@ -316,8 +319,10 @@ fn spawn_terrain_decorations(
         .collect();
 
     // Spawn rocks using noise-based placement
-    let noise = Perlin::new(42);
-    let mut rng = StdRng::seed_from_u64(42);
+    // Rock layout follows the simulation seed (reseeded on every map switch so it
+    // doesn't depend on how much of the SimRng stream has been used)
+    let noise = Perlin::new(sim_rng.seed as u32);
+    let mut rng = StdRng::seed_from_u64(sim_rng.seed);
     let half_size = config.terrain_size / 2.0;
 
     let mut rocks_spawned = 0;
@@ -360,7 +365,7 @@ fn spawn_terrain_decorations(
         // Place rock above terrain - rock mesh is centered, so offset by half the scale
         // to have rock sit on terrain surface
         let rock_y_offset = scale * 0.3; // Rocks are slightly flattened on bottom
-        commands.spawn((
+        let mut rock = commands.spawn((
             Mesh3d(rock_meshes[mesh_idx].clone()),
             MeshMaterial3d(rock_material.clone()),
             Transform::from_xyz(x, y + rock_y_offset, z)
@@ -370,6 +375,9 @@ fn spawn_terrain_decorations(
             TerrainDecoration, // Separate marker for decoration cleanup
             Name::new("Rock"),
         ));
+        if scale >= COVER_MIN_ROCK_SCALE {
+            rock.insert(Cover { radius: scale });
+        }
 
         rocks_spawned += 1;
     }
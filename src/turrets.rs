@@ -7,7 +7,8 @@ use bevy::render::render_resource::{
 };
 use bevy::render::alpha::AlphaMode;
 use crate::types::*;
-use crate::terrain::{TerrainHeightmap, MapSwitchEvent, MapPreset};
+use crate::terrain::{TerrainHeightmap, MapSwitchEvent};
+use crate::scenario::{map_starts_new_battle, ScenarioLibrary, ScenarioState};
 use crate::procedural_meshes::{
     create_mg_turret_base_mesh, create_mg_turret_assembly_mesh, create_mg_turret_barrel_mesh,
     create_turret_base_mesh, create_turret_rotating_assembly_mesh,
@@ -73,6 +74,7 @@ fn spawn_mg_turret_internal(
     x: f32,
     z: f32,
     terrain_height: f32,
+    team: Team,
 ) -> Entity {
     let turret_world_pos = Vec3::new(x, terrain_height, z);
 
@@ -108,9 +110,10 @@ fn spawn_mg_turret_internal(
         Mesh3d(base_mesh),
         MeshMaterial3d(base_material),
         Transform::from_translation(turret_world_pos),
-        TurretBase { team: Team::A },
+        TurretBase { team },
         BuildingCollider { radius: 3.0 }, // Smaller collision radius
         Health::new(MG_TURRET_HEALTH),
+        ArmorClass::Structure,
     )).id();
 
     // Spawn rotating assembly entity (child of base) - handles YAW rotation
@@ -119,7 +122,7 @@ fn spawn_mg_turret_internal(
         MeshMaterial3d(gun_material),
         Transform::from_xyz(0.0, 1.0, 0.0), // Mounted on top of base
         BattleDroid {
-            team: Team::A,
+            team,
             march_speed: 0.0,
             spawn_position: turret_world_pos,
             target_position: turret_world_pos,
@@ -178,7 +181,7 @@ pub fn spawn_mg_turret(
     let z = 10.0;
     let terrain_height = heightmap.sample_height(x, z);
 
-    let base_entity = spawn_mg_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height);
+    let base_entity = spawn_mg_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height, Team::A);
     info!("Spawned MG turret BASE ENTITY {:?} at position ({}, {}, {})", base_entity, x, terrain_height, z);
 }
 
@@ -191,6 +194,7 @@ fn spawn_heavy_turret_internal(
     x: f32,
     z: f32,
     terrain_height: f32,
+    team: Team,
 ) -> Entity {
     let turret_world_pos = Vec3::new(x, terrain_height, z);
 
@@ -218,9 +222,10 @@ fn spawn_heavy_turret_internal(
         Mesh3d(base_mesh),
         MeshMaterial3d(base_material),
         Transform::from_translation(turret_world_pos),
-        TurretBase { team: Team::A },
+        TurretBase { team },
         BuildingCollider { radius: 4.0 }, // Collision radius for laser blocking
         Health::new(HEAVY_TURRET_HEALTH),
+        ArmorClass::Structure,
     )).id();
 
     // Spawn rotating assembly entity (child)
@@ -229,7 +234,7 @@ fn spawn_heavy_turret_internal(
         MeshMaterial3d(gun_material),
         Transform::from_xyz(0.0, 2.7, 0.0), // Mounted on top of base
         BattleDroid {
-            team: Team::A,
+            team,
             march_speed: 0.0,
             spawn_position: turret_world_pos,
             target_position: turret_world_pos,
@@ -270,7 +275,7 @@ pub fn spawn_functional_turret(
     let z = 30.0;
     let terrain_height = heightmap.sample_height(x, z);
 
-    spawn_heavy_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height);
+    spawn_heavy_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height, Team::A);
     info!("Spawned functional turret at position ({}, {}, {})", x, terrain_height, z);
 }
 
@@ -282,6 +287,7 @@ pub fn respawn_turrets_on_map_switch(
     heightmap: Res<TerrainHeightmap>,
     mut map_switch_events: EventReader<MapSwitchEvent>,
     turret_base_query: Query<Entity, With<TurretBase>>,
+    (scenario_library, scenario_state): (Option<Res<ScenarioLibrary>>, Option<Res<ScenarioState>>),
 ) {
     // Only process if there's a map switch event
     let Some(event) = map_switch_events.read().next() else {
@@ -293,8 +299,8 @@ pub fn respawn_turrets_on_map_switch(
         commands.entity(base_entity).despawn();
     }
 
-    // Don't respawn turrets for Debug, FirebaseDelta, or DebugWhiteSand maps
-    if event.new_map == MapPreset::Debug || event.new_map == MapPreset::FirebaseDelta || event.new_map == MapPreset::DebugWhiteSand {
+    // Don't respawn turrets for Debug, FirebaseDelta, DebugWhiteSand or scenario maps
+    if map_starts_new_battle(event.new_map, scenario_library.as_deref(), scenario_state.as_deref()) {
         info!("Skipping turret respawn for {:?} map", event.new_map);
         return;
     }
@@ -305,14 +311,14 @@ pub fn respawn_turrets_on_map_switch(
     let mg_x = 10.0;
     let mg_z = 10.0;
     let mg_height = heightmap.sample_height(mg_x, mg_z);
-    spawn_mg_turret_internal(&mut commands, &mut meshes, &mut materials, mg_x, mg_z, mg_height);
+    spawn_mg_turret_internal(&mut commands, &mut meshes, &mut materials, mg_x, mg_z, mg_height, Team::A);
     info!("Respawned MG turret at ({}, {}, {})", mg_x, mg_height, mg_z);
 
     // Spawn heavy turret at new terrain height
     let heavy_x = 30.0;
     let heavy_z = 30.0;
     let heavy_height = heightmap.sample_height(heavy_x, heavy_z);
-    spawn_heavy_turret_internal(&mut commands, &mut meshes, &mut materials, heavy_x, heavy_z, heavy_height);
+    spawn_heavy_turret_internal(&mut commands, &mut meshes, &mut materials, heavy_x, heavy_z, heavy_height, Team::A);
     info!("Respawned heavy turret at ({}, {}, {})", heavy_x, heavy_height, heavy_z);
 }
 
@@ -321,27 +327,52 @@ pub fn respawn_turrets_on_map_switch(
 // ============================================================================
 
 /// Spawn an MG turret at the specified world position
-/// Used by scenario systems to place turrets programmatically
+/// Used by scenario systems and production to place turrets programmatically
 /// Returns the turret base entity for tracking/undo
 pub fn spawn_mg_turret_at(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     position: Vec3,
+    team: Team,
 ) -> Entity {
-    spawn_mg_turret_internal(commands, meshes, materials, position.x, position.z, position.y)
+    spawn_mg_turret_internal(commands, meshes, materials, position.x, position.z, position.y, team)
 }
 
 /// Spawn a heavy turret at the specified world position
-/// Used by scenario systems to place turrets programmatically
+/// Used by scenario systems and production to place turrets programmatically
 /// Returns the turret base entity for tracking/undo
 pub fn spawn_heavy_turret_at(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     position: Vec3,
+    team: Team,
 ) -> Entity {
-    spawn_heavy_turret_internal(commands, meshes, materials, position.x, position.z, position.y)
+    spawn_heavy_turret_internal(commands, meshes, materials, position.x, position.z, position.y, team)
+}
+
+/// Placeable turret types
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum TurretType {
+    MachineGun,
+    Heavy,
+}
+
+/// Spawn a turret of the given type at the specified world position
+/// Returns the turret base entity for tracking/undo
+pub fn spawn_turret_at(
+    turret_type: TurretType,
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    position: Vec3,
+    team: Team,
+) -> Entity {
+    match turret_type {
+        TurretType::MachineGun => spawn_mg_turret_at(commands, meshes, materials, position, team),
+        TurretType::Heavy => spawn_heavy_turret_at(commands, meshes, materials, position, team),
+    }
 }
 
 /// Debug system to toggle turrets on/off (M=MG, H=Heavy) when debug mode is active
@@ -371,7 +402,7 @@ pub fn debug_turret_toggle_system(
             let x = 10.0;
             let z = 10.0;
             let terrain_height = heightmap.sample_height(x, z);
-            spawn_mg_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height);
+            spawn_mg_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height, Team::A);
             info!("🔫 MG turret ENABLED");
         } else {
             // Despawn MG turret - find the base that has MG turret as child
@@ -396,7 +427,7 @@ pub fn debug_turret_toggle_system(
             let x = 30.0;
             let z = 30.0;
             let terrain_height = heightmap.sample_height(x, z);
-            spawn_heavy_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height);
+            spawn_heavy_turret_internal(&mut commands, &mut meshes, &mut materials, x, z, terrain_height, Team::A);
             info!("🔫 Heavy turret ENABLED");
         } else {
             // Despawn Heavy turret - find the base that has heavy turret (no MgTurret) as child
@@ -512,11 +543,33 @@ pub fn update_turret_health_bars(
     }
 }
 
-/// System to check turret health and trigger explosion + despawn when dead
+/// A turret base was destroyed and despawned; its explosion is still to be shown
+#[derive(Event)]
+pub struct TurretDestroyedEvent {
+    pub position: Vec3,
+}
+
+/// Despawn turrets whose base has no health left (FixedUpdate, before the win condition).
+/// The explosion is spawned by turret_death_system from the TurretDestroyedEvent.
+pub fn turret_destruction_system(
+    mut commands: Commands,
+    turret_query: Query<(Entity, &Transform, &Health), With<TurretBase>>,
+    mut destroyed_events: EventWriter<TurretDestroyedEvent>,
+) {
+    for (entity, transform, health) in turret_query.iter() {
+        if health.is_dead() {
+            info!("Turret destroyed at {:?}", transform.translation);
+            destroyed_events.write(TurretDestroyedEvent { position: transform.translation });
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// System to play the explosion of every turret destroyed this frame
 /// Uses WFX billboard explosion for better visual quality than hanabi particles
 pub fn turret_death_system(
     mut commands: Commands,
-    turret_query: Query<(Entity, &Transform, &Health), With<TurretBase>>,
+    mut destroyed_events: EventReader<TurretDestroyedEvent>,
     audio_assets: Res<crate::types::AudioAssets>,
     mut ducking: ResMut<crate::types::ExplosionDucking>,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -524,37 +577,29 @@ pub fn turret_death_system(
     mut smoke_materials: ResMut<Assets<crate::wfx_materials::SmokeScrollMaterial>>,
     asset_server: Res<AssetServer>,
 ) {
-    for (entity, transform, health) in turret_query.iter() {
-        if health.current <= 0.0 {
-            let position = transform.translation;
-            info!("Turret destroyed at {:?}", position);
-
-            // Play explosion sound. Use the punchy ground_explosion clips, not
-            // distant_explosion1: that clip is ~7dB quieter at the source (pre-muffled
-            // "distant" recording) and gets masked by MG fire at any volume setting.
-            let mut rng = rand::thread_rng();
-            commands.spawn((
-                AudioPlayer::new(audio_assets.get_random_ground_explosion_sound(&mut rng)),
-                PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::Linear(crate::constants::VOLUME_TURRET_EXPLOSION)),
-            ));
-
-            // Duck the gunfire bed so the explosion reads through the mix
-            ducking.timer = crate::constants::EXPLOSION_DUCK_DURATION;
-
-            // Spawn WFX billboard explosion (28 flames + 50 dot sparkles + 5 glow sparkles + 1 center glow)
-            crate::wfx_spawn::spawn_turret_wfx_explosion(
-                &mut commands,
-                &mut meshes,
-                &mut additive_materials,
-                &mut smoke_materials,
-                &asset_server,
-                position + Vec3::Y * 2.0,
-                1.5,
-            );
-
-            // Despawn the turret
-            commands.entity(entity).despawn();
-        }
+    for event in destroyed_events.read() {
+        // Play explosion sound. Use the punchy ground_explosion clips, not
+        // distant_explosion1: that clip is ~7dB quieter at the source (pre-muffled
+        // "distant" recording) and gets masked by MG fire at any volume setting.
+        let mut rng = rand::thread_rng();
+        commands.spawn((
+            AudioPlayer::new(audio_assets.get_random_ground_explosion_sound(&mut rng)),
+            PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::Linear(crate::constants::VOLUME_TURRET_EXPLOSION)),
+        ));
+
+        // Duck the gunfire bed so the explosion reads through the mix
+        ducking.timer = crate::constants::EXPLOSION_DUCK_DURATION;
+
+        // Spawn WFX billboard explosion (28 flames + 50 dot sparkles + 5 glow sparkles + 1 center glow)
+        crate::wfx_spawn::spawn_turret_wfx_explosion(
+            &mut commands,
+            &mut meshes,
+            &mut additive_materials,
+            &mut smoke_materials,
+            &asset_server,
+            event.position + Vec3::Y * 2.0,
+            1.5,
+        );
     }
 }
 
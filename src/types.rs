@@ -1,26 +1,291 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use rand::Rng;
+use crate::constants::{
+    BATTLEFIELD_SIZE, CRITICAL_ACCURACY_MULTIPLIER, CRITICAL_HEALTH_FRACTION, CRITICAL_SPEED_MULTIPLIER, DAMAGE_MATRIX,
+    DEFAULT_UNIT_ARCHETYPE, MULTI_TEAM_SPAWN_SCALE, SUPPRESSION_ACCURACY_PENALTY, SUPPRESSION_SPEED_PENALTY,
+    VETERANCY_ACCURACY_PER_RANK, VETERANCY_FIRE_RATE_PER_RANK, VETERANCY_RANK_KILLS,
+    WOUNDED_ACCURACY_MULTIPLIER, WOUNDED_HEALTH_FRACTION, WOUNDED_SPEED_MULTIPLIER,
+};
 
-#[derive(Component, Clone, Copy, PartialEq, Debug)]
-pub enum Team {
-    A,
-    B,
+/// Team ID. A and B are the classic two sides; up to Team::MAX teams can take part,
+/// and Diplomacy decides which of them are allied.
+/// Files store it as Team(n); loading rejects IDs of Team::MAX and above.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "TeamId")]
+pub struct Team(pub u8);
+
+/// Team as read from a save or replay, before the range check
+#[derive(serde::Deserialize)]
+#[serde(rename = "Team")]
+struct TeamId(u8);
+
+impl TryFrom<TeamId> for Team {
+    type Error = String;
+
+    fn try_from(TeamId(id): TeamId) -> Result<Self, Self::Error> {
+        if (id as usize) < Team::MAX {
+            Ok(Team(id))
+        } else {
+            Err(format!("team {} out of range (at most {} teams)", id, Team::MAX))
+        }
+    }
 }
 
 impl Team {
+    pub const A: Team = Team(0);
+    pub const B: Team = Team(1);
+    pub const C: Team = Team(2);
+    pub const D: Team = Team(3);
+
+    /// Most teams a battle supports (one per side of the battlefield)
+    pub const MAX: usize = 4;
+    pub const ALL: [Team; Team::MAX] = [Team::A, Team::B, Team::C, Team::D];
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Display letter ('A'..'D')
+    pub fn letter(self) -> char {
+        (b'A' + self.0) as char
+    }
+
+    /// Parse a team letter (case-insensitive)
+    pub fn from_letter(letter: char) -> Option<Team> {
+        Team::ALL.into_iter().find(|team| team.letter() == letter.to_ascii_uppercase())
+    }
+
+    /// Colors for this team's units, towers, shields and UI
+    pub fn palette(self) -> &'static TeamPalette {
+        &TEAM_PALETTES[self.index() % Team::MAX]
+    }
+
     /// Returns the primary color for this team's shields/UI
     pub fn shield_color(&self) -> Color {
-        match self {
-            Team::A => Color::srgb(0.2, 0.6, 1.0),  // Cyan/blue
-            Team::B => Color::srgb(1.0, 0.4, 0.2),  // Orange/red
+        self.palette().shield
+    }
+
+    /// Direction from the battlefield center to this team's side: A west, B east, C north, D south
+    pub fn home_direction(self) -> Vec3 {
+        match self.0 % Team::MAX as u8 {
+            0 => Vec3::new(-1.0, 0.0, 0.0),
+            1 => Vec3::new(1.0, 0.0, 0.0),
+            2 => Vec3::new(0.0, 0.0, -1.0),
+            _ => Vec3::new(0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Where this team's army is centered in a battle of `team_count` teams. With more than two
+    /// teams the armies stand further out so neighbouring sides don't overlap.
+    pub fn home_position(self, team_count: usize) -> Vec3 {
+        let scale = if team_count > 2 { MULTI_TEAM_SPAWN_SCALE } else { 1.0 };
+        self.home_direction() * BATTLEFIELD_SIZE / 2.0 * scale
+    }
+}
+
+impl std::fmt::Debug for Team {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// Per-team colors
+pub struct TeamPalette {
+    pub name: &'static str,
+    pub shield: Color,
+    pub body: Color,
+    pub head: Color,
+    pub body_metallic: f32,     // Head is 0.1 less metallic
+    pub commander_body: Color,
+    pub commander_head: Color,
+    pub commander_marker: Color,
+    pub laser: Color,           // Bolts and tracers (HDR, > 1.0 glows)
+    pub laser_emissive: Color,
+    pub tower: Color,
+    pub tower_emissive: Color,
+}
+
+const TEAM_PALETTES: [TeamPalette; Team::MAX] = [
+    // A: blue-gray droids with gold commanders
+    TeamPalette {
+        name: "Blue",
+        shield: Color::srgb(0.2, 0.6, 1.0),
+        body: Color::srgb(0.7, 0.7, 0.8),
+        head: Color::srgb(0.8, 0.6, 0.4),
+        body_metallic: 0.3,
+        commander_body: Color::srgb(0.9, 0.8, 0.4),
+        commander_head: Color::srgb(1.0, 0.9, 0.5),
+        commander_marker: Color::srgb(1.0, 1.0, 0.0),
+        laser: Color::srgb(0.0, 2.0, 0.0),
+        laser_emissive: Color::srgb(0.0, 1.0, 0.0),
+        tower: Color::srgb(0.2, 0.6, 0.9),
+        tower_emissive: Color::srgb(0.1, 0.3, 0.6),
+    },
+    // B: white droids with orange commanders
+    TeamPalette {
+        name: "Red",
+        shield: Color::srgb(1.0, 0.4, 0.2),
+        body: Color::srgb(0.9, 0.9, 0.95),
+        head: Color::srgb(0.95, 0.95, 1.0),
+        body_metallic: 0.4,
+        commander_body: Color::srgb(0.9, 0.5, 0.3),
+        commander_head: Color::srgb(1.0, 0.6, 0.4),
+        commander_marker: Color::srgb(1.0, 0.0, 0.0),
+        laser: Color::srgb(2.0, 0.0, 0.0),
+        laser_emissive: Color::srgb(1.0, 0.0, 0.0),
+        tower: Color::srgb(0.9, 0.3, 0.2),
+        tower_emissive: Color::srgb(0.6, 0.2, 0.1),
+    },
+    // C: olive droids with green commanders
+    TeamPalette {
+        name: "Green",
+        shield: Color::srgb(0.3, 0.9, 0.3),
+        body: Color::srgb(0.55, 0.6, 0.45),
+        head: Color::srgb(0.65, 0.7, 0.5),
+        body_metallic: 0.3,
+        commander_body: Color::srgb(0.4, 0.8, 0.3),
+        commander_head: Color::srgb(0.6, 1.0, 0.5),
+        commander_marker: Color::srgb(0.0, 1.0, 0.3),
+        laser: Color::srgb(0.4, 2.0, 0.4),
+        laser_emissive: Color::srgb(0.2, 1.0, 0.2),
+        tower: Color::srgb(0.3, 0.8, 0.3),
+        tower_emissive: Color::srgb(0.1, 0.5, 0.1),
+    },
+    // D: dark droids with violet commanders
+    TeamPalette {
+        name: "Purple",
+        shield: Color::srgb(0.7, 0.3, 1.0),
+        body: Color::srgb(0.35, 0.3, 0.4),
+        head: Color::srgb(0.5, 0.45, 0.55),
+        body_metallic: 0.4,
+        commander_body: Color::srgb(0.6, 0.3, 0.9),
+        commander_head: Color::srgb(0.8, 0.5, 1.0),
+        commander_marker: Color::srgb(0.8, 0.0, 1.0),
+        laser: Color::srgb(1.5, 0.0, 2.0),
+        laser_emissive: Color::srgb(0.75, 0.0, 1.0),
+        tower: Color::srgb(0.6, 0.3, 0.9),
+        tower_emissive: Color::srgb(0.4, 0.1, 0.6),
+    },
+];
+
+/// Teams in the battle and who is allied with whom. Teams sharing an alliance never
+/// target, shoot or block each other and win together; everyone else is hostile.
+#[derive(Resource, Clone, Debug)]
+pub struct Diplomacy {
+    /// Teams taking part, in spawn order
+    pub teams: Vec<Team>,
+    /// Alliance ID per team index
+    alliance: [u8; Team::MAX],
+}
+
+impl Default for Diplomacy {
+    /// Team A vs Team B
+    fn default() -> Self {
+        Self::from_alliances(&[vec![Team::A], vec![Team::B]])
+    }
+}
+
+impl Diplomacy {
+    /// One alliance per group, e.g. [[A, C], [B, D]] for 2v2 or [[A], [B], [C]] for a free-for-all
+    pub fn from_alliances(alliances: &[Vec<Team>]) -> Self {
+        let mut teams = Vec::new();
+        let mut alliance = [0u8; Team::MAX];
+        for (id, group) in alliances.iter().enumerate() {
+            for &team in group {
+                if !teams.contains(&team) {
+                    teams.push(team);
+                    alliance[team.index()] = id as u8;
+                }
+            }
+        }
+        Self { teams, alliance }
+    }
+
+    /// The alliance groups (inverse of from_alliances)
+    pub fn alliances(&self) -> Vec<Vec<Team>> {
+        let mut groups: Vec<(u8, Vec<Team>)> = Vec::new();
+        for &team in &self.teams {
+            let id = self.alliance[team.index()];
+            match groups.iter_mut().find(|(group_id, _)| *group_id == id) {
+                Some((_, group)) => group.push(team),
+                None => groups.push((id, vec![team])),
+            }
         }
+        groups.into_iter().map(|(_, group)| group).collect()
+    }
+
+    pub fn is_allied(&self, a: Team, b: Team) -> bool {
+        self.alliance[a.index()] == self.alliance[b.index()]
+    }
+
+    pub fn is_hostile(&self, a: Team, b: Team) -> bool {
+        !self.is_allied(a, b)
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum FormationType {
-    Rectangle,
+    Rectangle, // 10 wide x 5 deep, the balanced default
+    Line,      // 25 wide x 2 deep: every rifle can fire, thin against blasts
+    Column,    // 5 wide x 10 deep: fast to steer, few rifles face the front
+    Wedge,     // Arrowhead: spearhead assault, concentrated fire
+    Square,    // Hollow square facing all directions
+    Loose,     // Skirmish order: double spacing with jitter, hard to hit and to blast
+}
+
+impl FormationType {
+    /// Hotkey cycle order
+    pub const ALL: [FormationType; 6] = [
+        FormationType::Rectangle,
+        FormationType::Line,
+        FormationType::Column,
+        FormationType::Wedge,
+        FormationType::Square,
+        FormationType::Loose,
+    ];
+
+    /// Next formation in the hotkey cycle
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&f| f == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Multiplier on the hit chance of shots fired by this formation
+    pub fn accuracy_modifier(self) -> f32 {
+        match self {
+            FormationType::Rectangle => 1.0,
+            FormationType::Line => 1.1,    // Clear field of fire for everyone
+            FormationType::Column => 0.85, // Rear ranks fire past their own men
+            FormationType::Wedge => 1.05,
+            FormationType::Square => 0.9,  // Only one face engages a given enemy
+            FormationType::Loose => 0.95,  // No volley discipline
+        }
+    }
+
+    /// Multiplier on the hit chance of shots fired at this formation
+    pub fn exposure_modifier(self) -> f32 {
+        match self {
+            FormationType::Rectangle => 1.0,
+            FormationType::Line => 0.95,
+            FormationType::Column => 1.1,  // Dense target down its length
+            FormationType::Wedge => 1.05,
+            FormationType::Square => 1.0,
+            FormationType::Loose => 0.8,   // Spread out, easy to miss
+        }
+    }
+
+    /// Multiplier on area damage (artillery, explosions) taken by this formation
+    pub fn area_damage_modifier(self) -> f32 {
+        match self {
+            FormationType::Rectangle => 1.0,
+            FormationType::Line => 0.75,
+            FormationType::Column => 1.25,
+            FormationType::Wedge => 1.1,
+            FormationType::Square => 1.2,  // Packed ranks around a hollow center
+            FormationType::Loose => 0.5,
+        }
+    }
 }
 
 #[derive(Component)]
@@ -34,7 +299,18 @@ pub struct Squad {
     pub center_position: Vec3,
     pub facing_direction: Vec3,
     pub target_facing_direction: Vec3,  // Direction to rotate toward (for smooth rotation)
-    pub target_position: Vec3,
+    pub target_position: Vec3,          // Current waypoint (the destination when path is empty)
+    pub path: VecDeque<Vec3>,           // Remaining nav waypoints after target_position
+    pub previous_formation: FormationType, // Shape being morphed away from
+    pub formation_blend: f32,           // 0 = previous_formation, 1 = formation_type (reshape progress)
+    pub archetype: String,              // Unit archetype ID (see archetype.rs)
+    pub average_health: f32,            // Mean health fraction of living members (squad_health_system)
+    pub morale: f32,                    // 1 = steady, MORALE_BREAK_THRESHOLD or less = breaks (squad_morale_system)
+    pub rout_timer: Option<f32>,        // Seconds spent routing; Some while the squad runs for spawn
+    pub casualties: u32,                // Losses not yet counted against morale
+    pub commander_lost: bool,           // Commander died since morale was last updated
+    pub suppression: f32,               // 0 = free to act, 1 = pinned (see squad_suppression_system)
+    pub kills: u32,                     // Enemy units killed by the squad; sets its veterancy rank
 }
 
 impl Squad {
@@ -49,8 +325,96 @@ impl Squad {
             facing_direction,
             target_facing_direction: facing_direction,  // Initially same as facing
             target_position: center_position,
+            path: VecDeque::new(),
+            previous_formation: FormationType::Rectangle,
+            formation_blend: 1.0,
+            archetype: DEFAULT_UNIT_ARCHETYPE.to_string(),
+            average_health: 1.0,
+            morale: 1.0,
+            rout_timer: None,
+            casualties: 0,
+            commander_lost: false,
+            suppression: 0.0,
+            kills: 0,
         }
     }
+
+    /// Veterancy rank earned from kills (0 = Regular, up to VETERANCY_RANK_KILLS.len())
+    pub fn rank(&self) -> u8 {
+        VETERANCY_RANK_KILLS.iter().filter(|&&kills| self.kills >= kills).count() as u8
+    }
+
+    pub fn rank_name(&self) -> &'static str {
+        match self.rank() {
+            0 => "Regular",
+            1 => "Veteran",
+            2 => "Elite",
+            _ => "Heroic",
+        }
+    }
+
+    /// Kills still needed for the next rank (None at the top rank)
+    pub fn kills_to_next_rank(&self) -> Option<u32> {
+        VETERANCY_RANK_KILLS.get(self.rank() as usize).map(|&kills| kills - self.kills)
+    }
+
+    /// Count a kill for the squad; returns true when it earns a new rank
+    pub fn record_kill(&mut self) -> bool {
+        let rank = self.rank();
+        self.kills += 1;
+        self.rank() > rank
+    }
+
+    /// Accuracy multiplier from veterancy
+    pub fn veterancy_accuracy_modifier(&self) -> f32 {
+        1.0 + self.rank() as f32 * VETERANCY_ACCURACY_PER_RANK
+    }
+
+    /// Fire interval multiplier from veterancy (below 1 = faster fire)
+    pub fn veterancy_fire_interval_modifier(&self) -> f32 {
+        1.0 - self.rank() as f32 * VETERANCY_FIRE_RATE_PER_RANK
+    }
+
+    /// Pile incoming fire onto the squad (capped at fully pinned)
+    pub fn suppress(&mut self, amount: f32) {
+        self.suppression = (self.suppression + amount).min(1.0);
+    }
+
+    /// Accuracy multiplier from suppression
+    pub fn suppression_accuracy_modifier(&self) -> f32 {
+        1.0 - self.suppression * SUPPRESSION_ACCURACY_PENALTY
+    }
+
+    /// March speed multiplier from suppression
+    pub fn suppression_speed_modifier(&self) -> f32 {
+        1.0 - self.suppression * SUPPRESSION_SPEED_PENALTY
+    }
+
+    /// Routing squads run for their spawn point and ignore orders
+    pub fn is_routing(&self) -> bool {
+        self.rout_timer.is_some()
+    }
+
+    /// Start reshaping into a new formation (members move to their new slots gradually)
+    pub fn set_formation(&mut self, formation: FormationType) {
+        if formation == self.formation_type {
+            return;
+        }
+        self.previous_formation = self.formation_type;
+        self.formation_type = formation;
+        self.formation_blend = 0.0;
+    }
+
+    /// Where the squad's current move ends
+    pub fn destination(&self) -> Vec3 {
+        self.path.back().copied().unwrap_or(self.target_position)
+    }
+
+    /// Drop any remaining waypoints and head straight for `position`
+    pub fn set_direct_target(&mut self, position: Vec3) {
+        self.path.clear();
+        self.target_position = position;
+    }
     
     pub fn add_member(&mut self, entity: Entity) {
         if self.members.len() < crate::constants::SQUAD_SIZE {
@@ -129,6 +493,8 @@ pub struct LaserProjectile {
     pub lifetime: f32,
     pub team: Team, // Track which team fired this laser
     pub origin: Vec3, // Where the shot originated (for accuracy calculations)
+    pub weapon: WeaponType, // Resolved against the target's ArmorClass on impact
+    pub damage: f32,  // Base damage before the armor multiplier
 }
 
 #[derive(Component)]
@@ -165,12 +531,18 @@ impl AudioAssets {
 // (avoids per-shot allocation which causes lag spikes with rapid-fire weapons)
 #[derive(Resource)]
 pub struct LaserAssets {
-    pub team_a_material: Handle<StandardMaterial>,
-    pub team_b_material: Handle<StandardMaterial>,
+    pub team_materials: Vec<Handle<StandardMaterial>>, // Indexed by Team::index
     pub mg_laser_mesh: Handle<Mesh>,  // Shorter bolts for MG turret
     pub hitscan_tracer_mesh: Handle<Mesh>,  // Tracer bolt for hitscan weapons
 }
 
+impl LaserAssets {
+    /// Bolt/tracer material in the team's color
+    pub fn material(&self, team: Team) -> Handle<StandardMaterial> {
+        self.team_materials[team.index() % self.team_materials.len()].clone()
+    }
+}
+
 // Spatial grid for collision optimization
 #[derive(Resource, Default)]
 pub struct SpatialGrid {
@@ -273,9 +645,11 @@ impl SquadManager {
         if let Some(&squad_id) = self.entity_to_squad.get(&entity) {
             if let Some(squad) = self.squads.get_mut(&squad_id) {
                 squad.remove_member(entity);
+                squad.casualties += 1;
                 
                 // If this was the commander, promote someone else
                 if squad.commander == Some(entity) {
+                    squad.commander_lost = true;
                     squad.promote_new_commander();
                 }
             }
@@ -286,6 +660,14 @@ impl SquadManager {
         }
     }
     
+    /// The squads in `squad_ids` that will take orders (routing squads won't)
+    pub fn obedient_squads(&self, squad_ids: &[u32]) -> Vec<u32> {
+        squad_ids.iter()
+            .copied()
+            .filter(|&id| self.get_squad(id).is_some_and(|squad| !squad.is_routing()))
+            .collect()
+    }
+
     pub fn get_squad(&self, squad_id: u32) -> Option<&Squad> {
         self.squads.get(&squad_id)
     }
@@ -298,8 +680,99 @@ impl SquadManager {
     pub fn get_unit_squad_id(&self, entity: Entity) -> Option<u32> {
         self.entity_to_squad.get(&entity).copied()
     }
+
+    /// Accuracy multiplier from the suppression of a unit's squad (1 for turrets and towers)
+    pub fn unit_suppression_accuracy(&self, entity: Entity) -> f32 {
+        self.get_unit_squad_id(entity)
+            .and_then(|squad_id| self.get_squad(squad_id))
+            .map_or(1.0, |squad| squad.suppression_accuracy_modifier())
+    }
+
+    /// Credit a kill to a squad, announcing any rank it earns
+    pub fn credit_kill(&mut self, squad_id: u32) {
+        if let Some(squad) = self.squads.get_mut(&squad_id) {
+            if squad.record_kill() {
+                info!("Squad {} earned the rank of {} ({} kills)", squad_id, squad.rank_name(), squad.kills);
+            }
+        }
+    }
+
+    /// Accuracy multiplier from the veterancy of a unit's squad (1 for turrets and towers)
+    pub fn unit_veterancy_accuracy(&self, entity: Entity) -> f32 {
+        self.get_unit_squad_id(entity)
+            .and_then(|squad_id| self.get_squad(squad_id))
+            .map_or(1.0, |squad| squad.veterancy_accuracy_modifier())
+    }
+
+    /// Fire interval multiplier from the veterancy of a unit's squad (1 for turrets and towers)
+    pub fn unit_veterancy_fire_interval(&self, entity: Entity) -> f32 {
+        self.get_unit_squad_id(entity)
+            .and_then(|squad_id| self.get_squad(squad_id))
+            .map_or(1.0, |squad| squad.veterancy_fire_interval_modifier())
+    }
+
+    /// Formation of the squad a unit belongs to (None for turrets and towers)
+    pub fn unit_formation(&self, entity: Entity) -> Option<FormationType> {
+        self.get_unit_squad_id(entity)
+            .and_then(|squad_id| self.get_squad(squad_id))
+            .map(|squad| squad.formation_type)
+    }
+}
+
+// ===== SIMULATION RNG =====
+
+/// Seeded random source for everything that affects the outcome of a battle
+/// (hit rolls, area damage, spawn jitter, rock placement). Audio and purely
+/// visual randomness keep using thread_rng so they never shift the simulation.
+/// Running twice with the same `--seed` reproduces the same battle.
+#[derive(Resource)]
+pub struct SimRng {
+    pub seed: u64,
+    rng: rand::rngs::StdRng,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        use rand::SeedableRng;
+        Self {
+            seed,
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
 }
 
+impl Default for SimRng {
+    /// Random seed, logged so an interesting run can be replayed with `--seed`
+    fn default() -> Self {
+        let seed = rand::random::<u64>();
+        info!("Simulation seed: {} (pass --seed {} to reproduce)", seed, seed);
+        Self::new(seed)
+    }
+}
+
+impl rand::RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Fixed simulation ticks since the current battle started. Reset when a map switch
+/// starts a new battle, so tick-based cadences and replay timestamps line up across runs.
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct SimTick(pub u64);
+
 // ===== OBJECTIVE SYSTEM COMPONENTS =====
 
 #[derive(Component)]
@@ -324,10 +797,48 @@ impl Health {
         self.current <= 0.0
     }
     
-    #[allow(dead_code)]
     pub fn health_percentage(&self) -> f32 {
         self.current / self.max
     }
+
+    pub fn wound_state(&self) -> WoundState {
+        let fraction = self.health_percentage();
+        if fraction <= CRITICAL_HEALTH_FRACTION {
+            WoundState::Critical
+        } else if fraction <= WOUNDED_HEALTH_FRACTION {
+            WoundState::Wounded
+        } else {
+            WoundState::Healthy
+        }
+    }
+}
+
+/// How badly hurt an infantry unit is (from its Health fraction)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WoundState {
+    Healthy,
+    Wounded,
+    Critical,
+}
+
+impl WoundState {
+    /// March speed multiplier
+    pub fn speed_modifier(self) -> f32 {
+        match self {
+            WoundState::Healthy => 1.0,
+            WoundState::Wounded => WOUNDED_SPEED_MULTIPLIER,
+            WoundState::Critical => CRITICAL_SPEED_MULTIPLIER,
+        }
+    }
+
+    /// Accuracy multiplier when firing
+    pub fn accuracy_modifier(self) -> f32 {
+        match self {
+            WoundState::Healthy => 1.0,
+            WoundState::Wounded => WOUNDED_ACCURACY_MULTIPLIER,
+            WoundState::Critical => CRITICAL_ACCURACY_MULTIPLIER,
+        }
+    }
 }
 
 #[derive(Component)]
@@ -372,16 +883,35 @@ pub enum FiringMode {
     Continuous, // Continuously fires and switches targets ("mowing down")
 }
 
-/// Weapon types for future extensibility (kinetic, anti-armor, etc.)
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
-#[allow(dead_code)]
+/// Weapon families; each hit is scaled by DAMAGE_MATRIX against the target's ArmorClass
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub enum WeaponType {
     #[default]
     PulsedLaser,   // Infantry default - hitscan with visual tracer
-    // Future weapon types:
-    // Kinetic,    // Armor penetration bonus
-    // AntiArmor,  // High damage vs heavy units
-    // HeavyLaser, // Slow projectile (turrets)
+    Kinetic,       // MG turrets - armor penetration is poor, infantry falls fast
+    AntiArmor,     // High damage vs vehicles and structures
+    HeavyLaser,    // Heavy turrets - hard-hitting bolts
+}
+
+impl WeaponType {
+    /// Multiplier from DAMAGE_MATRIX for this weapon against an armor class
+    pub fn damage_multiplier(self, armor: ArmorClass) -> f32 {
+        DAMAGE_MATRIX[self as usize][armor as usize]
+    }
+
+    /// Damage one hit deals after the weapon/armor matrix
+    pub fn damage_against(self, base_damage: f32, armor: ArmorClass) -> f32 {
+        base_damage * self.damage_multiplier(armor)
+    }
+}
+
+/// How a unit or building soaks up hits (column of DAMAGE_MATRIX)
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub enum ArmorClass {
+    #[default]
+    Infantry,
+    Vehicle,
+    Structure,  // Towers, turrets, bunkers
 }
 
 /// Visual tracer for hitscan weapons - damage is instant, this is cosmetic only
@@ -436,29 +966,70 @@ pub struct AudioFadeOut {
 // PendingExplosion and ExplosionEffect moved to src/explosion_system.rs
 
 // Game state management
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GameState {
-    pub team_a_tower_destroyed: bool,
-    pub team_b_tower_destroyed: bool,
+    /// Teams whose primary tower has fallen (secondary objectives don't count)
+    pub destroyed_towers: Vec<Team>,
     pub game_ended: bool,
+    /// Winning team (with several allied survivors, the first of them in Diplomacy order)
     pub winner: Option<Team>,
+    /// How the battle was decided, e.g. "reached 500 points"
+    pub end_reason: Option<String>,
+    /// Victory points per team (Team::index), from capture points and secondary objectives
+    pub points: [f32; Team::MAX],
+    /// Simulated seconds since the battle started (time limit)
+    pub battle_time: f32,
 }
 
 impl GameState {
+    /// Record a fallen primary tower
     pub fn tower_destroyed(&mut self, team: Team) {
-        match team {
-            Team::A => self.team_a_tower_destroyed = true,
-            Team::B => self.team_b_tower_destroyed = true,
+        if !self.destroyed_towers.contains(&team) {
+            self.destroyed_towers.push(team);
         }
+    }
 
-        if !self.game_ended {
-            self.game_ended = true;
-            // Winner is the opposing team
-            self.winner = Some(match team {
-                Team::A => Team::B,
-                Team::B => Team::A,
-            });
-        }
+    /// Whether only one alliance still holds towers, and the first of its teams (None when no tower stands)
+    pub fn last_alliance_standing(&self, diplomacy: &Diplomacy) -> Option<Option<Team>> {
+        let standing: Vec<Team> = diplomacy.teams.iter()
+            .copied()
+            .filter(|team| !self.destroyed_towers.contains(team))
+            .collect();
+        let decided = standing.iter().all(|&team| diplomacy.is_allied(team, standing[0]));
+        decided.then(|| standing.first().copied())
+    }
+
+    /// Victory points of `team` and its allies
+    pub fn alliance_points(&self, team: Team, diplomacy: &Diplomacy) -> f32 {
+        diplomacy.teams.iter()
+            .filter(|&&other| diplomacy.is_allied(other, team))
+            .map(|other| self.points[other.index()])
+            .sum()
+    }
+
+    /// End the battle
+    pub fn end(&mut self, winner: Option<Team>, reason: impl Into<String>) {
+        self.game_ended = true;
+        self.winner = winner;
+        self.end_reason = Some(reason.into());
+    }
+}
+
+/// How a battle can be won (see win_condition_system); set by --points-to-win, --time-limit and
+/// --no-tower-victory. Scenarios bring their own victory rules and only keep the tower rule.
+#[derive(Resource, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WinConditions {
+    /// The last alliance holding primary towers wins
+    pub towers: bool,
+    /// The first alliance to reach this many victory points wins
+    pub points_to_win: Option<f32>,
+    /// After this many seconds the alliance with the most points (then most towers standing) wins
+    pub time_limit: Option<f32>,
+}
+
+impl Default for WinConditions {
+    fn default() -> Self {
+        Self { towers: true, points_to_win: None, time_limit: None }
     }
 }
 
@@ -491,6 +1062,7 @@ pub struct RagdollDeath {
 pub struct AreaDamageEvent {
     pub position: Vec3,
     pub scale: f32,  // Multiplier for damage radii
+    pub source_squad: Option<u32>,  // Squad credited with kills (None for off-map artillery)
 }
 
 // ===== UNIT COLLISION SYSTEM =====
@@ -503,7 +1075,7 @@ pub struct UnitMass(pub f32);
 // ===== MOVEMENT MODES (EaW-style) =====
 
 /// Movement mode for units - controls behavior when engaging enemies
-#[derive(Component, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum MovementMode {
     #[default]
     Move,        // March to destination, fire on the move (default behavior)